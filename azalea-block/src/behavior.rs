@@ -2,6 +2,12 @@ pub struct BlockBehavior {
     pub has_collision: bool,
     pub friction: f32,
     pub jump_factor: f32,
+    /// How long it takes to mine this block. This is 0 for blocks that break
+    /// instantly, and -1 for blocks that can't be broken in survival mode.
+    pub destroy_time: f32,
+    /// Whether the block only drops items when it's mined with the correct
+    /// tool. This also makes mining it without the correct tool much slower.
+    pub requires_correct_tool_for_drops: bool,
 }
 
 impl Default for BlockBehavior {
@@ -10,6 +16,8 @@ impl Default for BlockBehavior {
             has_collision: true,
             friction: 0.6,
             jump_factor: 1.0,
+            destroy_time: 0.,
+            requires_correct_tool_for_drops: false,
         }
     }
 }
@@ -32,4 +40,16 @@ impl BlockBehavior {
         self.jump_factor = jump_factor;
         self
     }
+
+    #[inline]
+    pub fn strength(mut self, destroy_time: f32) -> Self {
+        self.destroy_time = destroy_time;
+        self
+    }
+
+    #[inline]
+    pub fn requires_correct_tool_for_drops(mut self) -> Self {
+        self.requires_correct_tool_for_drops = true;
+        self
+    }
 }
@@ -58,6 +58,13 @@ impl BlockState {
     pub fn is_valid_state(state_id: u32) -> bool {
         state_id <= Self::max_state()
     }
+
+    /// Whether the block state is air, cave air, or void air.
+    pub fn is_air(&self) -> bool {
+        *self == Self::AIR
+            || *self == BlockState::from(azalea_registry::Block::CaveAir)
+            || *self == BlockState::from(azalea_registry::Block::VoidAir)
+    }
 }
 
 impl TryFrom<u32> for BlockState {
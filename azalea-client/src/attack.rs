@@ -0,0 +1,368 @@
+use azalea_core::{GameMode, Vec3, AABB};
+use azalea_inventory::ItemSlot;
+use azalea_physics::clip::{BlockShapeType, ClipContext, FluidPickType};
+use azalea_protocol::packets::game::{
+    serverbound_interact_packet::{self, InteractionHand, ServerboundInteractPacket},
+    serverbound_swing_packet::ServerboundSwingPacket,
+};
+use azalea_world::{
+    entity::{Attributes, EyeHeight, MinecraftEntityId, Physics, Position},
+    Instance,
+};
+use bevy_app::{App, CoreSchedule, IntoSystemAppConfig, Plugin};
+use bevy_ecs::prelude::*;
+use log::warn;
+
+use crate::{
+    inventory::InventoryComponent,
+    local_player::{handle_send_packet_event, LocalGameMode},
+    Client, LocalPlayer,
+};
+
+/// A plugin that allows clients to attack and right click entities.
+pub struct AttackPlugin;
+impl Plugin for AttackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AttackEvent>()
+            .add_event::<EntityInteractEvent>()
+            .add_systems(
+                (handle_attack_event, handle_entity_interact_event)
+                    .chain()
+                    .before(handle_send_packet_event),
+            )
+            .add_system(increment_attack_strength_ticker.in_schedule(CoreSchedule::FixedUpdate));
+    }
+}
+
+impl Client {
+    /// Attack (left click) an entity.
+    ///
+    /// The attack only happens if the entity is within reach and there isn't a
+    /// block in the way. Note that this doesn't look at the entity, and that
+    /// attacks do less damage if you attack again before
+    /// [`Self::has_attack_cooldown`] is false.
+    pub fn attack(&mut self, entity: Entity) {
+        self.ecs.lock().send_event(AttackEvent {
+            entity: self.entity,
+            target: entity,
+        });
+    }
+
+    /// Right click an entity with the given hand, like for trading with
+    /// villagers or riding horses.
+    ///
+    /// Like [`Self::attack`], this only works if the entity is within reach.
+    pub fn interact_entity(&mut self, entity: Entity, hand: InteractionHand) {
+        self.ecs.lock().send_event(EntityInteractEvent {
+            entity: self.entity,
+            target: entity,
+            hand,
+        });
+    }
+
+    /// Whether attacking right now would do less than full damage because we
+    /// attacked or switched items too recently.
+    pub fn has_attack_cooldown(&self) -> bool {
+        self.attack_strength_scale() < 1.
+    }
+
+    /// How strong an attack would be right now, from 0 to 1. Attacks do full
+    /// damage (and can do critical hits and sweeps) once this is 1.
+    pub fn attack_strength_scale(&self) -> f32 {
+        let mut ecs = self.ecs.lock();
+        let (ticker, attributes) = self.query::<(&AttackStrengthTicker, &Attributes)>(&mut ecs);
+        attack_strength_scale(ticker, attributes, 0.)
+    }
+}
+
+/// Attack an entity if it's within reach, and swing our arm.
+pub struct AttackEvent {
+    /// The local player entity that's attacking.
+    pub entity: Entity,
+    /// The entity that's being attacked.
+    pub target: Entity,
+}
+
+/// Right click an entity if it's within reach, and swing our arm.
+pub struct EntityInteractEvent {
+    /// The local player entity that's interacting.
+    pub entity: Entity,
+    /// The entity that's being interacted with.
+    pub target: Entity,
+    pub hand: InteractionHand,
+}
+
+/// A component that keeps track of how long it's been since we last attacked
+/// or switched items, which is what the strength of our attacks depends on.
+#[derive(Component, Clone, Debug, Default)]
+pub struct AttackStrengthTicker {
+    /// The number of ticks since we last attacked or switched items.
+    pub ticks: u32,
+    /// The item we were holding last tick, so we can tell when we switch items.
+    last_held_item: ItemSlot,
+}
+
+/// The number of ticks it takes for attacks to get back to full strength
+/// after attacking.
+pub fn attack_strength_delay(attributes: &Attributes) -> f32 {
+    (1. / attributes.attack_speed.calculate() * 20.) as f32
+}
+
+/// How strong an attack would be, from 0 to 1. `partial_tick` is how far we
+/// are into the current tick, vanilla uses 0.5 when attacking.
+pub fn attack_strength_scale(
+    ticker: &AttackStrengthTicker,
+    attributes: &Attributes,
+    partial_tick: f32,
+) -> f32 {
+    ((ticker.ticks as f32 + partial_tick) / attack_strength_delay(attributes)).clamp(0., 1.)
+}
+
+fn increment_attack_strength_ticker(
+    mut query: Query<(&mut AttackStrengthTicker, &InventoryComponent)>,
+) {
+    for (mut ticker, inventory) in &mut query {
+        ticker.ticks += 1;
+        let held_item = inventory.held_item();
+        if held_item != ticker.last_held_item {
+            // vanilla doesn't reset it if only the durability changed, but that's
+            // rare enough that it doesn't matter
+            ticker.ticks = 0;
+            ticker.last_held_item = held_item;
+        }
+    }
+}
+
+/// How far away a player in the given game mode can reach entities.
+pub fn entity_pick_range(game_mode: GameMode) -> f64 {
+    if game_mode == GameMode::Creative {
+        6.
+    } else {
+        3.
+    }
+}
+
+/// Find the point on the bounding box that a player with their eyes at
+/// `eye_position` would hit if they looked at the middle of it.
+///
+/// Returns `None` if that's further away than `pick_range` or if there's a
+/// block in the way.
+pub fn pick_entity(
+    instance: &Instance,
+    eye_position: Vec3,
+    bounding_box: &AABB,
+    pick_range: f64,
+) -> Option<Vec3> {
+    if bounding_box.contains(eye_position.x, eye_position.y, eye_position.z) {
+        return Some(eye_position);
+    }
+    let direction = (bounding_box.get_center() - eye_position).normalize();
+    let end_position = eye_position + direction * pick_range;
+    let location = bounding_box.clip(&eye_position, &end_position)?;
+
+    let block_hit_result = azalea_physics::clip::clip(
+        &instance.chunks,
+        ClipContext {
+            from: eye_position,
+            to: location,
+            block_shape_type: BlockShapeType::Outline,
+            fluid_pick_type: FluidPickType::None,
+        },
+    );
+    if !block_hit_result.miss {
+        // there's a block in the way
+        return None;
+    }
+    Some(location)
+}
+
+/// The part of the [`pick_entity`] logic that's shared between attacking and
+/// interacting. Returns the entity ID of the target and where we'd hit it.
+fn pick_target(
+    local_player: &LocalPlayer,
+    game_mode: &LocalGameMode,
+    position: &Position,
+    eye_height: &EyeHeight,
+    targets: &Query<(&MinecraftEntityId, &Position, &Physics)>,
+    target: Entity,
+) -> Option<(MinecraftEntityId, Vec3)> {
+    let Ok((target_id, target_position, target_physics)) = targets.get(target) else {
+        warn!("Tried to pick an entity that doesn't exist");
+        return None;
+    };
+    let eye_position = position.up(**eye_height as f64);
+    let instance = local_player.world.read();
+    let Some(location) = pick_entity(
+        &instance,
+        eye_position,
+        &target_physics.bounding_box,
+        entity_pick_range(game_mode.current),
+    ) else {
+        warn!(
+            "Entity at {:?} is out of reach from {eye_position:?}",
+            **target_position
+        );
+        return None;
+    };
+    Some((*target_id, location))
+}
+
+fn swing_arm(local_player: &LocalPlayer, hand: InteractionHand) {
+    local_player.write_packet(ServerboundSwingPacket { hand }.get());
+}
+
+fn handle_attack_event(
+    mut events: EventReader<AttackEvent>,
+    mut query: Query<(
+        &LocalPlayer,
+        &LocalGameMode,
+        &Position,
+        &EyeHeight,
+        &mut AttackStrengthTicker,
+    )>,
+    targets: Query<(&MinecraftEntityId, &Position, &Physics)>,
+) {
+    for event in events.iter() {
+        let Ok((local_player, game_mode, position, eye_height, mut ticker)) =
+            query.get_mut(event.entity)
+        else {
+            continue;
+        };
+        let Some((target_id, _)) = pick_target(
+            local_player,
+            game_mode,
+            position,
+            eye_height,
+            &targets,
+            event.target,
+        ) else {
+            continue;
+        };
+
+        local_player.write_packet(
+            ServerboundInteractPacket {
+                entity_id: *target_id,
+                action: serverbound_interact_packet::ActionType::Attack,
+                using_secondary_action: false,
+            }
+            .get(),
+        );
+        if game_mode.current != GameMode::Spectator {
+            ticker.ticks = 0;
+        }
+        swing_arm(local_player, InteractionHand::MainHand);
+    }
+}
+
+fn handle_entity_interact_event(
+    mut events: EventReader<EntityInteractEvent>,
+    query: Query<(&LocalPlayer, &LocalGameMode, &Position, &EyeHeight)>,
+    targets: Query<(&MinecraftEntityId, &Position, &Physics)>,
+) {
+    for event in events.iter() {
+        let Ok((local_player, game_mode, position, eye_height)) = query.get(event.entity) else {
+            continue;
+        };
+        let Some((target_id, location)) = pick_target(
+            local_player,
+            game_mode,
+            position,
+            eye_height,
+            &targets,
+            event.target,
+        ) else {
+            continue;
+        };
+        let Ok((_, target_position, _)) = targets.get(event.target) else {
+            continue;
+        };
+
+        // vanilla sends both of these, the first one is for clicking specific parts of
+        // the entity like armor stands
+        local_player.write_packet(
+            ServerboundInteractPacket {
+                entity_id: *target_id,
+                action: serverbound_interact_packet::ActionType::InteractAt {
+                    location: location - **target_position,
+                    hand: event.hand,
+                },
+                using_secondary_action: false,
+            }
+            .get(),
+        );
+        local_player.write_packet(
+            ServerboundInteractPacket {
+                entity_id: *target_id,
+                action: serverbound_interact_packet::ActionType::Interact { hand: event.hand },
+                using_secondary_action: false,
+            }
+            .get(),
+        );
+        swing_arm(local_player, event.hand);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azalea_core::{BlockPos, ChunkPos};
+    use azalea_world::{
+        entity::attributes::AttributeInstance, Chunk, ChunkStorage, PartialInstance,
+    };
+
+    #[test]
+    fn test_attack_strength_scale() {
+        let mut attributes = Attributes {
+            speed: AttributeInstance::new(0.1),
+            attack_speed: AttributeInstance::new(4.),
+            other: Default::default(),
+        };
+        let mut ticker = AttackStrengthTicker::default();
+        // 4 attacks per second means 5 ticks between attacks
+        assert_eq!(attack_strength_delay(&attributes), 5.);
+        assert_eq!(attack_strength_scale(&ticker, &attributes, 0.), 0.);
+        ticker.ticks = 2;
+        assert_eq!(attack_strength_scale(&ticker, &attributes, 0.5), 0.5);
+        ticker.ticks = 10;
+        assert_eq!(attack_strength_scale(&ticker, &attributes, 0.), 1.);
+
+        // swords have an attack speed of 1.6
+        attributes.attack_speed = AttributeInstance::new(1.6);
+        assert_eq!(attack_strength_scale(&ticker, &attributes, 0.), 0.8);
+    }
+
+    #[test]
+    fn test_pick_entity() {
+        let mut partial_world = PartialInstance::default();
+        let mut chunk_storage = ChunkStorage::default();
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut chunk_storage,
+        );
+        partial_world.chunks.set_block_state(
+            &BlockPos::new(4, 1, 0),
+            azalea_registry::Block::Stone.into(),
+            &mut chunk_storage,
+        );
+        let world = Instance::from(chunk_storage);
+        let bounding_box = AABB {
+            min_x: 2.2,
+            max_x: 2.8,
+            min_y: 1.,
+            max_y: 2.8,
+            min_z: 0.2,
+            max_z: 0.8,
+        };
+
+        let eye_position = Vec3::new(0.5, 1.5 + 1.62, 0.5);
+        let location = pick_entity(&world, eye_position, &bounding_box, 3.).unwrap();
+        assert!((location.x - 2.2).abs() < 1e-6);
+        // too far away
+        assert_eq!(pick_entity(&world, eye_position, &bounding_box, 1.), None);
+
+        // there's a block between us and the entity
+        let eye_position = Vec3::new(5.5, 1.5, 0.5);
+        assert_eq!(pick_entity(&world, eye_position, &bounding_box, 6.), None);
+    }
+}
@@ -1,24 +1,31 @@
 use crate::{
+    attack::{AttackPlugin, AttackStrengthTicker},
     chat::ChatPlugin,
     disconnect::{DisconnectEvent, DisconnectPlugin},
     events::{Event, EventPlugin, LocalPlayerEvents},
-    interact::{CurrentSequenceNumber, InteractPlugin},
+    hud::HudPlugin,
+    interact::{CurrentSequenceNumber, InteractPlugin, LastAckedSequenceNumber},
     inventory::{InventoryComponent, InventoryPlugin},
     local_player::{
         death_event, handle_send_packet_event, update_in_loaded_chunk, GameProfileComponent,
         LocalPlayer, PhysicsState, SendPacketEvent,
     },
+    merchant::MerchantPlugin,
+    mining::MinePlugin,
     movement::{LastSentLookDirection, PlayerMovePlugin},
     packet_handling::{self, PacketHandlerPlugin, PacketReceiver},
     player::retroactively_add_game_profile_component,
+    recipe_book::RecipeBookPlugin,
     respawn::RespawnPlugin,
+    scoreboard::Scoreboard,
     task_pool::TaskPoolPlugin,
+    weather::WeatherPlugin,
     Account, PlayerInfo,
 };
 
 use azalea_auth::{game_profile::GameProfile, sessionserver::ClientSessionServerError};
 use azalea_chat::FormattedText;
-use azalea_core::Vec3;
+use azalea_core::{GameMode, Vec3};
 use azalea_physics::{PhysicsPlugin, PhysicsSet};
 use azalea_protocol::{
     connect::{Connection, ConnectionError},
@@ -147,7 +154,47 @@ impl From<ClientboundPlayerAbilitiesPacket> for PlayerAbilities {
 /// }
 /// # }
 #[derive(Component, Clone, Debug, Deref, DerefMut, Default)]
-pub struct TabList(HashMap<Uuid, PlayerInfo>);
+pub struct TabList {
+    #[deref]
+    #[deref_mut]
+    pub players: HashMap<Uuid, PlayerInfo>,
+    /// The text that's shown above the list of players.
+    pub header: FormattedText,
+    /// The text that's shown below the list of players.
+    pub footer: FormattedText,
+}
+
+impl TabList {
+    /// The maximum number of players that vanilla shows in the tab list.
+    pub const MAX_SHOWN_PLAYERS: usize = 80;
+
+    /// Get a player in the tab list by their username.
+    pub fn get_by_name(&self, name: &str) -> Option<&PlayerInfo> {
+        self.players.values().find(|info| info.profile.name == name)
+    }
+
+    /// The players in the order that they're shown in the tab list.
+    ///
+    /// Like in vanilla, spectators are at the end, then players are sorted by
+    /// the name of their team and then by their username. Only the first
+    /// [`Self::MAX_SHOWN_PLAYERS`] are included.
+    pub fn sorted(&self, scoreboard: &Scoreboard) -> Vec<&PlayerInfo> {
+        let mut players = self.players.values().collect::<Vec<_>>();
+        players.sort_by_cached_key(|info| {
+            let team_name = scoreboard
+                .player_team(&info.profile.name)
+                .map(|team| team.name.clone())
+                .unwrap_or_default();
+            (
+                info.gamemode == GameMode::Spectator,
+                team_name,
+                info.profile.name.to_lowercase(),
+            )
+        });
+        players.truncate(Self::MAX_SHOWN_PLAYERS);
+        players
+    }
+}
 
 /// An error that happened while joining the server.
 #[derive(Error, Debug)]
@@ -301,6 +348,8 @@ impl Client {
             client_information: ClientInformation::default(),
             tab_list: TabList::default(),
             current_sequence_number: CurrentSequenceNumber::default(),
+            last_acked_sequence_number: LastAckedSequenceNumber::default(),
+            attack_strength_ticker: AttackStrengthTicker::default(),
             last_sent_direction: LastSentLookDirection::default(),
             abilities: PlayerAbilities::default(),
             _local: Local,
@@ -560,6 +609,8 @@ pub struct JoinedClientBundle {
     pub client_information: ClientInformation,
     pub tab_list: TabList,
     pub current_sequence_number: CurrentSequenceNumber,
+    pub last_acked_sequence_number: LastAckedSequenceNumber,
+    pub attack_strength_ticker: AttackStrengthTicker,
     pub last_sent_direction: LastSentLookDirection,
     pub abilities: PlayerAbilities,
     pub _local: Local,
@@ -709,6 +760,12 @@ impl PluginGroup for DefaultPlugins {
             .add(DisconnectPlugin)
             .add(PlayerMovePlugin)
             .add(InteractPlugin)
+            .add(AttackPlugin)
+            .add(HudPlugin)
+            .add(WeatherPlugin)
+            .add(RecipeBookPlugin)
+            .add(MerchantPlugin)
+            .add(MinePlugin)
             .add(RespawnPlugin)
             .add(TickBroadcastPlugin)
     }
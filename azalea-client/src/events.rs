@@ -3,6 +3,8 @@
 
 use std::sync::Arc;
 
+use azalea_chat::FormattedText;
+use azalea_core::{ChunkPos, GameMode};
 use azalea_protocol::packets::game::{
     clientbound_player_combat_kill_packet::ClientboundPlayerCombatKillPacket, ClientboundGamePacket,
 };
@@ -14,10 +16,17 @@ use tokio::sync::mpsc;
 
 use crate::{
     chat::{ChatPacket, ChatReceivedEvent},
+    hud::{ActionBarEvent, BossBarUpdate, BossBarUpdateEvent, Titles, TitlesUpdateEvent},
     packet_handling::{
-        AddPlayerEvent, DeathEvent, KeepAliveEvent, PacketEvent, RemovePlayerEvent,
-        UpdatePlayerEvent,
+        AddPlayerEvent, ChunkLoadedEvent, ChunkUnloadedEvent, DeathEvent, DemoEvent, DemoMessage,
+        GameModeChangeEvent, KeepAliveEvent, PacketEvent, RemovePlayerEvent, TabListHeaderEvent,
+        UpdatePlayerEvent, WinGameEvent,
     },
+    scoreboard::{
+        DisplayObjectiveUpdate, ObjectiveUpdate, ScoreUpdate, ScoreboardUpdate,
+        ScoreboardUpdateEvent, TeamUpdate,
+    },
+    weather::{WeatherChange, WeatherChangeEvent},
     PlayerInfo,
 };
 
@@ -83,10 +92,51 @@ pub enum Event {
     /// A player was updated in the tab list (gamemode, display
     /// name, or latency changed).
     UpdatePlayer(PlayerInfo),
+    /// The header or footer of the tab list changed.
+    UpdateTabListHeader {
+        header: FormattedText,
+        footer: FormattedText,
+    },
+    /// A chunk was loaded, either because the server sent it or because
+    /// another client in the same world already had it.
+    ChunkLoaded(ChunkPos),
+    /// A chunk was unloaded because it's outside of the view distance.
+    ChunkUnloaded(ChunkPos),
     /// The client player died in-game.
     Death(Option<Arc<ClientboundPlayerCombatKillPacket>>),
     /// A `KeepAlive` packet was sent by the server.
     KeepAlive(u64),
+    /// An objective was added, removed, or changed in the scoreboard.
+    UpdateObjective(ObjectiveUpdate),
+    /// A score was set or removed in the scoreboard.
+    UpdateScore(ScoreUpdate),
+    /// The objective shown in a scoreboard display slot (like the sidebar)
+    /// changed.
+    SetDisplayObjective(DisplayObjectiveUpdate),
+    /// A team was added, removed, or changed, or players joined or left a
+    /// team.
+    UpdateTeam(TeamUpdate),
+    /// A boss bar was added, removed, or changed.
+    UpdateBossBar(BossBarUpdate),
+    /// The title, subtitle, or their timings changed. This contains all of
+    /// them after the change.
+    UpdateTitles(Titles),
+    /// The server showed some text in the action bar above the hotbar.
+    ActionBar(FormattedText),
+    /// It started or stopped raining or thundering.
+    Weather(WeatherChange),
+    /// The server changed our game mode.
+    GameModeChange {
+        previous: GameMode,
+        current: GameMode,
+    },
+    /// The server showed us a message that's only for the demo version of the
+    /// game.
+    Demo(DemoMessage),
+    /// We went through the end portal after killing the ender dragon. `azalea`
+    /// respawns automatically after this unless its `AutoRespawnPlugin` is
+    /// disabled.
+    WinGame { show_credits: bool },
 }
 
 /// A component that contains an event sender for events that are only
@@ -107,8 +157,19 @@ impl Plugin for EventPlugin {
             .add_system(add_player_listener)
             .add_system(update_player_listener)
             .add_system(remove_player_listener)
+            .add_system(tab_list_header_listener)
+            .add_system(chunk_loaded_listener)
+            .add_system(chunk_unloaded_listener)
             .add_system(death_listener)
             .add_system(keepalive_listener)
+            .add_system(scoreboard_listener)
+            .add_system(boss_bar_listener)
+            .add_system(titles_listener)
+            .add_system(action_bar_listener)
+            .add_system(weather_listener)
+            .add_system(game_mode_change_listener)
+            .add_system(demo_listener)
+            .add_system(win_game_listener)
             .add_system(tick_listener.in_schedule(CoreSchedule::FixedUpdate));
     }
 }
@@ -194,6 +255,51 @@ fn remove_player_listener(
     }
 }
 
+fn tab_list_header_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<TabListHeaderEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive tab list events");
+        local_player_events
+            .send(Event::UpdateTabListHeader {
+                header: event.header.clone(),
+                footer: event.footer.clone(),
+            })
+            .unwrap();
+    }
+}
+
+fn chunk_loaded_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<ChunkLoadedEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive chunk events");
+        local_player_events
+            .send(Event::ChunkLoaded(event.pos))
+            .unwrap();
+    }
+}
+
+fn chunk_unloaded_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<ChunkUnloadedEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive chunk events");
+        local_player_events
+            .send(Event::ChunkUnloaded(event.pos))
+            .unwrap();
+    }
+}
+
 fn death_listener(query: Query<&LocalPlayerEvents>, mut events: EventReader<DeathEvent>) {
     for event in events.iter() {
         if let Ok(local_player_events) = query.get(event.entity) {
@@ -214,3 +320,109 @@ fn keepalive_listener(query: Query<&LocalPlayerEvents>, mut events: EventReader<
             .unwrap();
     }
 }
+
+fn scoreboard_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<ScoreboardUpdateEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive scoreboard events");
+        let event = match event.update.clone() {
+            ScoreboardUpdate::Objective(update) => Event::UpdateObjective(update),
+            ScoreboardUpdate::Score(update) => Event::UpdateScore(update),
+            ScoreboardUpdate::DisplayObjective(update) => Event::SetDisplayObjective(update),
+            ScoreboardUpdate::Team(update) => Event::UpdateTeam(update),
+        };
+        local_player_events.send(event).unwrap();
+    }
+}
+
+fn boss_bar_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<BossBarUpdateEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive boss bar events");
+        local_player_events
+            .send(Event::UpdateBossBar(event.update.clone()))
+            .unwrap();
+    }
+}
+
+fn titles_listener(query: Query<&LocalPlayerEvents>, mut events: EventReader<TitlesUpdateEvent>) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive title events");
+        local_player_events
+            .send(Event::UpdateTitles(event.titles.clone()))
+            .unwrap();
+    }
+}
+
+fn action_bar_listener(query: Query<&LocalPlayerEvents>, mut events: EventReader<ActionBarEvent>) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive action bar events");
+        local_player_events
+            .send(Event::ActionBar(event.text.clone()))
+            .unwrap();
+    }
+}
+
+fn weather_listener(query: Query<&LocalPlayerEvents>, mut events: EventReader<WeatherChangeEvent>) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive weather events");
+        local_player_events
+            .send(Event::Weather(event.change))
+            .unwrap();
+    }
+}
+
+fn game_mode_change_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<GameModeChangeEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive game mode events");
+        local_player_events
+            .send(Event::GameModeChange {
+                previous: event.previous,
+                current: event.current,
+            })
+            .unwrap();
+    }
+}
+
+fn demo_listener(query: Query<&LocalPlayerEvents>, mut events: EventReader<DemoEvent>) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive demo events");
+        local_player_events
+            .send(Event::Demo(event.message))
+            .unwrap();
+    }
+}
+
+fn win_game_listener(query: Query<&LocalPlayerEvents>, mut events: EventReader<WinGameEvent>) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive win game events");
+        local_player_events
+            .send(Event::WinGame {
+                show_credits: event.show_credits,
+            })
+            .unwrap();
+    }
+}
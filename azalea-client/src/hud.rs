@@ -0,0 +1,267 @@
+//! Keep track of the things that servers show on the screen, like boss bars,
+//! titles, and the action bar.
+
+use std::collections::HashMap;
+
+use azalea_chat::FormattedText;
+use azalea_protocol::packets::game::clientbound_boss_event_packet::{
+    BossBarColor, BossBarOverlay, Operation, Properties,
+};
+use bevy_app::{App, CoreSchedule, IntoSystemAppConfig, Plugin};
+use bevy_ecs::{component::Component, entity::Entity, system::Query};
+use derive_more::{Deref, DerefMut};
+use uuid::Uuid;
+
+/// A plugin that makes titles and the action bar disappear after they've been
+/// shown for long enough.
+pub struct HudPlugin;
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BossBarUpdateEvent>()
+            .add_event::<TitlesUpdateEvent>()
+            .add_event::<ActionBarEvent>()
+            .add_system(tick_hud.in_schedule(CoreSchedule::FixedUpdate));
+    }
+}
+
+/// A component with the boss bars that are currently shown, indexed by their
+/// UUID.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut)]
+pub struct BossBars(pub HashMap<Uuid, BossBar>);
+
+#[derive(Clone, Debug)]
+pub struct BossBar {
+    pub name: FormattedText,
+    /// How full the bar is, from 0 to 1.
+    pub progress: f32,
+    pub color: BossBarColor,
+    /// Whether the bar is split into notches.
+    pub overlay: BossBarOverlay,
+    pub properties: Properties,
+}
+
+impl BossBars {
+    /// Update the boss bars from a `BossEvent` packet. Returns `None` if the
+    /// packet was for a boss bar that doesn't exist.
+    pub fn apply_operation(&mut self, id: Uuid, operation: &Operation) -> Option<BossBarUpdate> {
+        match operation {
+            Operation::Add(add) => {
+                self.insert(
+                    id,
+                    BossBar {
+                        name: add.name.clone(),
+                        progress: add.progress,
+                        color: add.style.color,
+                        overlay: add.style.overlay,
+                        properties: add.properties.clone(),
+                    },
+                );
+            }
+            Operation::Remove => {
+                self.remove(&id)?;
+            }
+            Operation::UpdateProgress(progress) => self.get_mut(&id)?.progress = *progress,
+            Operation::UpdateName(name) => self.get_mut(&id)?.name = name.clone(),
+            Operation::UpdateStyle(style) => {
+                let bar = self.get_mut(&id)?;
+                bar.color = style.color;
+                bar.overlay = style.overlay;
+            }
+            Operation::UpdateProperties(properties) => {
+                self.get_mut(&id)?.properties = properties.clone();
+            }
+        }
+        Some(BossBarUpdate {
+            id,
+            bar: self.get(&id).cloned(),
+        })
+    }
+}
+
+/// A boss bar was added, removed, or changed.
+#[derive(Clone, Debug)]
+pub struct BossBarUpdate {
+    pub id: Uuid,
+    /// The boss bar after the change, or `None` if it was removed.
+    pub bar: Option<BossBar>,
+}
+
+/// A component with the title and subtitle that are currently shown.
+#[derive(Component, Clone, Debug)]
+pub struct Titles {
+    pub title: Option<FormattedText>,
+    /// The subtitle is only shown while there's a title.
+    pub subtitle: Option<FormattedText>,
+    /// The number of ticks the title takes to fade in.
+    pub fade_in: u32,
+    /// The number of ticks the title stays on the screen after fading in.
+    pub stay: u32,
+    /// The number of ticks the title takes to fade out.
+    pub fade_out: u32,
+    /// The number of ticks until the title disappears, or 0 if it's not
+    /// shown.
+    pub ticks_remaining: u32,
+}
+
+impl Default for Titles {
+    fn default() -> Self {
+        Self {
+            title: None,
+            subtitle: None,
+            fade_in: 10,
+            stay: 70,
+            fade_out: 20,
+            ticks_remaining: 0,
+        }
+    }
+}
+
+impl Titles {
+    /// Show a new title, which resets how long it's shown for.
+    pub fn set_title(&mut self, title: FormattedText) {
+        self.title = Some(title);
+        self.ticks_remaining = self.fade_in + self.stay + self.fade_out;
+    }
+
+    /// Count down until the title disappears. This is called every tick.
+    pub fn tick(&mut self) {
+        if self.ticks_remaining > 0 {
+            self.ticks_remaining -= 1;
+            if self.ticks_remaining == 0 {
+                self.title = None;
+                self.subtitle = None;
+            }
+        }
+    }
+
+    /// Remove the title and subtitle, and optionally reset the timings back to
+    /// the defaults.
+    pub fn clear(&mut self, reset_times: bool) {
+        self.title = None;
+        self.subtitle = None;
+        self.ticks_remaining = 0;
+        if reset_times {
+            let default = Self::default();
+            self.fade_in = default.fade_in;
+            self.stay = default.stay;
+            self.fade_out = default.fade_out;
+        }
+    }
+}
+
+/// A component with the text that was most recently shown in the action bar
+/// (the text above the hotbar).
+#[derive(Component, Clone, Debug, Default)]
+pub struct ActionBar {
+    pub text: FormattedText,
+    /// The number of ticks until the text disappears, or 0 if it's not shown.
+    pub ticks_remaining: u32,
+}
+
+impl ActionBar {
+    /// How long vanilla shows action bar text for.
+    pub const DURATION_TICKS: u32 = 60;
+
+    pub fn set(&mut self, text: FormattedText) {
+        self.text = text;
+        self.ticks_remaining = Self::DURATION_TICKS;
+    }
+}
+
+/// Sent when one of our boss bars is added, removed, or changed.
+#[derive(Clone, Debug)]
+pub struct BossBarUpdateEvent {
+    pub entity: Entity,
+    pub update: BossBarUpdate,
+}
+
+/// Sent when our title, subtitle, or their timings change. This has the
+/// [`Titles`] after the change.
+#[derive(Clone, Debug)]
+pub struct TitlesUpdateEvent {
+    pub entity: Entity,
+    pub titles: Titles,
+}
+
+/// Sent when the server shows text in our action bar.
+#[derive(Clone, Debug)]
+pub struct ActionBarEvent {
+    pub entity: Entity,
+    pub text: FormattedText,
+}
+
+fn tick_hud(mut query: Query<(&mut Titles, &mut ActionBar)>) {
+    for (mut titles, mut action_bar) in &mut query {
+        titles.tick();
+        action_bar.ticks_remaining = action_bar.ticks_remaining.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azalea_protocol::packets::game::clientbound_boss_event_packet::{AddOperation, Style};
+
+    #[test]
+    fn test_boss_bars() {
+        let mut boss_bars = BossBars::default();
+        let id = Uuid::from_u128(1);
+        let update = boss_bars
+            .apply_operation(
+                id,
+                &Operation::Add(AddOperation {
+                    name: FormattedText::from("Ender Dragon"),
+                    progress: 1.,
+                    style: Style {
+                        color: BossBarColor::Pink,
+                        overlay: BossBarOverlay::Progress,
+                    },
+                    properties: Properties {
+                        darken_screen: false,
+                        play_music: true,
+                        create_world_fog: true,
+                    },
+                }),
+            )
+            .unwrap();
+        assert_eq!(update.bar.unwrap().progress, 1.);
+
+        boss_bars.apply_operation(id, &Operation::UpdateProgress(0.25));
+        assert_eq!(boss_bars[&id].progress, 0.25);
+        assert_eq!(boss_bars[&id].name.to_string(), "Ender Dragon");
+
+        let update = boss_bars.apply_operation(id, &Operation::Remove).unwrap();
+        assert!(update.bar.is_none());
+        assert!(boss_bars.is_empty());
+        // updating a boss bar that doesn't exist does nothing
+        assert!(boss_bars
+            .apply_operation(id, &Operation::UpdateProgress(0.5))
+            .is_none());
+    }
+
+    #[test]
+    fn test_title_timing() {
+        let mut titles = Titles {
+            fade_in: 1,
+            stay: 2,
+            fade_out: 1,
+            ..Default::default()
+        };
+        titles.subtitle = Some(FormattedText::from("subtitle"));
+        titles.set_title(FormattedText::from("title"));
+        assert_eq!(titles.ticks_remaining, 4);
+        for _ in 0..3 {
+            titles.tick();
+        }
+        assert!(titles.title.is_some());
+        titles.tick();
+        assert!(titles.title.is_none());
+        assert!(titles.subtitle.is_none());
+
+        titles.set_title(FormattedText::from("title"));
+        titles.clear(true);
+        assert!(titles.title.is_none());
+        assert!(titles.subtitle.is_none());
+        assert_eq!(titles.stay, 70);
+    }
+}
@@ -1,14 +1,20 @@
-use azalea_block::BlockState;
+use azalea_block::{Block, BlockState};
 use azalea_core::{BlockHitResult, BlockPos, Direction, GameMode, Vec3};
-use azalea_inventory::{ItemSlot, ItemSlotData};
+use azalea_inventory::{operations::SwapClick, ItemSlot, ItemSlotData};
 use azalea_nbt::NbtList;
-use azalea_physics::clip::{BlockShapeType, ClipContext, FluidPickType};
+use azalea_physics::{
+    clip::{BlockShapeType, ClipContext, FluidPickType},
+    collision::{empty_shape, BlockWithShape},
+};
 use azalea_protocol::packets::game::{
     serverbound_interact_packet::InteractionHand,
     serverbound_use_item_on_packet::{BlockHit, ServerboundUseItemOnPacket},
 };
 use azalea_world::{
-    entity::{clamp_look_direction, view_vector, EyeHeight, LookDirection, Position, WorldName},
+    entity::{
+        clamp_look_direction, direction_looking_at, view_vector, EyeHeight, LookDirection,
+        Position, WorldName,
+    },
     Instance, InstanceContainer,
 };
 use bevy_app::{App, Plugin};
@@ -21,11 +27,13 @@ use bevy_ecs::{
 };
 use derive_more::{Deref, DerefMut};
 use log::warn;
+use thiserror::Error;
+use tokio::sync::broadcast::{self, error::RecvError};
 
 use crate::{
-    inventory::InventoryComponent,
+    inventory::{ContainerClickEvent, InventoryComponent, SetSelectedHotbarSlotEvent},
     local_player::{handle_send_packet_event, LocalGameMode},
-    Client, LocalPlayer,
+    Client, LocalPlayer, TickBroadcast,
 };
 
 /// A plugin that allows clients to interact with blocks in the world.
@@ -56,6 +64,186 @@ impl Client {
             position,
         });
     }
+
+    /// Place a block at the given position, and wait until the server tells
+    /// us whether it worked.
+    ///
+    /// This looks for a solid block next to the position to place against,
+    /// turns our head to look at it, and selects the item in the hotbar
+    /// (moving it there from the rest of the inventory if necessary).
+    ///
+    /// ```
+    /// # use azalea_client::Client;
+    /// # use azalea_core::BlockPos;
+    /// # async fn example(bot: &Client) {
+    /// let position = BlockPos::new(0, 70, 0);
+    /// if let Err(e) = bot.place_block(position, azalea_registry::Item::Cobblestone).await {
+    ///     println!("Couldn't place the block: {e}");
+    /// }
+    /// # }
+    /// ```
+    pub async fn place_block(
+        &self,
+        position: BlockPos,
+        item: azalea_registry::Item,
+    ) -> Result<(), PlaceBlockError> {
+        let mut receiver = {
+            let ecs = self.ecs.lock();
+            ecs.resource::<TickBroadcast>().subscribe()
+        };
+
+        let block_hit = {
+            let mut ecs = self.ecs.lock();
+            let (player_position, eye_height, game_mode) =
+                self.query::<(&Position, &EyeHeight, &LocalGameMode)>(&mut ecs);
+            let eye_position = player_position.up(**eye_height as f64);
+            let pick_range = pick_range(game_mode.current);
+
+            let world_lock = self.world();
+            let world = world_lock.read();
+            let Some(block_state) = world.chunks.get_block_state(&position) else {
+                return Err(PlaceBlockError::NoSupport);
+            };
+            if !is_replaceable(block_state) {
+                return Err(PlaceBlockError::Occupied);
+            }
+            let block_hit = find_block_to_place_against(&world, position, eye_position)
+                .ok_or(PlaceBlockError::NoSupport)?;
+            if eye_position.distance_to(&block_hit.location) > pick_range {
+                return Err(PlaceBlockError::OutOfReach);
+            }
+            block_hit
+        };
+
+        self.select_item_in_hotbar(item)?;
+        {
+            let mut ecs = self.ecs.lock();
+            let (player_position, eye_height, mut look_direction) =
+                self.query::<(&Position, &EyeHeight, &mut LookDirection)>(&mut ecs);
+            let eye_position = player_position.up(**eye_height as f64);
+            (look_direction.y_rot, look_direction.x_rot) =
+                direction_looking_at(&eye_position, &block_hit.location);
+        }
+
+        // wait until the server knows that we're looking at the block and holding the
+        // item. if it had to be moved into the hotbar then we also have to wait for
+        // the server to update our inventory.
+        let mut ticks_waiting = 0;
+        loop {
+            wait_one_tick(&mut receiver).await?;
+            let held_item = self
+                .query::<&InventoryComponent>(&mut self.ecs.lock())
+                .held_item();
+            if held_item.kind() == item {
+                break;
+            }
+            ticks_waiting += 1;
+            if ticks_waiting > 20 {
+                return Err(PlaceBlockError::MissingItem);
+            }
+        }
+
+        // send the face that we picked instead of using a BlockInteractEvent, since
+        // that would pick a face again based on where we're looking
+        let sequence_number = {
+            let mut ecs = self.ecs.lock();
+            let (local_player, mut sequence_number) =
+                self.query::<(&LocalPlayer, &mut CurrentSequenceNumber)>(&mut ecs);
+            **sequence_number += 1;
+            local_player.write_packet(
+                ServerboundUseItemOnPacket {
+                    hand: InteractionHand::MainHand,
+                    block_hit,
+                    sequence: **sequence_number,
+                }
+                .get(),
+            );
+            **sequence_number
+        };
+
+        // the server acknowledges the change after it sends the block updates, so once
+        // it's acknowledged the block should be there
+        let mut ticks_waiting = 0;
+        loop {
+            wait_one_tick(&mut receiver).await?;
+            let last_acked = **self.query::<&LastAckedSequenceNumber>(&mut self.ecs.lock());
+            if last_acked >= sequence_number {
+                break;
+            }
+            ticks_waiting += 1;
+            if ticks_waiting > 100 {
+                return Err(PlaceBlockError::Rejected);
+            }
+        }
+        let block_state = self.world().read().chunks.get_block_state(&position);
+        match block_state {
+            Some(block_state) if !is_replaceable(block_state) => Ok(()),
+            _ => Err(PlaceBlockError::Rejected),
+        }
+    }
+
+    /// Select a hotbar slot that has the given item, moving it into the
+    /// selected hotbar slot if it's somewhere else in the inventory.
+    fn select_item_in_hotbar(&self, item: azalea_registry::Item) -> Result<(), PlaceBlockError> {
+        let mut ecs = self.ecs.lock();
+        let inventory = self.query::<&InventoryComponent>(&mut ecs);
+        if inventory.held_item().kind() == item {
+            return Ok(());
+        }
+
+        let menu = inventory.menu();
+        let slots = menu.slots();
+        if let Some(hotbar_slot) = slots[menu.hotbar_slots_range()]
+            .iter()
+            .position(|slot| slot.kind() == item)
+        {
+            ecs.send_event(SetSelectedHotbarSlotEvent {
+                entity: self.entity,
+                slot: hotbar_slot as u8,
+            });
+            return Ok(());
+        }
+
+        let source_slot = menu
+            .player_slots_without_hotbar_range()
+            .find(|&i| slots[i].kind() == item)
+            .ok_or(PlaceBlockError::MissingItem)?;
+        let window_id = inventory.id;
+        let target_slot = inventory.selected_hotbar_slot;
+        ecs.send_event(ContainerClickEvent {
+            entity: self.entity,
+            window_id,
+            operation: SwapClick {
+                source_slot: source_slot as u16,
+                target_slot,
+            }
+            .into(),
+        });
+        Ok(())
+    }
+}
+
+async fn wait_one_tick(receiver: &mut broadcast::Receiver<()>) -> Result<(), PlaceBlockError> {
+    match receiver.recv().await {
+        Ok(()) | Err(RecvError::Lagged(_)) => Ok(()),
+        // the client was disconnected
+        Err(RecvError::Closed) => Err(PlaceBlockError::Rejected),
+    }
+}
+
+/// The reason that [`Client::place_block`] didn't place the block.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceBlockError {
+    #[error("There's already a block at that position")]
+    Occupied,
+    #[error("There are no solid blocks next to the position to place against")]
+    NoSupport,
+    #[error("The block is too far away to place")]
+    OutOfReach,
+    #[error("The item isn't in the inventory")]
+    MissingItem,
+    #[error("The server didn't place the block")]
+    Rejected,
 }
 
 /// Right click a block. The behavior of this depends on the target block,
@@ -73,6 +261,15 @@ pub struct BlockInteractEvent {
 #[derive(Component, Copy, Clone, Debug, Default, Deref, DerefMut)]
 pub struct CurrentSequenceNumber(u32);
 
+/// A component that contains the highest sequence number that the server has
+/// acknowledged with a `BlockChangedAck` packet.
+///
+/// Once this is at least the [`CurrentSequenceNumber`] that we used for a
+/// block change, the server has processed it and sent us the block updates
+/// that came from it.
+#[derive(Component, Copy, Clone, Debug, Default, Deref, DerefMut)]
+pub struct LastAckedSequenceNumber(pub u32);
+
 /// A component that contains the block that the player is currently looking at.
 #[derive(Component, Clone, Debug, Deref, DerefMut)]
 pub struct HitResultComponent(BlockHitResult);
@@ -83,15 +280,27 @@ pub fn handle_block_interact_event(
         &LocalPlayer,
         &mut CurrentSequenceNumber,
         &HitResultComponent,
+        &Position,
+        &EyeHeight,
     )>,
 ) {
     for event in events.iter() {
-        let Ok((local_player, mut sequence_number, hit_result)) = query.get_mut(event.entity) else {
+        let Ok((local_player, mut sequence_number, hit_result, position, eye_height)) =
+            query.get_mut(event.entity)
+        else {
             warn!("Sent BlockInteractEvent for entity that isn't LocalPlayer");
             continue;
         };
 
-        // TODO: check to make sure we're within the world border
+        if !local_player
+            .world
+            .read()
+            .world_border
+            .is_within_bounds(&event.position)
+        {
+            warn!("Tried to interact with a block outside of the world border");
+            continue;
+        }
 
         **sequence_number += 1;
 
@@ -110,11 +319,14 @@ pub fn handle_block_interact_event(
                 inside: hit_result.inside,
             }
         } else {
-            // we're not looking at the block, so make up some numbers
+            // we're not looking at the block, so pretend we clicked the middle of the face
+            // that's closest to our eyes
+            let eye_position = position.up(**eye_height as f64);
+            let direction = Direction::nearest(eye_position - event.position.center());
             BlockHit {
                 block_pos: event.position,
-                direction: Direction::Up,
-                location: event.position.center(),
+                direction,
+                location: face_center(event.position, direction),
                 inside: false,
             }
         };
@@ -147,11 +359,7 @@ fn update_hit_result_component(
     for (entity, hit_result_ref, game_mode, position, eye_height, look_direction, world_name) in
         &mut query
     {
-        let pick_range = if game_mode.current == GameMode::Creative {
-            6.
-        } else {
-            4.5
-        };
+        let pick_range = pick_range(game_mode.current);
         let eye_position = Vec3 {
             x: position.x,
             y: position.y + **eye_height as f64,
@@ -174,6 +382,93 @@ fn update_hit_result_component(
     }
 }
 
+/// How far away a player in the given game mode can reach blocks.
+pub fn pick_range(game_mode: GameMode) -> f64 {
+    if game_mode == GameMode::Creative {
+        6.
+    } else {
+        4.5
+    }
+}
+
+/// The middle of one of the faces of a block.
+fn face_center(block_pos: BlockPos, direction: Direction) -> Vec3 {
+    block_pos.center() + direction.normal() * 0.5
+}
+
+/// Whether placing a block at a position with this block state would replace
+/// it, like air, water, or tall grass.
+pub fn is_replaceable(block_state: BlockState) -> bool {
+    if block_state.is_air() {
+        return true;
+    }
+    let block = Box::<dyn Block>::from(block_state);
+    matches!(
+        block.id(),
+        "water"
+            | "lava"
+            | "grass"
+            | "fern"
+            | "dead_bush"
+            | "seagrass"
+            | "tall_seagrass"
+            | "tall_grass"
+            | "large_fern"
+            | "vine"
+            | "glow_lichen"
+            | "structure_void"
+            | "light"
+            | "fire"
+            | "soul_fire"
+    )
+}
+
+/// Find a face of a solid block next to `position` that we can click to place
+/// a block at `position`. We pick the face that's pointing at `eye_position`
+/// the most directly, so it's the easiest one to look at. Faces that point
+/// away from our eyes can't be seen, so they're never picked.
+pub fn find_block_to_place_against(
+    instance: &Instance,
+    position: BlockPos,
+    eye_position: Vec3,
+) -> Option<BlockHit> {
+    [
+        Direction::Down,
+        Direction::Up,
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ]
+    .into_iter()
+    .filter_map(|direction| {
+        // the block we're placing against is in this direction, and we click the
+        // face on the opposite side of it
+        let block_pos = position + BlockPos::from(direction.normal());
+        let block_state = instance.chunks.get_block_state(&block_pos)?;
+        if is_replaceable(block_state) || block_state.shape() == &empty_shape() {
+            return None;
+        }
+        let face = direction.opposite();
+        let location = face_center(block_pos, face);
+        // the cosine of the angle between the face and the direction to our eyes,
+        // which is negative if the face is pointing away from us
+        let to_eye = eye_position - location;
+        let facing = to_eye.dot(face.normal()) / to_eye.length();
+        if facing <= 0. {
+            return None;
+        }
+        Some((facing, face, block_pos, location))
+    })
+    .max_by(|a, b| a.0.total_cmp(&b.0))
+    .map(|(_, direction, block_pos, location)| BlockHit {
+        block_pos,
+        direction,
+        location,
+        inside: false,
+    })
+}
+
 /// Get the block that a player would be looking at if their eyes were at the
 /// given direction and position.
 ///
@@ -262,3 +557,61 @@ pub fn check_block_can_be_broken_by_item_in_adventure_mode(
 
     // true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azalea_core::ChunkPos;
+    use azalea_world::{Chunk, ChunkStorage, PartialInstance};
+
+    #[test]
+    fn test_find_block_to_place_against() {
+        let mut partial_world = PartialInstance::default();
+        let mut chunk_storage = ChunkStorage::default();
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut chunk_storage,
+        );
+        for pos in [BlockPos::new(1, 0, 1), BlockPos::new(2, 1, 1)] {
+            partial_world.chunks.set_block_state(
+                &pos,
+                azalea_registry::Block::Stone.into(),
+                &mut chunk_storage,
+            );
+        }
+        let world = Instance::from(chunk_storage);
+
+        // from above, the top of the floor is the face we can see
+        let block_hit =
+            find_block_to_place_against(&world, BlockPos::new(1, 1, 1), Vec3::new(1.5, 3., 1.5))
+                .unwrap();
+        assert_eq!(block_hit.block_pos, BlockPos::new(1, 0, 1));
+        assert_eq!(block_hit.direction, Direction::Up);
+        assert_eq!(block_hit.location, Vec3::new(1.5, 1., 1.5));
+
+        // from below the floor's top face is hidden, so we use the block next to it
+        let block_hit =
+            find_block_to_place_against(&world, BlockPos::new(1, 1, 1), Vec3::new(-1., 0.5, 1.5))
+                .unwrap();
+        assert_eq!(block_hit.block_pos, BlockPos::new(2, 1, 1));
+        assert_eq!(block_hit.direction, Direction::West);
+
+        // from the other side of the wall and below the floor, both faces point away
+        // from us
+        assert!(find_block_to_place_against(
+            &world,
+            BlockPos::new(1, 1, 1),
+            Vec3::new(4., 0.5, 1.5)
+        )
+        .is_none());
+
+        // nothing to place against in the air
+        assert!(find_block_to_place_against(
+            &world,
+            BlockPos::new(5, 5, 5),
+            Vec3::new(5.5, 7., 5.5)
+        )
+        .is_none());
+    }
+}
@@ -12,6 +12,7 @@ use azalea_inventory::{
 use azalea_protocol::packets::game::{
     serverbound_container_click_packet::ServerboundContainerClickPacket,
     serverbound_container_close_packet::ServerboundContainerClosePacket,
+    serverbound_set_carried_item_packet::ServerboundSetCarriedItemPacket,
 };
 use azalea_registry::MenuKind;
 use bevy_app::{App, Plugin};
@@ -21,11 +22,14 @@ use bevy_ecs::{
     event::EventReader,
     prelude::EventWriter,
     schedule::{IntoSystemConfig, IntoSystemConfigs},
-    system::Query,
+    system::{Commands, Query},
 };
 use log::warn;
 
-use crate::{client::PlayerAbilities, local_player::handle_send_packet_event, Client, LocalPlayer};
+use crate::{
+    client::PlayerAbilities, local_player::handle_send_packet_event, merchant::MerchantOffers,
+    Client, LocalPlayer,
+};
 
 pub struct InventoryPlugin;
 impl Plugin for InventoryPlugin {
@@ -35,13 +39,17 @@ impl Plugin for InventoryPlugin {
             .add_event::<CloseContainerEvent>()
             .add_event::<ContainerClickEvent>()
             .add_event::<SetContainerContentEvent>()
+            .add_event::<SetContainerDataEvent>()
+            .add_event::<SetSelectedHotbarSlotEvent>()
             .add_systems(
                 (
                     handle_menu_opened_event,
                     handle_set_container_content_event,
+                    handle_set_container_data_event,
                     handle_container_click_event,
                     handle_container_close_event.before(handle_send_packet_event),
                     handle_client_side_close_container_event,
+                    handle_set_selected_hotbar_slot_event.before(handle_send_packet_event),
                 )
                     .chain(),
             );
@@ -56,10 +64,26 @@ impl Client {
         let inventory = self.query::<&InventoryComponent>(&mut ecs);
         inventory.menu().clone()
     }
+
+    /// The index of the hotbar slot that we're holding, from 0 to 8.
+    pub fn selected_hotbar_slot(&self) -> u8 {
+        let mut ecs = self.ecs.lock();
+        self.query::<&InventoryComponent>(&mut ecs)
+            .selected_hotbar_slot
+    }
+
+    /// Hold the item in the given hotbar slot, from 0 to 8, and tell the
+    /// server about it.
+    pub fn set_selected_hotbar_slot(&self, slot: u8) {
+        self.ecs.lock().send_event(SetSelectedHotbarSlotEvent {
+            entity: self.entity,
+            slot,
+        });
+    }
 }
 
 /// A component present on all local players that have an inventory.
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone)]
 pub struct InventoryComponent {
     /// A component that contains the player's inventory menu. This is
     /// guaranteed to be a `Menu::Player`.
@@ -76,6 +100,11 @@ pub struct InventoryComponent {
     /// The current container menu that the player has open. If no container is
     /// open, this will be `None`.
     pub container_menu: Option<azalea_inventory::Menu>,
+    /// The properties of the open container that the server sent in
+    /// `ContainerSetData` packets, indexed by their id. These are used for
+    /// things like the progress of a furnace. This is cleared whenever a
+    /// container is opened or closed.
+    pub container_data: HashMap<u16, u16>,
     /// The item that is currently held by the cursor. `Slot::Empty` if nothing
     /// is currently being held.
     ///
@@ -290,7 +319,7 @@ impl InventoryComponent {
 
         match operation {
             // left clicking outside inventory
-            ClickOperation::Pickup(PickupClick::Left { slot: None }) => {
+            ClickOperation::Pickup(PickupClick::Left { slot: None } | PickupClick::LeftOutside) => {
                 if self.carried.is_present() {
                     // vanilla has `player.drop`s but they're only used
                     // server-side
@@ -301,7 +330,9 @@ impl InventoryComponent {
                     self.carried = ItemSlot::Empty;
                 }
             }
-            ClickOperation::Pickup(PickupClick::Right { slot: None }) => {
+            ClickOperation::Pickup(
+                PickupClick::Right { slot: None } | PickupClick::RightOutside,
+            ) => {
                 if self.carried.is_present() {
                     let _item = self.carried.split(1);
                     // player.drop(item, true);
@@ -310,16 +341,61 @@ impl InventoryComponent {
             ClickOperation::Pickup(
                 PickupClick::Left { slot: Some(slot) } | PickupClick::Right { slot: Some(slot) },
             ) => {
-                let Some(slot_item) = self.menu().slot(*slot as usize) else {
+                let is_left_click =
+                    matches!(operation, ClickOperation::Pickup(PickupClick::Left { .. }));
+                let slot_index = *slot as usize;
+                let Some(slot_item) = self.menu().slot(slot_index).cloned() else {
                     return;
                 };
-                let carried = &self.carried;
                 // vanilla does a check called tryItemClickBehaviourOverride
                 // here
                 // i don't understand it so i didn't implement it
                 match slot_item {
-                    ItemSlot::Empty => if carried.is_present() {},
-                    ItemSlot::Present(_) => todo!(),
+                    ItemSlot::Empty => {
+                        if let ItemSlot::Present(carried) = &self.carried {
+                            let count = if is_left_click { carried.count } else { 1 };
+                            self.safe_insert_carried(slot_index, count);
+                        }
+                    }
+                    ItemSlot::Present(slot_item) => {
+                        if !self.menu().may_pickup(slot_index) {
+                            return;
+                        }
+                        let ItemSlot::Present(carried) = self.carried.clone() else {
+                            // pick up the whole stack, or half of it (rounded up) if it was a
+                            // right click
+                            let count = if is_left_click {
+                                slot_item.count
+                            } else {
+                                (slot_item.count + 1) / 2
+                            };
+                            let slot = self.menu_mut().slot_mut(slot_index).unwrap();
+                            self.carried = slot.split(count as u8);
+                            return;
+                        };
+
+                        if self.menu().may_place(slot_index, &carried) {
+                            if slot_item.is_same_item_and_nbt(&carried) {
+                                let count = if is_left_click { carried.count } else { 1 };
+                                self.safe_insert_carried(slot_index, count);
+                            } else if carried.count <= self.max_stack_size_for(slot_index, &carried)
+                            {
+                                // swap the carried item with the one in the slot
+                                *self.menu_mut().slot_mut(slot_index).unwrap() =
+                                    ItemSlot::Present(carried);
+                                self.carried = ItemSlot::Present(slot_item);
+                            }
+                        } else if slot_item.is_same_item_and_nbt(&carried) {
+                            // the item can't be put in the slot (like a crafting result), so
+                            // take as many as we can hold instead
+                            let space = carried.kind.max_stack_size() - carried.count;
+                            let slot = self.menu_mut().slot_mut(slot_index).unwrap();
+                            let taken = slot.split(space.max(0) as u8);
+                            if let ItemSlot::Present(carried) = &mut self.carried {
+                                carried.count += taken.count();
+                            }
+                        }
+                    }
                 }
             }
             ClickOperation::QuickMove(
@@ -337,61 +413,61 @@ impl InventoryComponent {
             }
             ClickOperation::Swap(s) => {
                 let source_slot_index = s.source_slot as usize;
-                let target_slot_index = s.target_slot as usize;
-
-                let Some(source_slot) = self.menu().slot(source_slot_index) else {
+                // the target is an index in the hotbar (or 40 for the offhand), not in the menu
+                let Some(target_slot_index) = self.hotbar_index_to_menu_slot(s.target_slot) else {
                     return;
                 };
-                let Some(target_slot) = self.menu().slot(target_slot_index) else {
+
+                let Some(source_slot) = self.menu().slot(source_slot_index).cloned() else {
                     return;
                 };
-                if source_slot.is_empty() && target_slot.is_empty() {
+                let Some(target_slot) = self.menu().slot(target_slot_index).cloned() else {
                     return;
-                }
-
-                if target_slot.is_empty() {
-                    if self.menu().may_pickup(source_slot_index) {
-                        let source_slot = source_slot.clone();
-                        let target_slot = self.menu_mut().slot_mut(target_slot_index).unwrap();
-                        *target_slot = source_slot;
-                    }
-                } else if source_slot.is_empty() {
-                    let ItemSlot::Present(target_item) = target_slot else {
-                        unreachable!("target slot is not empty but is not present");
-                    };
-                    if self.menu().may_place(source_slot_index, target_item) {
-                        // get the target_item but mutable
-                        let source_max_stack_size = self.menu().max_stack_size(source_slot_index);
+                };
 
-                        let target_slot = self.menu_mut().slot_mut(target_slot_index).unwrap();
-                        let new_source_slot = target_slot.split(source_max_stack_size);
-                        *self.menu_mut().slot_mut(source_slot_index).unwrap() = new_source_slot;
+                match (&source_slot, &target_slot) {
+                    (ItemSlot::Empty, ItemSlot::Empty) => {}
+                    (ItemSlot::Present(_), ItemSlot::Empty) => {
+                        if self.menu().may_pickup(source_slot_index) {
+                            *self.menu_mut().slot_mut(target_slot_index).unwrap() = source_slot;
+                            *self.menu_mut().slot_mut(source_slot_index).unwrap() = ItemSlot::Empty;
+                        }
                     }
-                } else if self.menu().may_pickup(source_slot_index) {
-                    let ItemSlot::Present(target_item) = target_slot else {
-                        unreachable!("target slot is not empty but is not present");
-                    };
-                    if self.menu().may_place(source_slot_index, target_item) {
-                        let source_max_stack = self.menu().max_stack_size(source_slot_index);
-                        if target_slot.count() > source_max_stack as i8 {
-                            // if there's more than the max stack size in the target slot
+                    (ItemSlot::Empty, ItemSlot::Present(target_item)) => {
+                        if self.menu().may_place(source_slot_index, target_item) {
+                            let source_max_stack_size =
+                                self.max_stack_size_for(source_slot_index, target_item);
 
                             let target_slot = self.menu_mut().slot_mut(target_slot_index).unwrap();
-                            let new_source_slot = target_slot.split(source_max_stack);
+                            let new_source_slot = target_slot.split(source_max_stack_size as u8);
                             *self.menu_mut().slot_mut(source_slot_index).unwrap() = new_source_slot;
-                            // if !self.inventory_menu.add(new_source_slot) {
-                            //     player.drop(new_source_slot, true);
-                            // }
-                        } else {
-                            // normal swap
-                            let new_target_slot = source_slot.clone();
-                            let new_source_slot = target_slot.clone();
-
-                            let target_slot = self.menu_mut().slot_mut(target_slot_index).unwrap();
-                            *target_slot = new_target_slot;
+                        }
+                    }
+                    (ItemSlot::Present(_), ItemSlot::Present(target_item)) => {
+                        if self.menu().may_pickup(source_slot_index)
+                            && self.menu().may_place(source_slot_index, target_item)
+                        {
+                            let source_max_stack_size =
+                                self.max_stack_size_for(source_slot_index, target_item);
+                            if target_item.count > source_max_stack_size {
+                                // if there's more than the max stack size in the target slot
 
-                            let source_slot = self.menu_mut().slot_mut(source_slot_index).unwrap();
-                            *source_slot = new_source_slot;
+                                let target_slot =
+                                    self.menu_mut().slot_mut(target_slot_index).unwrap();
+                                let new_source_slot =
+                                    target_slot.split(source_max_stack_size as u8);
+                                *self.menu_mut().slot_mut(source_slot_index).unwrap() =
+                                    new_source_slot;
+                                // the item that was in the source slot goes somewhere else
+                                // in our inventory
+                                if let ItemSlot::Present(source_item) = source_slot {
+                                    self.add_to_player_slots(source_item);
+                                }
+                            } else {
+                                // normal swap
+                                *self.menu_mut().slot_mut(target_slot_index).unwrap() = source_slot;
+                                *self.menu_mut().slot_mut(source_slot_index).unwrap() = target_slot;
+                            }
                         }
                     }
                 }
@@ -492,6 +568,110 @@ impl InventoryComponent {
         }
     }
 
+    /// Put up to `count` of the carried item in the slot. This is
+    /// `Slot.safeInsert` in vanilla.
+    fn safe_insert_carried(&mut self, slot_index: usize, count: i8) {
+        let ItemSlot::Present(mut carried) = self.carried.clone() else {
+            return;
+        };
+        if !self.menu().may_place(slot_index, &carried) {
+            return;
+        }
+        let max_stack_size = self.max_stack_size_for(slot_index, &carried);
+        let Some(slot) = self.menu_mut().slot_mut(slot_index) else {
+            return;
+        };
+        let count = count.min(carried.count).min(max_stack_size - slot.count());
+        if count <= 0 {
+            return;
+        }
+        match slot {
+            ItemSlot::Empty => *slot = ItemSlot::Present(carried.split(count as u8)),
+            ItemSlot::Present(slot_item) if slot_item.is_same_item_and_nbt(&carried) => {
+                slot_item.count += count;
+                carried.count -= count;
+            }
+            ItemSlot::Present(_) => return,
+        }
+        self.carried = ItemSlot::Present(carried);
+        self.carried.update_empty();
+    }
+
+    /// The most of the item that can go in the slot, which is the smaller of
+    /// the slot's limit and the item's max stack size.
+    fn max_stack_size_for(&self, slot_index: usize, item: &ItemSlotData) -> i8 {
+        i8::min(
+            self.menu().max_stack_size(slot_index) as i8,
+            item.kind.max_stack_size(),
+        )
+    }
+
+    /// Put an item in the player's slots of the current menu, like vanilla's
+    /// `Inventory::add`. Stacks of the same item are filled first, starting
+    /// with the selected hotbar slot, and then empty slots are used with the
+    /// hotbar before the rest of the inventory. Whatever doesn't fit gets
+    /// dropped by the server, so it's not added anywhere.
+    fn add_to_player_slots(&mut self, mut item: ItemSlotData) {
+        let menu = self.menu();
+        let hotbar_slots = menu.hotbar_slots_range();
+        let selected_slot = *hotbar_slots.start() + self.selected_hotbar_slot as usize;
+        let offhand_slot =
+            matches!(menu, Menu::Player(_)).then_some(azalea_inventory::Player::OFFHAND_SLOT);
+        let slots = hotbar_slots
+            .chain(menu.player_slots_without_hotbar_range())
+            .collect::<Vec<_>>();
+
+        let stacking_slots = [selected_slot]
+            .into_iter()
+            .chain(offhand_slot)
+            .chain(slots.iter().copied());
+        for slot_index in stacking_slots {
+            let max_stack_size = self.max_stack_size_for(slot_index, &item);
+            let Some(ItemSlot::Present(slot_item)) = self.menu_mut().slot_mut(slot_index) else {
+                continue;
+            };
+            if !slot_item.is_same_item_and_nbt(&item) {
+                continue;
+            }
+            let count = item.count.min(max_stack_size - slot_item.count);
+            if count > 0 {
+                slot_item.count += count;
+                item.count -= count;
+            }
+            if item.count <= 0 {
+                return;
+            }
+        }
+        for slot_index in slots {
+            let max_stack_size = self.max_stack_size_for(slot_index, &item);
+            let Some(slot) = self.menu_mut().slot_mut(slot_index) else {
+                continue;
+            };
+            if slot.is_present() {
+                continue;
+            }
+            *slot = ItemSlot::Present(item.split(max_stack_size as u8));
+            if item.count <= 0 {
+                return;
+            }
+        }
+    }
+
+    /// Convert the index of a hotbar slot (0-8, or 40 for the offhand) like
+    /// the one in [`SwapClick`](operations::SwapClick) into a protocol index in
+    /// the current menu.
+    ///
+    /// Returns `None` if the slot isn't in the menu, which is the case for
+    /// the offhand in every menu except the player's inventory.
+    pub fn hotbar_index_to_menu_slot(&self, hotbar_index: u8) -> Option<usize> {
+        let menu = self.menu();
+        match hotbar_index {
+            0..=8 => Some(*menu.hotbar_slots_range().start() + hotbar_index as usize),
+            40 if matches!(menu, Menu::Player(_)) => Some(azalea_inventory::Player::OFFHAND_SLOT),
+            _ => None,
+        }
+    }
+
     fn reset_quick_craft(&mut self) {
         self.quick_craft_status = QuickCraftStatusKind::Start;
         self.quick_craft_slots.clear();
@@ -551,6 +731,7 @@ impl Default for InventoryComponent {
             inventory_menu: Menu::Player(azalea_inventory::Player::default()),
             id: 0,
             container_menu: None,
+            container_data: HashMap::new(),
             carried: ItemSlot::Empty,
             state_id: 0,
             quick_craft_status: QuickCraftStatusKind::Start,
@@ -572,12 +753,19 @@ pub struct MenuOpenedEvent {
 }
 fn handle_menu_opened_event(
     mut events: EventReader<MenuOpenedEvent>,
-    mut query: Query<&mut InventoryComponent>,
+    mut query: Query<(&mut InventoryComponent, Option<&MerchantOffers>)>,
+    mut commands: Commands,
 ) {
     for event in events.iter() {
-        let mut inventory = query.get_mut(event.entity).unwrap();
+        let (mut inventory, merchant_offers) = query.get_mut(event.entity).unwrap();
         inventory.id = event.window_id as u8;
         inventory.container_menu = Some(Menu::from_kind(event.menu_type));
+        inventory.container_data.clear();
+        // the offers might've already been sent for this container if the packets
+        // arrived in the same tick
+        if merchant_offers.is_some_and(|offers| offers.container_id != inventory.id) {
+            commands.entity(event.entity).remove::<MerchantOffers>();
+        }
     }
 }
 
@@ -627,11 +815,14 @@ pub struct ClientSideCloseContainerEvent {
 pub fn handle_client_side_close_container_event(
     mut events: EventReader<ClientSideCloseContainerEvent>,
     mut query: Query<&mut InventoryComponent>,
+    mut commands: Commands,
 ) {
     for event in events.iter() {
         let mut inventory = query.get_mut(event.entity).unwrap();
         inventory.container_menu = None;
+        inventory.container_data.clear();
         inventory.id = 0;
+        commands.entity(event.entity).remove::<MerchantOffers>();
     }
 }
 
@@ -691,6 +882,8 @@ pub struct SetContainerContentEvent {
     pub entity: Entity,
     pub slots: Vec<ItemSlot>,
     pub container_id: u8,
+    pub state_id: u32,
+    pub carried_item: ItemSlot,
 }
 fn handle_set_container_content_event(
     mut events: EventReader<SetContainerContentEvent>,
@@ -713,5 +906,184 @@ fn handle_set_container_content_event(
                 *slot_mut = slot.clone();
             }
         }
+        inventory.state_id = event.state_id;
+        inventory.carried = event.carried_item.clone();
+    }
+}
+
+/// Sent from the server when a property of the open container changes, like
+/// the progress of a furnace. Usually triggered by the `ContainerSetData`
+/// packet.
+pub struct SetContainerDataEvent {
+    pub entity: Entity,
+    pub container_id: u8,
+    pub id: u16,
+    pub value: u16,
+}
+fn handle_set_container_data_event(
+    mut events: EventReader<SetContainerDataEvent>,
+    mut query: Query<&mut InventoryComponent>,
+) {
+    for event in events.iter() {
+        let mut inventory = query.get_mut(event.entity).unwrap();
+
+        if event.container_id != inventory.id {
+            warn!(
+                "Tried to set container data with ID {}, but the current container ID is {}",
+                event.container_id, inventory.id
+            );
+            continue;
+        }
+
+        inventory.container_data.insert(event.id, event.value);
+    }
+}
+
+/// Change which hotbar slot is selected, and tell the server about it.
+pub struct SetSelectedHotbarSlotEvent {
+    pub entity: Entity,
+    /// The index of the hotbar slot, from 0 to 8.
+    pub slot: u8,
+}
+fn handle_set_selected_hotbar_slot_event(
+    mut events: EventReader<SetSelectedHotbarSlotEvent>,
+    mut query: Query<(&LocalPlayer, &mut InventoryComponent)>,
+) {
+    for event in events.iter() {
+        let (local_player, mut inventory) = query.get_mut(event.entity).unwrap();
+
+        if event.slot > 8 {
+            warn!(
+                "Tried to select hotbar slot {}, but it must be 0-8",
+                event.slot
+            );
+            continue;
+        }
+        // the server doesn't care if we send the same slot again, but there's no
+        // point in doing it
+        if inventory.selected_hotbar_slot == event.slot {
+            continue;
+        }
+
+        inventory.selected_hotbar_slot = event.slot;
+        local_player.write_packet(
+            ServerboundSetCarriedItemPacket {
+                slot: event.slot as u16,
+            }
+            .get(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azalea_inventory::operations::SwapClick;
+    use azalea_registry::Item;
+
+    fn click(inventory: &mut InventoryComponent, operation: impl Into<ClickOperation>) {
+        inventory.simulate_click(&operation.into(), &PlayerAbilities::default());
+    }
+
+    #[test]
+    fn test_simulate_pickup_and_place() {
+        let mut inventory = InventoryComponent::default();
+        *inventory.inventory_menu.slot_mut(9).unwrap() = ItemSlot::new(Item::Stone, 10);
+
+        click(&mut inventory, PickupClick::Right { slot: Some(9) });
+        assert_eq!(inventory.carried, ItemSlot::new(Item::Stone, 5));
+        assert_eq!(
+            inventory.inventory_menu.slot(9),
+            Some(&ItemSlot::new(Item::Stone, 5))
+        );
+
+        click(&mut inventory, PickupClick::Right { slot: Some(10) });
+        assert_eq!(inventory.carried, ItemSlot::new(Item::Stone, 4));
+        assert_eq!(
+            inventory.inventory_menu.slot(10),
+            Some(&ItemSlot::new(Item::Stone, 1))
+        );
+
+        click(&mut inventory, PickupClick::Left { slot: Some(9) });
+        assert_eq!(inventory.carried, ItemSlot::Empty);
+        assert_eq!(
+            inventory.inventory_menu.slot(9),
+            Some(&ItemSlot::new(Item::Stone, 9))
+        );
+    }
+
+    #[test]
+    fn test_simulate_pickup_merge_and_swap() {
+        let mut inventory = InventoryComponent::default();
+        *inventory.inventory_menu.slot_mut(9).unwrap() = ItemSlot::new(Item::Stone, 40);
+        *inventory.inventory_menu.slot_mut(10).unwrap() = ItemSlot::new(Item::Stone, 40);
+        *inventory.inventory_menu.slot_mut(11).unwrap() = ItemSlot::new(Item::Dirt, 1);
+
+        // only 24 fit in the other stack
+        click(&mut inventory, PickupClick::Left { slot: Some(9) });
+        click(&mut inventory, PickupClick::Left { slot: Some(10) });
+        assert_eq!(inventory.carried, ItemSlot::new(Item::Stone, 16));
+        assert_eq!(
+            inventory.inventory_menu.slot(10),
+            Some(&ItemSlot::new(Item::Stone, 64))
+        );
+
+        // clicking a different item swaps it with the one we're holding
+        click(&mut inventory, PickupClick::Left { slot: Some(11) });
+        assert_eq!(inventory.carried, ItemSlot::new(Item::Dirt, 1));
+        assert_eq!(
+            inventory.inventory_menu.slot(11),
+            Some(&ItemSlot::new(Item::Stone, 16))
+        );
+    }
+
+    #[test]
+    fn test_simulate_swap_with_hotbar() {
+        let mut inventory = InventoryComponent::default();
+        *inventory.inventory_menu.slot_mut(9).unwrap() = ItemSlot::new(Item::Stone, 1);
+
+        click(
+            &mut inventory,
+            SwapClick {
+                source_slot: 9,
+                target_slot: 2,
+            },
+        );
+        let hotbar_slot = azalea_inventory::Player::HOTBAR_SLOTS.start() + 2;
+        assert_eq!(inventory.inventory_menu.slot(9), Some(&ItemSlot::Empty));
+        assert_eq!(
+            inventory.inventory_menu.slot(hotbar_slot),
+            Some(&ItemSlot::new(Item::Stone, 1))
+        );
+    }
+
+    #[test]
+    fn test_simulate_swap_with_oversized_hotbar_stack() {
+        let mut inventory = InventoryComponent::default();
+        let hotbar_slot = *azalea_inventory::Player::HOTBAR_SLOTS.start();
+        *inventory.inventory_menu.slot_mut(9).unwrap() = ItemSlot::new(Item::Stone, 5);
+        *inventory.inventory_menu.slot_mut(hotbar_slot).unwrap() = ItemSlot::new(Item::Dirt, 70);
+
+        click(
+            &mut inventory,
+            SwapClick {
+                source_slot: 9,
+                target_slot: 0,
+            },
+        );
+        // only a full stack fits in the source slot, and the stone that was there
+        // goes in the first empty hotbar slot
+        assert_eq!(
+            inventory.inventory_menu.slot(9),
+            Some(&ItemSlot::new(Item::Dirt, 64))
+        );
+        assert_eq!(
+            inventory.inventory_menu.slot(hotbar_slot),
+            Some(&ItemSlot::new(Item::Dirt, 6))
+        );
+        assert_eq!(
+            inventory.inventory_menu.slot(hotbar_slot + 1),
+            Some(&ItemSlot::new(Item::Stone, 5))
+        );
     }
 }
@@ -12,29 +12,35 @@
 #![feature(type_alias_impl_trait)]
 
 mod account;
+pub mod attack;
 pub mod chat;
 mod client;
 pub mod disconnect;
 mod entity_query;
 mod events;
 mod get_mc_dir;
+pub mod hud;
 pub mod interact;
 pub mod inventory;
 mod local_player;
-mod mining;
+pub mod merchant;
+pub mod mining;
 mod movement;
 pub mod packet_handling;
 pub mod ping;
 mod player;
+pub mod recipe_book;
 pub mod respawn;
+pub mod scoreboard;
 pub mod task_pool;
+pub mod weather;
 
 pub use account::{Account, AccountOpts};
 pub use client::{
-    start_ecs, Client, ClientInformation, DefaultPlugins, JoinError, JoinedClientBundle, TabList,
-    TickBroadcast,
+    start_ecs, Client, ClientInformation, DefaultPlugins, JoinError, JoinedClientBundle,
+    PlayerAbilities, TabList, TickBroadcast,
 };
 pub use events::Event;
-pub use local_player::{GameProfileComponent, LocalPlayer};
+pub use local_player::{GameProfileComponent, LocalGameMode, LocalPlayer};
 pub use movement::{SprintDirection, StartSprintEvent, StartWalkEvent, WalkDirection};
 pub use player::PlayerInfo;
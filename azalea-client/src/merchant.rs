@@ -0,0 +1,86 @@
+//! Keep track of the trades that villagers and wandering traders offer, and
+//! pick which one to use.
+
+use azalea_protocol::packets::game::{
+    clientbound_merchant_offers_packet::{ClientboundMerchantOffersPacket, MerchantOffer},
+    serverbound_select_trade_packet::ServerboundSelectTradePacket,
+};
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component, entity::Entity, event::EventReader, schedule::IntoSystemConfig,
+    system::Query,
+};
+use log::warn;
+
+use crate::{inventory::InventoryComponent, local_player::handle_send_packet_event, LocalPlayer};
+
+/// A plugin that adds [`SelectTradeEvent`].
+pub struct MerchantPlugin;
+impl Plugin for MerchantPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SelectTradeEvent>()
+            .add_system(handle_select_trade_event.before(handle_send_packet_event));
+    }
+}
+
+/// A component with the trades of the merchant that we have open. This is only
+/// inserted once the server sends us the offers, and it's removed when the
+/// merchant is closed or another container is opened.
+#[derive(Component, Clone, Debug)]
+pub struct MerchantOffers {
+    /// The id of the merchant's container. This is the same as
+    /// [`InventoryComponent::id`] while the merchant is open.
+    pub container_id: u8,
+    pub offers: Vec<MerchantOffer>,
+    /// The level of the villager, from 1 (novice) to 5 (master). Wandering
+    /// traders are always level 1.
+    pub villager_level: u32,
+    pub villager_xp: u32,
+    /// Whether the villager's level and xp bar are shown. This is false for
+    /// wandering traders.
+    pub show_progress: bool,
+    /// Whether the offers that are out of stock will be restocked. This is
+    /// false for wandering traders.
+    pub can_restock: bool,
+}
+
+impl From<&ClientboundMerchantOffersPacket> for MerchantOffers {
+    fn from(packet: &ClientboundMerchantOffersPacket) -> Self {
+        Self {
+            container_id: packet.container_id as u8,
+            offers: packet.offers.clone(),
+            villager_level: packet.villager_level,
+            villager_xp: packet.villager_xp,
+            show_progress: packet.show_progress,
+            can_restock: packet.can_restock,
+        }
+    }
+}
+
+/// Tell the server which trade we want to use in the open merchant menu. The
+/// server moves the items for the trade from our inventory into the payment
+/// slots if we have them.
+#[derive(Debug)]
+pub struct SelectTradeEvent {
+    pub entity: Entity,
+    pub window_id: u8,
+    /// The index of the offer in [`MerchantOffers::offers`].
+    pub index: u32,
+}
+fn handle_select_trade_event(
+    mut events: EventReader<SelectTradeEvent>,
+    query: Query<(&LocalPlayer, &InventoryComponent)>,
+) {
+    for event in events.iter() {
+        let (local_player, inventory) = query.get(event.entity).unwrap();
+        if inventory.id != event.window_id {
+            warn!(
+                "Tried to select trade in container with ID {}, but the current container ID is {}",
+                event.window_id, inventory.id
+            );
+            continue;
+        }
+
+        local_player.write_packet(ServerboundSelectTradePacket { item: event.index }.get());
+    }
+}
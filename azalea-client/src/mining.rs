@@ -1,35 +1,996 @@
-use azalea_core::BlockPos;
-use bevy_app::{App, Plugin};
+use azalea_block::{Block, BlockState};
+use azalea_core::{BlockPos, Direction, GameMode};
+use azalea_inventory::{
+    item::{ToolExt, ToolKind},
+    ItemSlot,
+};
+use azalea_physics::is_water;
+use azalea_protocol::packets::game::{
+    serverbound_interact_packet::InteractionHand,
+    serverbound_player_action_packet::{Action, ServerboundPlayerActionPacket},
+    serverbound_swing_packet::ServerboundSwingPacket,
+};
+use azalea_registry::{Enchantment, MobEffect};
+use azalea_world::entity::{ActiveEffects, EyeHeight, Physics, Position};
+use bevy_app::{App, CoreSchedule, IntoSystemAppConfig, Plugin};
 use bevy_ecs::prelude::*;
+use derive_more::{Deref, DerefMut};
+use thiserror::Error;
+use tokio::sync::broadcast::{self, error::RecvError};
 
-use crate::Client;
+use crate::{
+    interact::{CurrentSequenceNumber, HitResultComponent, LastAckedSequenceNumber},
+    inventory::InventoryComponent,
+    local_player::{handle_send_packet_event, LocalGameMode},
+    Client, LocalPlayer, TickBroadcast,
+};
 
 /// A plugin that allows clients to break blocks in the world.
 pub struct MinePlugin;
 impl Plugin for MinePlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<StartMiningBlockEvent>()
-            .add_system(handle_start_mining_block_event);
+            .add_event::<StopMiningBlockEvent>()
+            .add_event::<FinishMiningBlockEvent>()
+            .add_systems(
+                (
+                    handle_stop_mining_block_event,
+                    handle_start_mining_block_event,
+                )
+                    .chain()
+                    .before(handle_send_packet_event),
+            )
+            .add_system(continue_mining_block.in_schedule(CoreSchedule::FixedUpdate));
     }
 }
 
+/// The reason that [`Client::mine`] didn't break the block.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MineError {
+    #[error("The block can't be broken")]
+    Unbreakable,
+    #[error("Stopped mining before the block was broken")]
+    Interrupted,
+    #[error("The server didn't let us break the block")]
+    Rejected,
+}
+
 impl Client {
-    /// Start mining a block.
+    /// Start mining a block, and keep mining it until it breaks. Use
+    /// [`Self::mine`] if you want to wait until it's broken.
+    ///
+    /// Note that this doesn't look at the block or check whether we can reach
+    /// it.
     pub fn start_mining_block(&self, position: BlockPos) {
         self.ecs.lock().send_event(StartMiningBlockEvent {
             entity: self.entity,
             position,
         });
     }
+
+    /// Stop mining the block that we're currently mining, if any.
+    pub fn stop_mining_block(&self) {
+        self.ecs.lock().send_event(StopMiningBlockEvent {
+            entity: self.entity,
+        });
+    }
+
+    /// Mine a block and wait until the server agrees that it's broken.
+    ///
+    /// ```
+    /// # use azalea_client::Client;
+    /// # use azalea_core::BlockPos;
+    /// # async fn example(bot: &Client) {
+    /// if bot.mine(BlockPos::new(0, 70, 0)).await.is_err() {
+    ///     bot.chat("I couldn't break that block :(");
+    /// }
+    /// # }
+    /// ```
+    pub async fn mine(&self, position: BlockPos) -> Result<(), MineError> {
+        let block_state = self.world().read().chunks.get_block_state(&position);
+        let Some(block_state) = block_state else {
+            return Err(MineError::Interrupted);
+        };
+        if block_state.is_air() {
+            return Ok(());
+        }
+        let block = Box::<dyn Block>::from(block_state);
+        if block.behavior().destroy_time < 0. {
+            return Err(MineError::Unbreakable);
+        }
+
+        let mut receiver = {
+            let ecs = self.ecs.lock();
+            ecs.resource::<TickBroadcast>().subscribe()
+        };
+        self.start_mining_block(position);
+
+        let mut has_started = false;
+        let mut ticks_waiting = 0;
+        loop {
+            match receiver.recv().await {
+                Ok(()) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return Err(MineError::Interrupted),
+            }
+            let is_air = self
+                .world()
+                .read()
+                .chunks
+                .get_block_state(&position)
+                .map(|block_state| block_state.is_air())
+                .unwrap_or_default();
+            if is_air {
+                return self.wait_for_mined_block(&mut receiver, position).await;
+            }
+            let is_mining = self
+                .ecs
+                .lock()
+                .get::<Mining>(self.entity)
+                .map(|mining| mining.pos == position)
+                .unwrap_or_default();
+            if is_mining {
+                has_started = true;
+            } else if has_started {
+                // we were mining it but stopped before the block broke
+                return Err(MineError::Interrupted);
+            } else {
+                // if it broke instantly then the server should tell us soon
+                ticks_waiting += 1;
+                if ticks_waiting > 20 {
+                    return Err(MineError::Interrupted);
+                }
+            }
+        }
+    }
+
+    /// Wait for the server to acknowledge the block that we broke locally, and
+    /// then check that it didn't put it back.
+    async fn wait_for_mined_block(
+        &self,
+        receiver: &mut broadcast::Receiver<()>,
+        position: BlockPos,
+    ) -> Result<(), MineError> {
+        // the action that finished mining the block used the current sequence
+        // number (or an earlier one), and the server acknowledges them in order
+        let sequence_number = **self.query::<&CurrentSequenceNumber>(&mut self.ecs.lock());
+        let mut ticks_waiting = 0;
+        loop {
+            let last_acked = **self.query::<&LastAckedSequenceNumber>(&mut self.ecs.lock());
+            if last_acked >= sequence_number {
+                break;
+            }
+            ticks_waiting += 1;
+            if ticks_waiting > 100 {
+                return Err(MineError::Rejected);
+            }
+            match receiver.recv().await {
+                Ok(()) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return Err(MineError::Interrupted),
+            }
+        }
+
+        // the server sends the block updates before the acknowledgement, so if it
+        // didn't let us break the block we know by now
+        let is_air = self
+            .world()
+            .read()
+            .chunks
+            .get_block_state(&position)
+            .map(|block_state| block_state.is_air())
+            .unwrap_or_default();
+        if is_air {
+            Ok(())
+        } else {
+            Err(MineError::Rejected)
+        }
+    }
 }
 
+/// Start mining a block, and keep mining it every tick until it breaks.
 pub struct StartMiningBlockEvent {
     pub entity: Entity,
     pub position: BlockPos,
 }
 
-fn handle_start_mining_block_event(mut _events: EventReader<StartMiningBlockEvent>) {
-    // for event in events.iter() {
-    //     //
-    // }
+/// Stop mining the block that we're currently mining, without breaking it.
+pub struct StopMiningBlockEvent {
+    pub entity: Entity,
+}
+
+/// Sent when we break a block by mining it. The server still has to agree
+/// that we broke it, so listen for block updates if you need to be sure.
+pub struct FinishMiningBlockEvent {
+    pub entity: Entity,
+    pub position: BlockPos,
+}
+
+/// A component that's present while we're mining a block, which keeps track of
+/// how far along we are.
+#[derive(Component, Clone, Debug)]
+pub struct Mining {
+    pub pos: BlockPos,
+    /// The face of the block that we're mining.
+    pub dir: Direction,
+    /// How much of the block has been mined, where 1 is fully broken.
+    pub progress: f32,
+    /// The item we started mining with. If we switch to a different item we
+    /// have to start over.
+    item: ItemSlot,
+}
+
+/// The number of ticks to wait after breaking a block before we can continue
+/// mining another one.
+#[derive(Component, Clone, Copy, Debug, Default, Deref, DerefMut)]
+pub struct MineDelay(pub u32);
+
+/// The number of ticks we have to wait between breaking blocks.
+const MINE_DELAY_TICKS: u32 = 5;
+
+/// The face of the block that we should say we're mining. If we're looking at
+/// the block this is the face that we're looking at, otherwise it's the side
+/// that faces our eyes.
+fn mining_direction(
+    position: BlockPos,
+    hit_result: Option<&HitResultComponent>,
+    player_position: &Position,
+    eye_height: &EyeHeight,
+) -> Direction {
+    if let Some(hit_result) = hit_result {
+        if hit_result.block_pos == position {
+            return hit_result.direction;
+        }
+    }
+    let eye_position = **player_position + azalea_core::Vec3::new(0., **eye_height as f64, 0.);
+    Direction::nearest(eye_position - position.center())
+}
+
+fn send_player_action(
+    local_player: &LocalPlayer,
+    action: Action,
+    pos: BlockPos,
+    direction: Direction,
+    sequence: u32,
+) {
+    local_player.write_packet(
+        ServerboundPlayerActionPacket {
+            action,
+            pos,
+            direction,
+            sequence,
+        }
+        .get(),
+    );
+}
+
+/// Set the block to air in our world without waiting for the server, like
+/// vanilla does.
+fn destroy_block_locally(local_player: &LocalPlayer, pos: BlockPos) {
+    local_player
+        .world
+        .write()
+        .chunks
+        .set_block_state(&pos, BlockState::AIR);
+}
+
+#[allow(clippy::type_complexity)]
+fn handle_start_mining_block_event(
+    mut commands: Commands,
+    mut events: EventReader<StartMiningBlockEvent>,
+    mut query: Query<(
+        &LocalPlayer,
+        &mut CurrentSequenceNumber,
+        &LocalGameMode,
+        &InventoryComponent,
+        &Position,
+        &EyeHeight,
+        &Physics,
+        Option<&ActiveEffects>,
+        Option<&HitResultComponent>,
+        Option<&Mining>,
+    )>,
+    mut finish_mining_events: EventWriter<FinishMiningBlockEvent>,
+) {
+    for event in events.iter() {
+        let Ok((
+            local_player,
+            mut sequence_number,
+            game_mode,
+            inventory,
+            position,
+            eye_height,
+            physics,
+            active_effects,
+            hit_result,
+            mining,
+        )) = query.get_mut(event.entity)
+        else {
+            continue;
+        };
+        if let Some(mining) = mining {
+            if mining.pos == event.position {
+                // we're already mining it
+                continue;
+            }
+            send_player_action(
+                local_player,
+                Action::AbortDestroyBlock,
+                mining.pos,
+                mining.dir,
+                0,
+            );
+            commands.entity(event.entity).remove::<Mining>();
+        }
+
+        // TODO: adventure mode lets you mine blocks if your item has the right
+        // CanDestroy tag
+        if matches!(game_mode.current, GameMode::Spectator | GameMode::Adventure) {
+            continue;
+        }
+
+        let block_state = {
+            let world = local_player.world.read();
+            if !world.world_border.is_within_bounds(&event.position) {
+                continue;
+            }
+            world.chunks.get_block_state(&event.position)
+        };
+        let Some(block_state) = block_state else {
+            continue;
+        };
+        if block_state.is_air() {
+            continue;
+        }
+
+        let direction = mining_direction(event.position, hit_result, position, eye_height);
+        **sequence_number += 1;
+        send_player_action(
+            local_player,
+            Action::StartDestroyBlock,
+            event.position,
+            direction,
+            **sequence_number,
+        );
+        local_player.write_packet(
+            ServerboundSwingPacket {
+                hand: InteractionHand::MainHand,
+            }
+            .get(),
+        );
+
+        let item = inventory.held_item();
+        let is_instant = game_mode.current == GameMode::Creative || {
+            let block = Box::<dyn Block>::from(block_state);
+            let eye_in_water = is_eye_in_water(local_player, position, eye_height);
+            let modifiers =
+                MiningModifiers::new(inventory, active_effects, physics.on_ground, eye_in_water);
+            destroy_progress(block.as_ref(), &item, &modifiers) >= 1.
+        };
+        if is_instant {
+            destroy_block_locally(local_player, event.position);
+            commands
+                .entity(event.entity)
+                .insert(MineDelay(MINE_DELAY_TICKS));
+            finish_mining_events.send(FinishMiningBlockEvent {
+                entity: event.entity,
+                position: event.position,
+            });
+        } else {
+            commands.entity(event.entity).insert(Mining {
+                pos: event.position,
+                dir: direction,
+                progress: 0.,
+                item,
+            });
+        }
+    }
+}
+
+fn handle_stop_mining_block_event(
+    mut commands: Commands,
+    mut events: EventReader<StopMiningBlockEvent>,
+    query: Query<(&LocalPlayer, &Mining)>,
+) {
+    for event in events.iter() {
+        let Ok((local_player, mining)) = query.get(event.entity) else {
+            continue;
+        };
+        send_player_action(
+            local_player,
+            Action::AbortDestroyBlock,
+            mining.pos,
+            mining.dir,
+            0,
+        );
+        commands.entity(event.entity).remove::<Mining>();
+    }
+}
+
+/// Mine a bit more of the block we're mining every tick, and finish mining it
+/// once it's broken.
+#[allow(clippy::type_complexity)]
+fn continue_mining_block(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &LocalPlayer,
+        &mut Mining,
+        &mut CurrentSequenceNumber,
+        &InventoryComponent,
+        &Position,
+        &EyeHeight,
+        &Physics,
+        Option<&ActiveEffects>,
+        Option<&mut MineDelay>,
+    )>,
+    mut finish_mining_events: EventWriter<FinishMiningBlockEvent>,
+) {
+    for (
+        entity,
+        local_player,
+        mut mining,
+        mut sequence_number,
+        inventory,
+        position,
+        eye_height,
+        physics,
+        active_effects,
+        mine_delay,
+    ) in &mut query
+    {
+        if let Some(mut mine_delay) = mine_delay {
+            if **mine_delay > 0 {
+                **mine_delay -= 1;
+                continue;
+            }
+        }
+
+        let block_state = local_player
+            .world
+            .read()
+            .chunks
+            .get_block_state(&mining.pos)
+            .unwrap_or(BlockState::AIR);
+        if block_state.is_air() {
+            // someone else broke it
+            commands.entity(entity).remove::<Mining>();
+            continue;
+        }
+
+        let item = inventory.held_item();
+        if item != mining.item {
+            // switching items makes us start over
+            send_player_action(
+                local_player,
+                Action::AbortDestroyBlock,
+                mining.pos,
+                mining.dir,
+                0,
+            );
+            **sequence_number += 1;
+            send_player_action(
+                local_player,
+                Action::StartDestroyBlock,
+                mining.pos,
+                mining.dir,
+                **sequence_number,
+            );
+            mining.progress = 0.;
+            mining.item = item.clone();
+        }
+
+        let block = Box::<dyn Block>::from(block_state);
+        let eye_in_water = is_eye_in_water(local_player, position, eye_height);
+        let modifiers =
+            MiningModifiers::new(inventory, active_effects, physics.on_ground, eye_in_water);
+        mining.progress += destroy_progress(block.as_ref(), &item, &modifiers);
+        local_player.write_packet(
+            ServerboundSwingPacket {
+                hand: InteractionHand::MainHand,
+            }
+            .get(),
+        );
+
+        if mining.progress >= 1. {
+            **sequence_number += 1;
+            send_player_action(
+                local_player,
+                Action::StopDestroyBlock,
+                mining.pos,
+                mining.dir,
+                **sequence_number,
+            );
+            destroy_block_locally(local_player, mining.pos);
+            finish_mining_events.send(FinishMiningBlockEvent {
+                entity,
+                position: mining.pos,
+            });
+            commands
+                .entity(entity)
+                .remove::<Mining>()
+                .insert(MineDelay(MINE_DELAY_TICKS));
+        }
+    }
+}
+
+fn is_eye_in_water(
+    local_player: &LocalPlayer,
+    position: &Position,
+    eye_height: &EyeHeight,
+) -> bool {
+    let eye_position =
+        BlockPos::from(**position + azalea_core::Vec3::new(0., **eye_height as f64, 0.));
+    local_player
+        .world
+        .read()
+        .chunks
+        .get_block_state(&eye_position)
+        .map(is_water)
+        .unwrap_or_default()
+}
+
+/// Everything other than the held item that affects how fast a player can
+/// mine.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MiningModifiers {
+    /// The amplifier of the Haste (or Conduit Power) effect.
+    pub haste: Option<u8>,
+    /// The amplifier of the Mining Fatigue effect.
+    pub mining_fatigue: Option<u8>,
+    /// Whether our eyes are underwater and we don't have a helmet with Aqua
+    /// Affinity.
+    pub underwater: bool,
+    pub on_ground: bool,
+}
+
+impl Default for MiningModifiers {
+    fn default() -> Self {
+        Self {
+            haste: None,
+            mining_fatigue: None,
+            underwater: false,
+            on_ground: true,
+        }
+    }
+}
+
+impl MiningModifiers {
+    pub fn new(
+        inventory: &InventoryComponent,
+        active_effects: Option<&ActiveEffects>,
+        on_ground: bool,
+        eye_in_water: bool,
+    ) -> Self {
+        let effect = |effect| active_effects.and_then(|effects| effects.amplifier(effect));
+        let helmet = &inventory.inventory_menu.as_player().armor[0];
+        let has_aqua_affinity = match helmet {
+            ItemSlot::Present(helmet) => helmet.enchantment_level(Enchantment::AquaAffinity) > 0,
+            ItemSlot::Empty => false,
+        };
+        Self {
+            haste: effect(MobEffect::Haste).max(effect(MobEffect::ConduitPower)),
+            mining_fatigue: effect(MobEffect::MiningFatigue),
+            underwater: eye_in_water && !has_aqua_affinity,
+            on_ground,
+        }
+    }
+}
+
+/// The kind of tool that mines the block the fastest, or `None` if no tool
+/// makes a difference.
+///
+/// Vanilla gets this from the `minecraft:mineable/*` block tags, but since we
+/// don't keep track of tags yet it's guessed from the block's id.
+pub fn mineable_with(block: &dyn Block) -> Option<ToolKind> {
+    let id = block.id();
+
+    const WOOD_TYPES: &[&str] = &[
+        "oak_",
+        "spruce_",
+        "birch_",
+        "jungle_",
+        "acacia_",
+        "cherry_",
+        "dark_oak_",
+        "mangrove_",
+        "bamboo",
+        "crimson_",
+        "warped_",
+    ];
+    const SHOVEL_BLOCKS: &[&str] = &[
+        "grass_block",
+        "dirt",
+        "coarse_dirt",
+        "rooted_dirt",
+        "podzol",
+        "mycelium",
+        "farmland",
+        "dirt_path",
+        "mud",
+        "muddy_mangrove_roots",
+        "clay",
+        "sand",
+        "red_sand",
+        "suspicious_sand",
+        "gravel",
+        "suspicious_gravel",
+        "soul_sand",
+        "soul_soil",
+        "snow",
+        "snow_block",
+        "powder_snow",
+    ];
+    const AXE_BLOCKS: &[&str] = &[
+        "chest",
+        "trapped_chest",
+        "crafting_table",
+        "cartography_table",
+        "fletching_table",
+        "smithing_table",
+        "barrel",
+        "bookshelf",
+        "chiseled_bookshelf",
+        "jukebox",
+        "note_block",
+        "lectern",
+        "loom",
+        "composter",
+        "campfire",
+        "soul_campfire",
+        "beehive",
+        "bee_nest",
+        "ladder",
+        "pumpkin",
+        "carved_pumpkin",
+        "jack_o_lantern",
+        "melon",
+        "cocoa",
+        "brown_mushroom_block",
+        "red_mushroom_block",
+        "mushroom_stem",
+        "daylight_detector",
+    ];
+    const HOE_BLOCKS: &[&str] = &[
+        "hay_block",
+        "sponge",
+        "wet_sponge",
+        "target",
+        "dried_kelp_block",
+        "moss_block",
+        "moss_carpet",
+        "nether_wart_block",
+        "warped_wart_block",
+        "shroomlight",
+        "sculk",
+        "sculk_vein",
+        "sculk_catalyst",
+        "sculk_sensor",
+        "calibrated_sculk_sensor",
+        "sculk_shrieker",
+    ];
+    const PICKAXE_BLOCKS: &[&str] = &[
+        "ice",
+        "packed_ice",
+        "blue_ice",
+        "frosted_ice",
+        "piston",
+        "sticky_piston",
+        "piston_head",
+        "rail",
+        "powered_rail",
+        "detector_rail",
+        "activator_rail",
+        "stone_button",
+        "polished_blackstone_button",
+        "conduit",
+        "amethyst_cluster",
+        "large_amethyst_bud",
+        "medium_amethyst_bud",
+        "small_amethyst_bud",
+    ];
+
+    if id == "cobweb" {
+        return Some(ToolKind::Sword);
+    }
+    if SHOVEL_BLOCKS.contains(&id) || id.ends_with("_concrete_powder") {
+        return Some(ToolKind::Shovel);
+    }
+    if HOE_BLOCKS.contains(&id) || id.ends_with("_leaves") {
+        return Some(ToolKind::Hoe);
+    }
+    if PICKAXE_BLOCKS.contains(&id) || block.behavior().requires_correct_tool_for_drops {
+        return Some(ToolKind::Pickaxe);
+    }
+    if AXE_BLOCKS.contains(&id)
+        || id.ends_with("_log")
+        || id.ends_with("_wood")
+        || id.ends_with("_stem")
+        || id.ends_with("_hyphae")
+        || id.ends_with("_planks")
+        || id.ends_with("_sign")
+        || id.ends_with("_banner")
+        || (WOOD_TYPES.iter().any(|wood| id.starts_with(wood))
+            && !id.ends_with("_nylium")
+            && !id.ends_with("_fungus")
+            && !id.ends_with("_roots")
+            && !id.ends_with("_sapling")
+            && !id.ends_with("_propagule"))
+    {
+        return Some(ToolKind::Axe);
+    }
+    None
+}
+
+/// The lowest tool tier level that can harvest the block, see
+/// [`ToolTier::level`].
+///
+/// Like [`mineable_with`], vanilla uses block tags for this.
+///
+/// [`ToolTier::level`]: azalea_inventory::item::ToolTier::level
+fn required_tool_level(block: &dyn Block) -> u8 {
+    let id = block.id();
+    let is_ore_of = |ore: &str| {
+        id == format!("{ore}_ore")
+            || id == format!("deepslate_{ore}_ore")
+            || id == format!("{ore}_block")
+            || id == format!("raw_{ore}_block")
+    };
+
+    if matches!(
+        id,
+        "obsidian" | "crying_obsidian" | "respawn_anchor" | "netherite_block" | "ancient_debris"
+    ) {
+        3
+    } else if is_ore_of("diamond")
+        || is_ore_of("emerald")
+        || is_ore_of("gold")
+        || is_ore_of("redstone")
+    {
+        2
+    } else if is_ore_of("iron")
+        || is_ore_of("lapis")
+        || id.contains("copper")
+        || id == "lightning_rod"
+    {
+        1
+    } else {
+        0
+    }
+}
+
+/// Whether mining the block with the given item would make it drop items.
+/// This also decides whether the block gets mined at the fast or the slow
+/// speed.
+pub fn is_correct_tool_for_drops(block: &dyn Block, item: azalea_registry::Item) -> bool {
+    let behavior = block.behavior();
+    if !behavior.requires_correct_tool_for_drops {
+        return true;
+    }
+    if item == azalea_registry::Item::Shears {
+        return matches!(block.id(), "cobweb" | "redstone_wire" | "tripwire");
+    }
+    let Some((kind, tier)) = item.tool() else {
+        return false;
+    };
+    Some(kind) == mineable_with(block) && tier.level() >= required_tool_level(block)
+}
+
+/// How fast the item mines the block, without taking enchantments or effects
+/// into account. This is 1 for items that don't make mining the block any
+/// faster.
+pub fn destroy_speed(block: &dyn Block, item: azalea_registry::Item) -> f32 {
+    let id = block.id();
+    if item == azalea_registry::Item::Shears {
+        return if id == "cobweb" || id.ends_with("_leaves") {
+            15.
+        } else if id.ends_with("_wool") {
+            5.
+        } else if id == "vine" || id == "glow_lichen" {
+            2.
+        } else {
+            1.
+        };
+    }
+    let Some((kind, tier)) = item.tool() else {
+        return 1.;
+    };
+    if kind == ToolKind::Sword {
+        return if id == "cobweb" {
+            15.
+        } else if id.ends_with("_leaves") || id == "vine" || id == "cocoa" || id == "pumpkin" {
+            1.5
+        } else {
+            1.
+        };
+    }
+    if Some(kind) == mineable_with(block) {
+        tier.speed()
+    } else {
+        1.
+    }
+}
+
+/// How fast a player mines the block while holding the given item, taking
+/// Efficiency, effects, and whether we're underwater or in the air into
+/// account.
+pub fn player_destroy_speed(
+    block: &dyn Block,
+    item: &ItemSlot,
+    modifiers: &MiningModifiers,
+) -> f32 {
+    let mut speed = destroy_speed(block, item.kind());
+    if speed > 1. {
+        if let ItemSlot::Present(item) = item {
+            let efficiency = item.enchantment_level(Enchantment::Efficiency);
+            if efficiency > 0 {
+                speed += (efficiency * efficiency + 1) as f32;
+            }
+        }
+    }
+    if let Some(haste) = modifiers.haste {
+        speed *= 1. + (haste as f32 + 1.) * 0.2;
+    }
+    if let Some(mining_fatigue) = modifiers.mining_fatigue {
+        speed *= match mining_fatigue {
+            0 => 0.3,
+            1 => 0.09,
+            2 => 0.0027,
+            _ => 8.1e-4,
+        };
+    }
+    if modifiers.underwater {
+        speed /= 5.;
+    }
+    if !modifiers.on_ground {
+        speed /= 5.;
+    }
+    speed
+}
+
+/// How much of the block gets mined every tick by a player holding the given
+/// item, where 1 is the whole block. This is 0 if the block can't be broken.
+pub fn destroy_progress(block: &dyn Block, item: &ItemSlot, modifiers: &MiningModifiers) -> f32 {
+    let destroy_time = block.behavior().destroy_time;
+    if destroy_time < 0. {
+        return 0.;
+    }
+    if destroy_time == 0. {
+        return 1.;
+    }
+    let divisor = if is_correct_tool_for_drops(block, item.kind()) {
+        30.
+    } else {
+        100.
+    };
+    player_destroy_speed(block, item, modifiers) / destroy_time / divisor
+}
+
+/// How much of the block gets mined every tick while holding the given item,
+/// where 1 is the whole block. This is 0 if the block can't be broken.
+///
+/// This assumes that we're on the ground and don't have any enchantments or
+/// effects, see [`destroy_progress`] if you need those.
+pub fn destroy_progress_per_tick(block: &dyn Block, item: azalea_registry::Item) -> f32 {
+    let item = if item == azalea_registry::Item::Air {
+        ItemSlot::Empty
+    } else {
+        ItemSlot::new(item, 1)
+    };
+    destroy_progress(block, &item, &MiningModifiers::default())
+}
+
+/// The number of ticks it takes to mine the block while holding the given
+/// item, or `None` if it can't be mined at all.
+///
+/// Blocks that break instantly take 0 ticks.
+pub fn ticks_to_destroy(block_state: BlockState, item: azalea_registry::Item) -> Option<u32> {
+    let block = Box::<dyn Block>::from(block_state);
+    let progress = destroy_progress_per_tick(block.as_ref(), item);
+    if progress <= 0. {
+        None
+    } else if progress >= 1. {
+        Some(0)
+    } else {
+        Some((1. / progress).ceil() as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azalea_registry::Item;
+
+    #[test]
+    fn test_ticks_to_destroy() {
+        let stone = azalea_registry::Block::Stone.into();
+        // 1.5 hardness * 100 for not having the right tool
+        assert_eq!(ticks_to_destroy(stone, Item::Air), Some(150));
+        // 1.5 hardness * 30 / 2 (wood speed)
+        assert_eq!(ticks_to_destroy(stone, Item::WoodenPickaxe), Some(23));
+        assert_eq!(ticks_to_destroy(stone, Item::DiamondPickaxe), Some(6));
+        // axes don't help with stone
+        assert_eq!(ticks_to_destroy(stone, Item::DiamondAxe), Some(150));
+
+        let dirt = azalea_registry::Block::Dirt.into();
+        assert_eq!(ticks_to_destroy(dirt, Item::Air), Some(15));
+        assert_eq!(ticks_to_destroy(dirt, Item::IronShovel), Some(3));
+
+        let torch = azalea_registry::Block::Torch.into();
+        assert_eq!(ticks_to_destroy(torch, Item::Air), Some(0));
+
+        let bedrock = azalea_registry::Block::Bedrock.into();
+        assert_eq!(ticks_to_destroy(bedrock, Item::NetheritePickaxe), None);
+    }
+
+    #[test]
+    fn test_correct_tool_tier() {
+        let diamond_ore =
+            Box::<dyn Block>::from(BlockState::from(azalea_registry::Block::DiamondOre));
+        assert!(!is_correct_tool_for_drops(
+            diamond_ore.as_ref(),
+            Item::StonePickaxe
+        ));
+        assert!(is_correct_tool_for_drops(
+            diamond_ore.as_ref(),
+            Item::IronPickaxe
+        ));
+        let oak_log = Box::<dyn Block>::from(BlockState::from(azalea_registry::Block::OakLog));
+        assert_eq!(mineable_with(oak_log.as_ref()), Some(ToolKind::Axe));
+        assert!(is_correct_tool_for_drops(oak_log.as_ref(), Item::Air));
+    }
+
+    fn enchanted_item(kind: Item, enchantment: &str, level: i16) -> ItemSlot {
+        use azalea_nbt::{Nbt, NbtCompound, NbtList};
+        let enchantments = NbtList::Compound(vec![NbtCompound::from_iter([
+            ("id".into(), Nbt::String(enchantment.into())),
+            ("lvl".into(), Nbt::Short(level)),
+        ])]);
+        let tag = NbtCompound::from_iter([("Enchantments".into(), Nbt::List(enchantments))]);
+        ItemSlot::Present(azalea_inventory::ItemSlotData {
+            kind,
+            count: 1,
+            nbt: Nbt::Compound(NbtCompound::from_iter([("".into(), Nbt::Compound(tag))])),
+        })
+    }
+
+    #[test]
+    fn test_destroy_progress_modifiers() {
+        let stone = Box::<dyn Block>::from(BlockState::from(azalea_registry::Block::Stone));
+        let stone = stone.as_ref();
+        let pickaxe = enchanted_item(Item::DiamondPickaxe, "minecraft:unbreaking", 3);
+        let normal = MiningModifiers::default();
+        let assert_progress = |item: &ItemSlot, modifiers: &MiningModifiers, expected: f32| {
+            let progress = destroy_progress(stone, item, modifiers);
+            assert!(
+                (progress - expected).abs() < 1e-6,
+                "expected {expected}, got {progress}"
+            );
+        };
+        // 8 / 1.5 / 30
+        assert_progress(&pickaxe, &normal, 8. / 45.);
+
+        // efficiency 5 adds 5² + 1
+        let efficiency_pickaxe = enchanted_item(Item::DiamondPickaxe, "minecraft:efficiency", 5);
+        assert_progress(&efficiency_pickaxe, &normal, 34. / 45.);
+        // but not when the tool isn't the right one
+        let efficiency_shovel = enchanted_item(Item::DiamondShovel, "minecraft:efficiency", 5);
+        assert_progress(&efficiency_shovel, &normal, 1. / 150.);
+
+        let haste = MiningModifiers {
+            haste: Some(1),
+            ..normal
+        };
+        assert_progress(&pickaxe, &haste, 8. * 1.4 / 45.);
+        let mining_fatigue = MiningModifiers {
+            mining_fatigue: Some(0),
+            ..normal
+        };
+        assert_progress(&pickaxe, &mining_fatigue, 8. * 0.3 / 45.);
+
+        let underwater_in_air = MiningModifiers {
+            underwater: true,
+            on_ground: false,
+            ..normal
+        };
+        assert_progress(&pickaxe, &underwater_in_air, 8. / 25. / 45.);
+    }
 }
@@ -1,9 +1,16 @@
-use std::{collections::HashSet, io::Cursor, sync::Arc};
+use std::{collections::HashSet, io::Cursor, str::FromStr, sync::Arc, time::Duration};
 
-use azalea_core::{ChunkPos, GameMode, ResourceLocation, Vec3};
+use azalea_block::BlockState;
+use azalea_chat::FormattedText;
+use azalea_core::{
+    rotation_from_byte, velocity_from_shorts, BlockPos, ChunkBlockPos, ChunkPos, GameMode,
+    ResourceLocation, Vec3,
+};
+use azalea_nbt::Nbt;
 use azalea_protocol::{
     connect::{ReadConnection, WriteConnection},
     packets::game::{
+        clientbound_light_update_packet::ClientboundLightUpdatePacketData,
         clientbound_player_combat_kill_packet::ClientboundPlayerCombatKillPacket,
         serverbound_accept_teleportation_packet::ServerboundAcceptTeleportationPacket,
         serverbound_custom_payload_packet::ServerboundCustomPayloadPacket,
@@ -16,22 +23,25 @@ use azalea_protocol::{
     read::ReadPacketError,
 };
 use azalea_world::{
+    block_entity::BlockEntity,
     entity::{
         metadata::{apply_metadata, Health, PlayerMetadataBundle},
-        Dead, EntityBundle, EntityKind, EntityUpdateSet, LastSentPosition, LookDirection,
-        MinecraftEntityId, Physics, PlayerBundle, Position, WorldName,
+        ActiveEffects, Attributes, Dead, EntityBundle, EntityKind, EntityUpdateSet, Equipment,
+        HeadRotation, LastSentPosition, Local, LookDirection, MinecraftEntityId, MobEffectData,
+        Physics, PlayerBundle, Position, WorldName,
     },
     entity::{LoadedBy, RelativeEntityUpdate},
-    InstanceContainer, PartialInstance,
+    Chunk, InstanceContainer, PartialInstance, WorldBorder,
 };
 use bevy_app::{App, CoreSet, Plugin};
 use bevy_ecs::{
     component::Component,
     entity::Entity,
     event::{EventReader, EventWriter, Events},
+    query::Without,
     schedule::IntoSystemConfig,
     system::{Commands, Query, ResMut, SystemState},
-    world::World,
+    world::{EntityMut, World},
 };
 use log::{debug, error, trace, warn};
 use parking_lot::Mutex;
@@ -41,11 +51,17 @@ use crate::{
     chat::{ChatPacket, ChatReceivedEvent},
     client::{PlayerAbilities, TabList},
     disconnect::DisconnectEvent,
+    hud::{ActionBar, ActionBarEvent, BossBarUpdateEvent, BossBars, Titles, TitlesUpdateEvent},
+    interact::LastAckedSequenceNumber,
     inventory::{
         ClientSideCloseContainerEvent, InventoryComponent, MenuOpenedEvent,
-        SetContainerContentEvent,
+        SetContainerContentEvent, SetContainerDataEvent,
     },
     local_player::{GameProfileComponent, LocalGameMode, LocalPlayer},
+    merchant::MerchantOffers,
+    recipe_book::RecipeBook,
+    scoreboard::{Scoreboard, ScoreboardUpdate, ScoreboardUpdateEvent},
+    weather::{Weather, WeatherChangeEvent},
     ClientInformation, PlayerInfo,
 };
 
@@ -92,9 +108,17 @@ impl Plugin for PacketHandlerPlugin {
             .add_event::<AddPlayerEvent>()
             .add_event::<RemovePlayerEvent>()
             .add_event::<UpdatePlayerEvent>()
+            .add_event::<TabListHeaderEvent>()
             .add_event::<ChatReceivedEvent>()
             .add_event::<DeathEvent>()
-            .add_event::<KeepAliveEvent>();
+            .add_event::<GameModeChangeEvent>()
+            .add_event::<DemoEvent>()
+            .add_event::<WinGameEvent>()
+            .add_event::<KeepAliveEvent>()
+            .add_event::<BlockUpdateEvent>()
+            .add_event::<ChunkLoadedEvent>()
+            .add_event::<ChunkUnloadedEvent>()
+            .add_event::<ScoreboardUpdateEvent>();
     }
 }
 
@@ -123,6 +147,15 @@ pub struct UpdatePlayerEvent {
     pub info: PlayerInfo,
 }
 
+/// The header or footer of the tab list of a local player changed.
+#[derive(Debug, Clone)]
+pub struct TabListHeaderEvent {
+    /// The local player entity that received this event.
+    pub entity: Entity,
+    pub header: FormattedText,
+    pub footer: FormattedText,
+}
+
 /// Event for when an entity dies. dies. If it's a local player and there's a
 /// reason in the death screen, the [`ClientboundPlayerCombatKillPacket`] will
 /// be included.
@@ -132,6 +165,63 @@ pub struct DeathEvent {
     pub packet: Option<ClientboundPlayerCombatKillPacket>,
 }
 
+/// The server changed the game mode of a local player.
+#[derive(Debug, Clone)]
+pub struct GameModeChangeEvent {
+    pub entity: Entity,
+    pub previous: GameMode,
+    pub current: GameMode,
+}
+
+/// The server showed a message that's only for players in the demo version of
+/// the game.
+#[derive(Debug, Clone)]
+pub struct DemoEvent {
+    pub entity: Entity,
+    pub message: DemoMessage,
+}
+
+/// A message that the server shows to players in the demo, see [`DemoEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DemoMessage {
+    /// The screen that's shown when the demo starts.
+    Welcome,
+    /// A hint about the keys for walking around.
+    MovementControls,
+    /// A hint about the key for jumping.
+    JumpControl,
+    /// A hint about the key for opening the inventory.
+    InventoryControl,
+    /// The demo's time is up.
+    DemoOver,
+}
+impl DemoMessage {
+    /// Get the message from the parameter of a `DemoEvent` game event, or
+    /// `None` if it's not one we know about.
+    pub fn from_param(param: f32) -> Option<Self> {
+        match param as u32 {
+            0 => Some(Self::Welcome),
+            101 => Some(Self::MovementControls),
+            102 => Some(Self::JumpControl),
+            103 => Some(Self::InventoryControl),
+            104 => Some(Self::DemoOver),
+            _ => None,
+        }
+    }
+}
+
+/// A local player went through the end portal after killing the ender dragon,
+/// so the vanilla client would show the credits. The server waits for us to
+/// respawn before putting us back in the overworld, which `azalea` does
+/// automatically with its `AutoRespawnPlugin`.
+#[derive(Debug, Clone)]
+pub struct WinGameEvent {
+    pub entity: Entity,
+    /// Whether the credits would be shown. This is false if the player has
+    /// already seen them before.
+    pub show_credits: bool,
+}
+
 /// A KeepAlive packet is sent from the server to verify that the client is
 /// still connected.
 #[derive(Debug, Clone)]
@@ -142,6 +232,33 @@ pub struct KeepAliveEvent {
     pub id: u64,
 }
 
+/// A chunk was loaded by a local player. If the chunk was already loaded by
+/// another client in the same world, this is sent without the chunk being
+/// parsed again.
+#[derive(Debug, Clone)]
+pub struct ChunkLoadedEvent {
+    pub entity: Entity,
+    pub pos: ChunkPos,
+}
+
+/// A chunk was unloaded by a local player because it's outside of the view
+/// distance. The chunk might still be loaded by other clients in the same
+/// world.
+#[derive(Debug, Clone)]
+pub struct ChunkUnloadedEvent {
+    pub entity: Entity,
+    pub pos: ChunkPos,
+}
+
+/// A block in the world of a local player was changed by the server. This is
+/// sent for both the `BlockUpdate` and `SectionBlocksUpdate` packets.
+#[derive(Debug, Clone)]
+pub struct BlockUpdateEvent {
+    pub entity: Entity,
+    pub position: BlockPos,
+    pub block_state: BlockState,
+}
+
 /// Something that receives packets from the server.
 #[derive(Component, Clone)]
 pub struct PacketReceiver {
@@ -233,6 +350,7 @@ fn process_packet_events(ecs: &mut World) {
                         dimension.height,
                         dimension.min_y,
                     );
+                    weak_world.write().biome_names = p.registry_holder.root.biome_names();
                     // set the partial_world to an empty world
                     // (when we add chunks or entities those will be in the
                     // instance_container)
@@ -263,6 +381,12 @@ fn process_packet_events(ecs: &mut World) {
                             previous: p.previous_game_type.into(),
                         },
                         player_bundle,
+                        Scoreboard::default(),
+                        BossBars::default(),
+                        Titles::default(),
+                        ActionBar::default(),
+                        Weather::default(),
+                        RecipeBook::default(),
                     ));
                 }
 
@@ -323,14 +447,28 @@ fn process_packet_events(ecs: &mut World) {
                 // bye
                 return;
             }
-            ClientboundGamePacket::UpdateRecipes(_p) => {
+            ClientboundGamePacket::UpdateRecipes(p) => {
                 debug!("Got update recipes packet");
+
+                let mut system_state: SystemState<Query<&mut RecipeBook>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let Ok(mut recipe_book) = query.get_mut(player_entity) else {
+                    continue;
+                };
+                recipe_book.update_recipes(&p.recipes);
             }
             ClientboundGamePacket::EntityEvent(_p) => {
                 // debug!("Got entity event packet {:?}", p);
             }
-            ClientboundGamePacket::Recipe(_p) => {
+            ClientboundGamePacket::Recipe(p) => {
                 debug!("Got recipe packet");
+
+                let mut system_state: SystemState<Query<&mut RecipeBook>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let Ok(mut recipe_book) = query.get_mut(player_entity) else {
+                    continue;
+                };
+                recipe_book.apply_recipe_packet(&p);
             }
             ClientboundGamePacket::PlayerPosition(p) => {
                 // TODO: reply with teleport confirm
@@ -437,13 +575,12 @@ fn process_packet_events(ecs: &mut World) {
                 for updated_info in &p.entries {
                     // add the new player maybe
                     if p.actions.add_player {
-                        let info = PlayerInfo {
-                            profile: updated_info.profile.clone(),
-                            uuid: updated_info.profile.uuid,
-                            gamemode: updated_info.game_mode,
-                            latency: updated_info.latency,
-                            display_name: updated_info.display_name.clone(),
-                        };
+                        let info = PlayerInfo::new(
+                            updated_info.profile.clone(),
+                            updated_info.game_mode,
+                            updated_info.latency,
+                            updated_info.display_name.clone(),
+                        );
                         tab_list.insert(updated_info.profile.uuid, info.clone());
                         add_player_events.send(AddPlayerEvent {
                             entity: player_entity,
@@ -453,10 +590,10 @@ fn process_packet_events(ecs: &mut World) {
                         // `else if` because the block for add_player above
                         // already sets all the fields
                         if p.actions.update_game_mode {
-                            info.gamemode = updated_info.game_mode;
+                            info.set_gamemode(updated_info.game_mode);
                         }
                         if p.actions.update_latency {
-                            info.latency = updated_info.latency;
+                            info.set_latency(updated_info.latency);
                         }
                         if p.actions.update_display_name {
                             info.display_name = updated_info.display_name.clone();
@@ -500,16 +637,45 @@ fn process_packet_events(ecs: &mut World) {
 
                 partial_world.chunks.view_center = ChunkPos::new(p.x, p.z);
             }
-            ClientboundGamePacket::ChunksBiomes(_) => {}
-            ClientboundGamePacket::LightUpdate(_p) => {
-                // debug!("Got light update packet {:?}", p);
+            ClientboundGamePacket::ChunksBiomes(p) => {
+                debug!("Got chunks biomes packet");
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+                let world = local_player.world.read();
+
+                for chunk_biome_data in &p.chunk_biome_data {
+                    let Some(chunk) = world.chunks.get(&chunk_biome_data.pos) else {
+                        continue;
+                    };
+                    let mut chunk = chunk.write();
+                    if let Err(e) = chunk.read_biomes(&mut Cursor::new(&chunk_biome_data.buffer)) {
+                        error!("Couldn't set chunk biomes: {}", e);
+                    }
+                }
+            }
+            ClientboundGamePacket::LightUpdate(p) => {
+                trace!("Got light update packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+                let world = local_player.world.read();
+
+                if let Some(chunk) = world.chunks.get(&ChunkPos::new(p.x, p.z)) {
+                    update_chunk_light(&mut chunk.write(), &p.light_data);
+                }
             }
             ClientboundGamePacket::LevelChunkWithLight(p) => {
                 debug!("Got chunk with light packet {} {}", p.x, p.z);
                 let pos = ChunkPos::new(p.x, p.z);
 
-                let mut system_state: SystemState<Query<&mut LocalPlayer>> = SystemState::new(ecs);
-                let mut query = system_state.get_mut(ecs);
+                let mut system_state: SystemState<(
+                    Query<&mut LocalPlayer>,
+                    EventWriter<ChunkLoadedEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut chunk_loaded_events) = system_state.get_mut(ecs);
                 let local_player = query.get_mut(player_entity).unwrap();
 
                 // OPTIMIZATION: if we already know about the chunk from the
@@ -539,16 +705,43 @@ fn process_packet_events(ecs: &mut World) {
                             Some(shared_chunk.clone()),
                             &mut world.chunks,
                         );
+                        chunk_loaded_events.send(ChunkLoadedEvent {
+                            entity: player_entity,
+                            pos,
+                        });
                         continue;
                     }
                 }
 
+                let block_entities = p
+                    .chunk_data
+                    .block_entities
+                    .iter()
+                    .map(|block_entity| {
+                        let pos = ChunkBlockPos::new(
+                            block_entity.packed_xz >> 4,
+                            block_entity.y as i16 as i32,
+                            block_entity.packed_xz & 15,
+                        );
+                        (
+                            pos,
+                            BlockEntity::new(block_entity.kind, block_entity.data.clone()),
+                        )
+                    })
+                    .collect();
                 if let Err(e) = partial_world.chunks.replace_with_packet_data(
                     &pos,
                     &mut Cursor::new(&p.chunk_data.data),
+                    block_entities,
                     &mut world.chunks,
                 ) {
                     error!("Couldn't set chunk data: {}", e);
+                } else if let Some(chunk) = world.chunks.get(&pos) {
+                    update_chunk_light(&mut chunk.write(), &p.light_data);
+                    chunk_loaded_events.send(ChunkLoadedEvent {
+                        entity: player_entity,
+                        pos,
+                    });
                 }
             }
             ClientboundGamePacket::AddEntity(p) => {
@@ -606,11 +799,58 @@ fn process_packet_events(ecs: &mut World) {
 
                 system_state.apply(ecs);
             }
-            ClientboundGamePacket::UpdateAttributes(_p) => {
+            ClientboundGamePacket::UpdateAttributes(p) => {
                 // debug!("Got update attributes packet {:?}", p);
+
+                let mut new_attributes = Vec::new();
+                for snapshot in p.attributes {
+                    let Ok(attribute) =
+                        azalea_registry::Attribute::from_str(&snapshot.attribute.to_string())
+                    else {
+                        warn!("Got unknown attribute {}", snapshot.attribute);
+                        continue;
+                    };
+                    new_attributes.push((attribute, snapshot.base, snapshot.modifiers));
+                }
+
+                update_entity(ecs, player_entity, p.entity_id, move |entity_mut| {
+                    let Some(mut attributes) = entity_mut.get_mut::<Attributes>() else {
+                        return;
+                    };
+                    for (attribute, base, modifiers) in new_attributes {
+                        attributes.set(attribute, base, modifiers);
+                    }
+                });
             }
-            ClientboundGamePacket::SetEntityMotion(_p) => {
+            ClientboundGamePacket::SetEntityMotion(p) => {
                 // debug!("Got entity velocity packet {:?}", p);
+
+                let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> =
+                    SystemState::new(ecs);
+                let (mut commands, query) = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let world = local_player.world.read();
+                let entity = world.entity_by_id(&MinecraftEntityId(p.id));
+                drop(world);
+
+                if let Some(entity) = entity {
+                    let delta = velocity_from_shorts(p.xa, p.ya, p.za);
+                    commands.entity(entity).add(RelativeEntityUpdate {
+                        partial_world: local_player.partial_instance.clone(),
+                        update: Box::new(move |entity_mut| {
+                            let mut physics = entity_mut.get_mut::<Physics>().unwrap();
+                            physics.delta = delta;
+                        }),
+                    });
+                } else {
+                    warn!(
+                        "Got set entity motion packet for unknown entity id {}",
+                        p.id
+                    );
+                }
+
+                system_state.apply(ecs);
             }
             ClientboundGamePacket::SetEntityLink(p) => {
                 debug!("Got set entity link packet {:?}", p);
@@ -645,9 +885,31 @@ fn process_packet_events(ecs: &mut World) {
             }
             ClientboundGamePacket::InitializeBorder(p) => {
                 debug!("Got initialize border packet {:?}", p);
+
+                update_world_border(ecs, player_entity, |world_border| {
+                    world_border.center_x = p.new_center_x;
+                    world_border.center_z = p.new_center_z;
+                    world_border.lerp_size_between(
+                        p.old_size,
+                        p.new_size,
+                        Duration::from_millis(p.lerp_time),
+                    );
+                    world_border.absolute_max_size = p.new_absolute_max_size;
+                    world_border.warning_blocks = p.warning_blocks;
+                    world_border.warning_time = p.warning_time;
+                });
             }
-            ClientboundGamePacket::SetTime(_p) => {
+            ClientboundGamePacket::SetTime(p) => {
                 // debug!("Got set time packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get(ecs);
+                let local_player = query.get(player_entity).unwrap();
+                local_player
+                    .world
+                    .write()
+                    .time
+                    .update(p.game_time, p.day_time as i64);
             }
             ClientboundGamePacket::SetDefaultSpawnPosition(p) => {
                 debug!("Got set default spawn position packet {:?}", p);
@@ -688,11 +950,15 @@ fn process_packet_events(ecs: &mut World) {
 
                 if let Some(entity) = entity {
                     let new_position = p.position;
+                    let new_look_direction = look_direction_from_bytes(p.x_rot, p.y_rot);
+                    let on_ground = p.on_ground;
                     commands.entity(entity).add(RelativeEntityUpdate {
                         partial_world: local_player.partial_instance.clone(),
                         update: Box::new(move |entity| {
                             let mut position = entity.get_mut::<Position>().unwrap();
                             **position = new_position;
+                            *entity.get_mut::<LookDirection>().unwrap() = new_look_direction;
+                            entity.get_mut::<Physics>().unwrap().on_ground = on_ground;
                         }),
                     });
                 } else {
@@ -704,8 +970,34 @@ fn process_packet_events(ecs: &mut World) {
             ClientboundGamePacket::UpdateAdvancements(p) => {
                 debug!("Got update advancements packet {:?}", p);
             }
-            ClientboundGamePacket::RotateHead(_p) => {
+            ClientboundGamePacket::RotateHead(p) => {
                 // debug!("Got rotate head packet {:?}", p);
+
+                let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> =
+                    SystemState::new(ecs);
+                let (mut commands, query) = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let world = local_player.world.read();
+                let entity = world.entity_by_id(&MinecraftEntityId(p.entity_id));
+                drop(world);
+
+                if let Some(entity) = entity {
+                    let new_head_rotation = rotation_from_byte(p.y_head_rot);
+                    commands.entity(entity).add(RelativeEntityUpdate {
+                        partial_world: local_player.partial_instance.clone(),
+                        update: Box::new(move |entity_mut| {
+                            **entity_mut.get_mut::<HeadRotation>().unwrap() = new_head_rotation;
+                        }),
+                    });
+                } else {
+                    warn!(
+                        "Got rotate head packet for unknown entity id {}",
+                        p.entity_id
+                    );
+                }
+
+                system_state.apply(ecs);
             }
             ClientboundGamePacket::MoveEntityPos(p) => {
                 let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> =
@@ -719,11 +1011,13 @@ fn process_packet_events(ecs: &mut World) {
 
                 if let Some(entity) = entity {
                     let delta = p.delta.clone();
+                    let on_ground = p.on_ground;
                     commands.entity(entity).add(RelativeEntityUpdate {
                         partial_world: local_player.partial_instance.clone(),
                         update: Box::new(move |entity_mut| {
                             let mut position = entity_mut.get_mut::<Position>().unwrap();
                             **position = position.with_delta(&delta);
+                            entity_mut.get_mut::<Physics>().unwrap().on_ground = on_ground;
                         }),
                     });
                 } else {
@@ -747,11 +1041,15 @@ fn process_packet_events(ecs: &mut World) {
 
                 if let Some(entity) = entity {
                     let delta = p.delta.clone();
+                    let new_look_direction = look_direction_from_bytes(p.x_rot, p.y_rot);
+                    let on_ground = p.on_ground;
                     commands.entity(entity).add(RelativeEntityUpdate {
                         partial_world: local_player.partial_instance.clone(),
                         update: Box::new(move |entity_mut| {
                             let mut position = entity_mut.get_mut::<Position>().unwrap();
                             **position = position.with_delta(&delta);
+                            *entity_mut.get_mut::<LookDirection>().unwrap() = new_look_direction;
+                            entity_mut.get_mut::<Physics>().unwrap().on_ground = on_ground;
                         }),
                     });
                 } else {
@@ -764,8 +1062,36 @@ fn process_packet_events(ecs: &mut World) {
                 system_state.apply(ecs);
             }
 
-            ClientboundGamePacket::MoveEntityRot(_p) => {
+            ClientboundGamePacket::MoveEntityRot(p) => {
                 // debug!("Got move entity rot packet {:?}", p);
+
+                let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> =
+                    SystemState::new(ecs);
+                let (mut commands, query) = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let world = local_player.world.read();
+                let entity = world.entity_by_id(&MinecraftEntityId(p.entity_id));
+                drop(world);
+
+                if let Some(entity) = entity {
+                    let new_look_direction = look_direction_from_bytes(p.x_rot, p.y_rot);
+                    let on_ground = p.on_ground;
+                    commands.entity(entity).add(RelativeEntityUpdate {
+                        partial_world: local_player.partial_instance.clone(),
+                        update: Box::new(move |entity_mut| {
+                            *entity_mut.get_mut::<LookDirection>().unwrap() = new_look_direction;
+                            entity_mut.get_mut::<Physics>().unwrap().on_ground = on_ground;
+                        }),
+                    });
+                } else {
+                    warn!(
+                        "Got move entity rot packet for unknown entity id {}",
+                        p.entity_id
+                    );
+                }
+
+                system_state.apply(ecs);
             }
             ClientboundGamePacket::KeepAlive(p) => {
                 debug!("Got keep alive packet {p:?} for {player_entity:?}");
@@ -821,29 +1147,44 @@ fn process_packet_events(ecs: &mut World) {
             ClientboundGamePacket::BlockUpdate(p) => {
                 debug!("Got block update packet {:?}", p);
 
-                let mut system_state: SystemState<Query<&mut LocalPlayer>> = SystemState::new(ecs);
-                let mut query = system_state.get_mut(ecs);
+                let mut system_state: SystemState<(
+                    Query<&mut LocalPlayer>,
+                    EventWriter<BlockUpdateEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut block_update_events) = system_state.get_mut(ecs);
                 let local_player = query.get_mut(player_entity).unwrap();
 
                 let world = local_player.world.write();
 
                 world.chunks.set_block_state(&p.pos, p.block_state);
+                block_update_events.send(BlockUpdateEvent {
+                    entity: player_entity,
+                    position: p.pos,
+                    block_state: p.block_state,
+                });
             }
             ClientboundGamePacket::Animate(p) => {
                 debug!("Got animate packet {:?}", p);
             }
             ClientboundGamePacket::SectionBlocksUpdate(p) => {
                 debug!("Got section blocks update packet {:?}", p);
-                let mut system_state: SystemState<Query<&mut LocalPlayer>> = SystemState::new(ecs);
-                let mut query = system_state.get_mut(ecs);
+                let mut system_state: SystemState<(
+                    Query<&mut LocalPlayer>,
+                    EventWriter<BlockUpdateEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut block_update_events) = system_state.get_mut(ecs);
                 let local_player = query.get_mut(player_entity).unwrap();
 
                 let world = local_player.world.write();
 
                 for state in &p.states {
-                    world
-                        .chunks
-                        .set_block_state(&(p.section_pos + state.pos.clone()), state.state);
+                    let position = p.section_pos + state.pos.clone();
+                    world.chunks.set_block_state(&position, state.state);
+                    block_update_events.send(BlockUpdateEvent {
+                        entity: player_entity,
+                        position,
+                        block_state: state.state,
+                    });
                 }
             }
             ClientboundGamePacket::GameEvent(p) => {
@@ -851,17 +1192,69 @@ fn process_packet_events(ecs: &mut World) {
 
                 debug!("Got game event packet {:?}", p);
 
-                #[allow(clippy::single_match)]
                 match p.event {
                     EventType::ChangeGameMode => {
-                        let mut system_state: SystemState<Query<&mut LocalGameMode>> =
-                            SystemState::new(ecs);
-                        let mut query = system_state.get_mut(ecs);
+                        let mut system_state: SystemState<(
+                            Query<&mut LocalGameMode>,
+                            EventWriter<GameModeChangeEvent>,
+                        )> = SystemState::new(ecs);
+                        let (mut query, mut game_mode_change_events) = system_state.get_mut(ecs);
                         let mut local_game_mode = query.get_mut(player_entity).unwrap();
                         if let Some(new_game_mode) = GameMode::from_id(p.param as u8) {
-                            local_game_mode.current = new_game_mode;
+                            if new_game_mode != local_game_mode.current {
+                                let previous = local_game_mode.current;
+                                local_game_mode.previous = Some(previous);
+                                local_game_mode.current = new_game_mode;
+                                game_mode_change_events.send(GameModeChangeEvent {
+                                    entity: player_entity,
+                                    previous,
+                                    current: new_game_mode,
+                                });
+                            }
+                        }
+                    }
+                    EventType::StartRaining
+                    | EventType::StopRaining
+                    | EventType::RainLevelChange
+                    | EventType::ThunderLevelChange => {
+                        let mut system_state: SystemState<(
+                            Query<&mut Weather>,
+                            EventWriter<WeatherChangeEvent>,
+                        )> = SystemState::new(ecs);
+                        let (mut query, mut weather_change_events) = system_state.get_mut(ecs);
+                        let Ok(mut weather) = query.get_mut(player_entity) else {
+                            continue;
+                        };
+                        for change in weather.apply_game_event(p.event, p.param) {
+                            weather_change_events.send(WeatherChangeEvent {
+                                entity: player_entity,
+                                change,
+                            });
                         }
                     }
+                    EventType::WinGame => {
+                        let mut system_state: SystemState<EventWriter<WinGameEvent>> =
+                            SystemState::new(ecs);
+                        let mut win_game_events = system_state.get_mut(ecs);
+                        // the vanilla client skips the credits if the param is 0
+                        win_game_events.send(WinGameEvent {
+                            entity: player_entity,
+                            show_credits: p.param != 0.,
+                        });
+                    }
+                    EventType::DemoEvent => {
+                        let Some(message) = DemoMessage::from_param(p.param) else {
+                            warn!("Got unknown demo event with param {}", p.param);
+                            continue;
+                        };
+                        let mut system_state: SystemState<EventWriter<DemoEvent>> =
+                            SystemState::new(ecs);
+                        let mut demo_events = system_state.get_mut(ecs);
+                        demo_events.send(DemoEvent {
+                            entity: player_entity,
+                            message,
+                        });
+                    }
                     _ => {}
                 }
             }
@@ -873,19 +1266,81 @@ fn process_packet_events(ecs: &mut World) {
             }
             ClientboundGamePacket::SetEquipment(p) => {
                 debug!("Got set equipment packet {:?}", p);
+
+                update_entity(ecs, player_entity, p.entity as u32, move |entity_mut| {
+                    if let Some(mut equipment) = entity_mut.get_mut::<Equipment>() {
+                        p.slots.apply_to(&mut equipment);
+                    }
+                });
             }
             ClientboundGamePacket::UpdateMobEffect(p) => {
                 debug!("Got update mob effect packet {:?}", p);
+
+                update_entity(ecs, player_entity, p.entity_id, move |entity_mut| {
+                    if let Some(mut active_effects) = entity_mut.get_mut::<ActiveEffects>() {
+                        active_effects.insert(
+                            p.effect,
+                            MobEffectData {
+                                amplifier: p.effect_amplifier,
+                                duration_ticks: p.effect_duration_ticks,
+                            },
+                        );
+                    }
+                });
             }
             ClientboundGamePacket::AddExperienceOrb(_) => {}
             ClientboundGamePacket::AwardStats(_) => {}
-            ClientboundGamePacket::BlockChangedAck(_) => {}
+            ClientboundGamePacket::BlockChangedAck(p) => {
+                debug!("Got block changed ack packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&mut LastAckedSequenceNumber>> =
+                    SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let mut last_acked_sequence_number = query.get_mut(player_entity).unwrap();
+                **last_acked_sequence_number =
+                    (**last_acked_sequence_number).max(p.sequence as u32);
+            }
             ClientboundGamePacket::BlockDestruction(_) => {}
-            ClientboundGamePacket::BlockEntityData(_) => {}
+            ClientboundGamePacket::BlockEntityData(p) => {
+                debug!("Got block entity data packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+                let world = local_player.world.read();
+
+                // the server sends an empty tag when it only wants the client to know the
+                // block entity exists, so don't overwrite data we already have
+                if p.tag == Nbt::End && world.chunks.get_block_entity(&p.pos).is_some() {
+                    continue;
+                }
+                world.chunks.set_block_entity(
+                    &p.pos,
+                    Some(BlockEntity::new(p.block_entity_type, p.tag.clone())),
+                );
+            }
             ClientboundGamePacket::BlockEvent(p) => {
                 debug!("Got block event packet {:?}", p);
             }
-            ClientboundGamePacket::BossEvent(_) => {}
+            ClientboundGamePacket::BossEvent(p) => {
+                debug!("Got boss event packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut BossBars>,
+                    EventWriter<BossBarUpdateEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut boss_bar_update_events) = system_state.get_mut(ecs);
+                let Ok(mut boss_bars) = query.get_mut(player_entity) else {
+                    continue;
+                };
+
+                if let Some(update) = boss_bars.apply_operation(p.id, &p.operation) {
+                    boss_bar_update_events.send(BossBarUpdateEvent {
+                        entity: player_entity,
+                        update,
+                    });
+                }
+            }
             ClientboundGamePacket::CommandSuggestions(_) => {}
             ClientboundGamePacket::ContainerSetContent(p) => {
                 debug!("Got container set content packet {:?}", p);
@@ -905,27 +1360,35 @@ fn process_packet_events(ecs: &mut World) {
                             *slot_mut = slot.clone();
                         }
                     }
+                    // the state id and carried item belong to the menu that's open
+                    if inventory.id == 0 {
+                        inventory.state_id = p.state_id;
+                        inventory.carried = p.carried_item.clone();
+                    }
                 } else {
                     events.send(SetContainerContentEvent {
                         entity: player_entity,
                         slots: p.items.clone(),
                         container_id: p.container_id as u8,
+                        state_id: p.state_id,
+                        carried_item: p.carried_item.clone(),
                     });
                 }
             }
             ClientboundGamePacket::ContainerSetData(p) => {
                 debug!("Got container set data packet {:?}", p);
-                // let mut system_state: SystemState<Query<&mut
-                // InventoryComponent>> =
-                //     SystemState::new(ecs);
-                // let mut query = system_state.get_mut(ecs);
-                // let mut inventory =
-                // query.get_mut(player_entity).unwrap();
-
-                // TODO: handle ContainerSetData packet
-                // this is used for various things like the furnace progress
-                // bar
+
+                // this is used for various things like the furnace progress bar
                 // see https://wiki.vg/Protocol#Set_Container_Property
+                let mut system_state: SystemState<EventWriter<SetContainerDataEvent>> =
+                    SystemState::new(ecs);
+                let mut events = system_state.get_mut(ecs);
+                events.send(SetContainerDataEvent {
+                    entity: player_entity,
+                    container_id: p.container_id as u8,
+                    id: p.id,
+                    value: p.value,
+                });
             }
             ClientboundGamePacket::ContainerSetSlot(p) => {
                 debug!("Got container set slot packet {:?}", p);
@@ -978,10 +1441,59 @@ fn process_packet_events(ecs: &mut World) {
             ClientboundGamePacket::CustomChatCompletions(_) => {}
             ClientboundGamePacket::DeleteChat(_) => {}
             ClientboundGamePacket::Explode(_) => {}
-            ClientboundGamePacket::ForgetLevelChunk(_) => {}
+            ClientboundGamePacket::ForgetLevelChunk(p) => {
+                debug!("Got forget level chunk packet {:?}", p);
+                let pos = ChunkPos::new(p.x, p.z);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let entities_in_chunk = {
+                    let mut world = local_player.world.write();
+                    let mut partial_world = local_player.partial_instance.write();
+                    if !partial_world.chunks.unload(&pos, &mut world.chunks) {
+                        continue;
+                    }
+                    world
+                        .entities_by_chunk
+                        .get(&pos)
+                        .cloned()
+                        .unwrap_or_default()
+                };
+
+                #[allow(clippy::type_complexity)]
+                let mut system_state: SystemState<(
+                    Query<&mut LoadedBy, Without<Local>>,
+                    EventWriter<ChunkUnloadedEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut chunk_unloaded_events) = system_state.get_mut(ecs);
+
+                // we aren't loading the entities in the chunk anymore, so they'll be
+                // despawned if no other clients are loading them
+                for entity in entities_in_chunk {
+                    if let Ok(mut loaded_by) = query.get_mut(entity) {
+                        loaded_by.remove(&player_entity);
+                    }
+                }
+
+                chunk_unloaded_events.send(ChunkUnloadedEvent {
+                    entity: player_entity,
+                    pos,
+                });
+            }
             ClientboundGamePacket::HorseScreenOpen(_) => {}
             ClientboundGamePacket::MapItemData(_) => {}
-            ClientboundGamePacket::MerchantOffers(_) => {}
+            ClientboundGamePacket::MerchantOffers(p) => {
+                debug!("Got merchant offers packet {:?}", p);
+
+                let mut system_state: SystemState<Commands> = SystemState::new(ecs);
+                let mut commands = system_state.get_mut(ecs);
+                commands
+                    .entity(player_entity)
+                    .insert(MerchantOffers::from(&p));
+                system_state.apply(ecs);
+            }
             ClientboundGamePacket::MoveVehicle(_) => {}
             ClientboundGamePacket::OpenBook(_) => {}
             ClientboundGamePacket::OpenScreen(p) => {
@@ -1032,7 +1544,15 @@ fn process_packet_events(ecs: &mut World) {
                 system_state.apply(ecs);
             }
             ClientboundGamePacket::PlayerLookAt(_) => {}
-            ClientboundGamePacket::RemoveMobEffect(_) => {}
+            ClientboundGamePacket::RemoveMobEffect(p) => {
+                debug!("Got remove mob effect packet {:?}", p);
+
+                update_entity(ecs, player_entity, p.entity_id, move |entity_mut| {
+                    if let Some(mut active_effects) = entity_mut.get_mut::<ActiveEffects>() {
+                        active_effects.remove(&p.effect);
+                    }
+                });
+            }
             ClientboundGamePacket::ResourcePack(p) => {
                 debug!("Got resource pack packet {:?}", p);
 
@@ -1056,26 +1576,239 @@ fn process_packet_events(ecs: &mut World) {
             }
 
             ClientboundGamePacket::SelectAdvancementsTab(_) => {}
-            ClientboundGamePacket::SetActionBarText(_) => {}
-            ClientboundGamePacket::SetBorderCenter(_) => {}
-            ClientboundGamePacket::SetBorderLerpSize(_) => {}
-            ClientboundGamePacket::SetBorderSize(_) => {}
-            ClientboundGamePacket::SetBorderWarningDelay(_) => {}
-            ClientboundGamePacket::SetBorderWarningDistance(_) => {}
+            ClientboundGamePacket::SetActionBarText(p) => {
+                debug!("Got set action bar text packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut ActionBar>,
+                    EventWriter<ActionBarEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut action_bar_events) = system_state.get_mut(ecs);
+                let Ok(mut action_bar) = query.get_mut(player_entity) else {
+                    continue;
+                };
+
+                action_bar.set(p.text.clone());
+                action_bar_events.send(ActionBarEvent {
+                    entity: player_entity,
+                    text: p.text.clone(),
+                });
+            }
+            ClientboundGamePacket::SetBorderCenter(p) => {
+                debug!("Got set border center packet {:?}", p);
+
+                update_world_border(ecs, player_entity, |world_border| {
+                    world_border.center_x = p.new_center_x;
+                    world_border.center_z = p.new_center_z;
+                });
+            }
+            ClientboundGamePacket::SetBorderLerpSize(p) => {
+                debug!("Got set border lerp size packet {:?}", p);
+
+                update_world_border(ecs, player_entity, |world_border| {
+                    world_border.lerp_size_between(
+                        p.old_size,
+                        p.new_size,
+                        Duration::from_millis(p.lerp_time),
+                    );
+                });
+            }
+            ClientboundGamePacket::SetBorderSize(p) => {
+                debug!("Got set border size packet {:?}", p);
+
+                update_world_border(ecs, player_entity, |world_border| {
+                    world_border.set_size(p.size);
+                });
+            }
+            ClientboundGamePacket::SetBorderWarningDelay(p) => {
+                debug!("Got set border warning delay packet {:?}", p);
+
+                update_world_border(ecs, player_entity, |world_border| {
+                    world_border.warning_time = p.warning_delay;
+                });
+            }
+            ClientboundGamePacket::SetBorderWarningDistance(p) => {
+                debug!("Got set border warning distance packet {:?}", p);
+
+                update_world_border(ecs, player_entity, |world_border| {
+                    world_border.warning_blocks = p.warning_blocks;
+                });
+            }
             ClientboundGamePacket::SetCamera(_) => {}
-            ClientboundGamePacket::SetDisplayObjective(_) => {}
-            ClientboundGamePacket::SetObjective(_) => {}
+            ClientboundGamePacket::SetDisplayObjective(p) => {
+                debug!("Got set display objective packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut Scoreboard>,
+                    EventWriter<ScoreboardUpdateEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut scoreboard_update_events) = system_state.get_mut(ecs);
+                let Ok(mut scoreboard) = query.get_mut(player_entity) else {
+                    continue;
+                };
+
+                if let Some(update) =
+                    scoreboard.apply_set_display_objective(p.slot, &p.objective_name)
+                {
+                    scoreboard_update_events.send(ScoreboardUpdateEvent {
+                        entity: player_entity,
+                        update: ScoreboardUpdate::DisplayObjective(update),
+                    });
+                }
+            }
+            ClientboundGamePacket::SetObjective(p) => {
+                debug!("Got set objective packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut Scoreboard>,
+                    EventWriter<ScoreboardUpdateEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut scoreboard_update_events) = system_state.get_mut(ecs);
+                let Ok(mut scoreboard) = query.get_mut(player_entity) else {
+                    continue;
+                };
+
+                if let Some(update) = scoreboard.apply_set_objective(&p) {
+                    scoreboard_update_events.send(ScoreboardUpdateEvent {
+                        entity: player_entity,
+                        update: ScoreboardUpdate::Objective(update),
+                    });
+                }
+            }
             ClientboundGamePacket::SetPassengers(_) => {}
-            ClientboundGamePacket::SetPlayerTeam(_) => {}
-            ClientboundGamePacket::SetScore(_) => {}
+            ClientboundGamePacket::SetPlayerTeam(p) => {
+                debug!("Got set player team packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut Scoreboard>,
+                    EventWriter<ScoreboardUpdateEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut scoreboard_update_events) = system_state.get_mut(ecs);
+                let Ok(mut scoreboard) = query.get_mut(player_entity) else {
+                    continue;
+                };
+
+                if let Some(update) = scoreboard.apply_set_player_team(&p) {
+                    scoreboard_update_events.send(ScoreboardUpdateEvent {
+                        entity: player_entity,
+                        update: ScoreboardUpdate::Team(update),
+                    });
+                }
+            }
+            ClientboundGamePacket::SetScore(p) => {
+                debug!("Got set score packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut Scoreboard>,
+                    EventWriter<ScoreboardUpdateEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut scoreboard_update_events) = system_state.get_mut(ecs);
+                let Ok(mut scoreboard) = query.get_mut(player_entity) else {
+                    continue;
+                };
+
+                for update in scoreboard.apply_set_score(&p) {
+                    scoreboard_update_events.send(ScoreboardUpdateEvent {
+                        entity: player_entity,
+                        update: ScoreboardUpdate::Score(update),
+                    });
+                }
+            }
             ClientboundGamePacket::SetSimulationDistance(_) => {}
-            ClientboundGamePacket::SetSubtitleText(_) => {}
-            ClientboundGamePacket::SetTitleText(_) => {}
-            ClientboundGamePacket::SetTitlesAnimation(_) => {}
-            ClientboundGamePacket::ClearTitles(_) => {}
+            ClientboundGamePacket::SetSubtitleText(p) => {
+                debug!("Got set subtitle text packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut Titles>,
+                    EventWriter<TitlesUpdateEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut titles_update_events) = system_state.get_mut(ecs);
+                let Ok(mut titles) = query.get_mut(player_entity) else {
+                    continue;
+                };
+
+                titles.subtitle = Some(p.text.clone());
+                titles_update_events.send(TitlesUpdateEvent {
+                    entity: player_entity,
+                    titles: titles.clone(),
+                });
+            }
+            ClientboundGamePacket::SetTitleText(p) => {
+                debug!("Got set title text packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut Titles>,
+                    EventWriter<TitlesUpdateEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut titles_update_events) = system_state.get_mut(ecs);
+                let Ok(mut titles) = query.get_mut(player_entity) else {
+                    continue;
+                };
+
+                titles.set_title(p.text.clone());
+                titles_update_events.send(TitlesUpdateEvent {
+                    entity: player_entity,
+                    titles: titles.clone(),
+                });
+            }
+            ClientboundGamePacket::SetTitlesAnimation(p) => {
+                debug!("Got set titles animation packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut Titles>,
+                    EventWriter<TitlesUpdateEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut titles_update_events) = system_state.get_mut(ecs);
+                let Ok(mut titles) = query.get_mut(player_entity) else {
+                    continue;
+                };
+
+                titles.fade_in = p.fade_in;
+                titles.stay = p.stay;
+                titles.fade_out = p.fade_out;
+                titles_update_events.send(TitlesUpdateEvent {
+                    entity: player_entity,
+                    titles: titles.clone(),
+                });
+            }
+            ClientboundGamePacket::ClearTitles(p) => {
+                debug!("Got clear titles packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut Titles>,
+                    EventWriter<TitlesUpdateEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut titles_update_events) = system_state.get_mut(ecs);
+                let Ok(mut titles) = query.get_mut(player_entity) else {
+                    continue;
+                };
+
+                titles.clear(p.reset_times);
+                titles_update_events.send(TitlesUpdateEvent {
+                    entity: player_entity,
+                    titles: titles.clone(),
+                });
+            }
             ClientboundGamePacket::SoundEntity(_) => {}
             ClientboundGamePacket::StopSound(_) => {}
-            ClientboundGamePacket::TabList(_) => {}
+            ClientboundGamePacket::TabList(p) => {
+                debug!("Got tab list packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut TabList>,
+                    EventWriter<TabListHeaderEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut tab_list_header_events) = system_state.get_mut(ecs);
+                let mut tab_list = query.get_mut(player_entity).unwrap();
+
+                tab_list.header = p.header.clone();
+                tab_list.footer = p.footer.clone();
+                tab_list_header_events.send(TabListHeaderEvent {
+                    entity: player_entity,
+                    header: p.header.clone(),
+                    footer: p.footer.clone(),
+                });
+            }
             ClientboundGamePacket::TagQuery(_) => {}
             ClientboundGamePacket::TakeItemEntity(_) => {}
             ClientboundGamePacket::DisguisedChat(_) => {}
@@ -1125,3 +1858,62 @@ impl PacketReceiver {
         // receiver is automatically closed when it's dropped
     }
 }
+
+/// Update the entity with the given Minecraft entity ID with a
+/// [`RelativeEntityUpdate`], so the update only gets applied once if other
+/// clients in the same world get the packet too.
+fn update_entity(
+    ecs: &mut World,
+    player_entity: Entity,
+    entity_id: u32,
+    update: impl FnOnce(&mut EntityMut) + Send + Sync + 'static,
+) {
+    let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> = SystemState::new(ecs);
+    let (mut commands, query) = system_state.get_mut(ecs);
+    let local_player = query.get(player_entity).unwrap();
+
+    let entity = local_player
+        .world
+        .read()
+        .entity_by_id(&MinecraftEntityId(entity_id));
+    let Some(entity) = entity else {
+        warn!("Got update for unknown entity id {entity_id}");
+        return;
+    };
+    commands.entity(entity).add(RelativeEntityUpdate {
+        partial_world: local_player.partial_instance.clone(),
+        update: Box::new(update),
+    });
+
+    system_state.apply(ecs);
+}
+
+/// Change the world border of the instance that the player is in.
+fn update_world_border(ecs: &mut World, player_entity: Entity, f: impl FnOnce(&mut WorldBorder)) {
+    let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+    let query = system_state.get(ecs);
+    let local_player = query.get(player_entity).unwrap();
+    f(&mut local_player.world.write().world_border);
+}
+
+/// Convert the rotation that the server sends in entity packets to a
+/// [`LookDirection`].
+fn look_direction_from_bytes(x_rot: i8, y_rot: i8) -> LookDirection {
+    LookDirection {
+        x_rot: rotation_from_byte(x_rot),
+        y_rot: rotation_from_byte(y_rot),
+    }
+}
+
+fn update_chunk_light(chunk: &mut Chunk, light_data: &ClientboundLightUpdatePacketData) {
+    chunk.light.update_sky_light(
+        &light_data.sky_y_mask,
+        &light_data.empty_sky_y_mask,
+        &light_data.sky_updates,
+    );
+    chunk.light.update_block_light(
+        &light_data.block_y_mask,
+        &light_data.empty_block_y_mask,
+        &light_data.block_updates,
+    );
+}
@@ -1,5 +1,7 @@
+use std::collections::VecDeque;
+
 use azalea_auth::game_profile::GameProfile;
-use azalea_chat::FormattedText;
+use azalea_chat::{style::ChatFormatting, text_component::TextComponent, FormattedText};
 use azalea_core::GameMode;
 use azalea_world::entity::EntityInfos;
 use bevy_ecs::{
@@ -8,7 +10,7 @@ use bevy_ecs::{
 };
 use uuid::Uuid;
 
-use crate::{packet_handling::AddPlayerEvent, GameProfileComponent};
+use crate::{packet_handling::AddPlayerEvent, scoreboard::Scoreboard, GameProfileComponent};
 
 /// A player in the tab list.
 #[derive(Debug, Clone)]
@@ -27,6 +29,102 @@ pub struct PlayerInfo {
     /// from the player's normal username. Use `player_info.profile.name` to get
     /// the player's actual username.
     pub display_name: Option<FormattedText>,
+    /// The player's most recent gamemodes, oldest first and including the
+    /// current one. At most [`PlayerInfo::HISTORY_LENGTH`] are kept.
+    pub gamemode_history: VecDeque<GameMode>,
+    /// The player's most recent latencies, oldest first and including the
+    /// current one. At most [`PlayerInfo::HISTORY_LENGTH`] are kept.
+    pub latency_history: VecDeque<i32>,
+}
+
+impl PlayerInfo {
+    /// How many gamemodes and latencies are kept in the histories.
+    pub const HISTORY_LENGTH: usize = 20;
+
+    pub fn new(
+        profile: GameProfile,
+        gamemode: GameMode,
+        latency: i32,
+        display_name: Option<FormattedText>,
+    ) -> Self {
+        Self {
+            uuid: profile.uuid,
+            profile,
+            gamemode,
+            latency,
+            display_name,
+            gamemode_history: VecDeque::from([gamemode]),
+            latency_history: VecDeque::from([latency]),
+        }
+    }
+
+    /// Change the player's gamemode and add it to the history. The history
+    /// only changes if the gamemode is different from the current one.
+    pub fn set_gamemode(&mut self, gamemode: GameMode) {
+        if gamemode == self.gamemode {
+            return;
+        }
+        self.gamemode = gamemode;
+        push_with_limit(&mut self.gamemode_history, gamemode);
+    }
+
+    /// Change the player's latency and add it to the history.
+    pub fn set_latency(&mut self, latency: i32) {
+        self.latency = latency;
+        push_with_limit(&mut self.latency_history, latency);
+    }
+
+    /// The gamemode the player had before their current one, if we saw them
+    /// change it.
+    pub fn previous_gamemode(&self) -> Option<GameMode> {
+        let len = self.gamemode_history.len();
+        if len < 2 {
+            return None;
+        }
+        self.gamemode_history.get(len - 2).copied()
+    }
+
+    /// The mean of the latencies in [`Self::latency_history`], in
+    /// milliseconds.
+    pub fn average_latency(&self) -> f64 {
+        if self.latency_history.is_empty() {
+            return self.latency as f64;
+        }
+        self.latency_history.iter().map(|&l| l as f64).sum::<f64>()
+            / self.latency_history.len() as f64
+    }
+
+    /// The name that's shown for this player in the tab list.
+    ///
+    /// This is the player's [`Self::display_name`] if they have one, or
+    /// otherwise their username with their team's prefix, suffix, and color.
+    pub fn tab_list_name(&self, scoreboard: &Scoreboard) -> FormattedText {
+        if let Some(display_name) = &self.display_name {
+            return display_name.clone();
+        }
+        let mut name = TextComponent::new(self.profile.name.clone());
+        let Some(team) = scoreboard.player_team(&self.profile.name) else {
+            return FormattedText::Text(name);
+        };
+        if team.color != ChatFormatting::Reset {
+            name.base.style.apply_formatting(&team.color);
+        }
+
+        let mut text = TextComponent::new(String::new());
+        text.base.siblings = vec![
+            team.prefix.clone(),
+            FormattedText::Text(name),
+            team.suffix.clone(),
+        ];
+        FormattedText::Text(text)
+    }
+}
+
+fn push_with_limit<T>(history: &mut VecDeque<T>, value: T) {
+    if history.len() >= PlayerInfo::HISTORY_LENGTH {
+        history.pop_front();
+    }
+    history.push_back(value);
 }
 
 /// Add a [`GameProfileComponent`] when an [`AddPlayerEvent`] is received.
@@ -45,3 +143,99 @@ pub fn retroactively_add_game_profile_component(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::{scoreboard::Team, TabList};
+
+    fn player(name: &str, gamemode: GameMode) -> PlayerInfo {
+        let uuid = Uuid::from_u128(name.bytes().map(u128::from).sum());
+        PlayerInfo::new(GameProfile::new(uuid, name.to_string()), gamemode, 0, None)
+    }
+
+    fn team(name: &str, color: ChatFormatting, players: &[&str]) -> Team {
+        Team {
+            name: name.to_string(),
+            display_name: FormattedText::from(name),
+            prefix: FormattedText::from("["),
+            suffix: FormattedText::from("]"),
+            color,
+            allow_friendly_fire: true,
+            see_friendly_invisibles: true,
+            nametag_visibility: "always".to_string(),
+            collision_rule: "always".to_string(),
+            players: players
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<HashSet<_>>(),
+        }
+    }
+
+    #[test]
+    fn test_histories() {
+        let mut info = player("a", GameMode::Survival);
+        assert_eq!(info.previous_gamemode(), None);
+        info.set_gamemode(GameMode::Creative);
+        assert_eq!(info.previous_gamemode(), Some(GameMode::Survival));
+        // the server resending the same gamemode isn't a change
+        info.set_gamemode(GameMode::Creative);
+        assert_eq!(info.previous_gamemode(), Some(GameMode::Survival));
+
+        for latency in 1..=PlayerInfo::HISTORY_LENGTH as i32 + 5 {
+            info.set_latency(latency * 10);
+        }
+        assert_eq!(info.latency_history.len(), PlayerInfo::HISTORY_LENGTH);
+        assert_eq!(info.latency_history.front(), Some(&60));
+        assert_eq!(info.latency, 250);
+        assert!((info.average_latency() - 155.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sorted_tab_list() {
+        let mut scoreboard = Scoreboard::default();
+        for team in [
+            team("red", ChatFormatting::Red, &["c"]),
+            team("blue", ChatFormatting::Blue, &["D", "b"]),
+        ] {
+            for player in &team.players {
+                scoreboard
+                    .teams_by_player
+                    .insert(player.clone(), team.name.clone());
+            }
+            scoreboard.teams.insert(team.name.clone(), team);
+        }
+
+        let mut tab_list = TabList::default();
+        for info in [
+            player("a", GameMode::Spectator),
+            player("b", GameMode::Survival),
+            player("c", GameMode::Creative),
+            player("D", GameMode::Survival),
+            player("e", GameMode::Survival),
+        ] {
+            tab_list.insert(info.uuid, info);
+        }
+
+        let names = tab_list
+            .sorted(&scoreboard)
+            .into_iter()
+            .map(|info| info.profile.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["e", "b", "D", "c", "a"]);
+
+        let c = tab_list.get_by_name("c").unwrap();
+        assert_eq!(c.tab_list_name(&scoreboard).to_string(), "[c]");
+        assert_eq!(
+            tab_list
+                .get_by_name("e")
+                .unwrap()
+                .tab_list_name(&scoreboard)
+                .to_string(),
+            "e"
+        );
+        assert!(tab_list.get_by_name("f").is_none());
+    }
+}
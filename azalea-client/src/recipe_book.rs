@@ -0,0 +1,249 @@
+//! Keep track of the recipes that the server has and which of them we've
+//! unlocked, and tell the server to put recipes into crafting grids.
+
+use std::collections::{HashMap, HashSet};
+
+use azalea_core::ResourceLocation;
+use azalea_protocol::packets::game::{
+    clientbound_recipe_packet::{ClientboundRecipePacket, RecipeBookSettings, State},
+    clientbound_update_recipes_packet::{Ingredient, Recipe, RecipeData},
+    serverbound_place_recipe_packet::ServerboundPlaceRecipePacket,
+};
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component, entity::Entity, event::EventReader, schedule::IntoSystemConfig,
+    system::Query,
+};
+use log::warn;
+
+use crate::{inventory::InventoryComponent, local_player::handle_send_packet_event, LocalPlayer};
+
+/// A plugin that adds [`PlaceRecipeEvent`].
+pub struct RecipeBookPlugin;
+impl Plugin for RecipeBookPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlaceRecipeEvent>()
+            .add_system(handle_place_recipe_event.before(handle_send_packet_event));
+    }
+}
+
+/// A component with every recipe that the server has, and which of them are
+/// unlocked in our recipe book. This is reset every time we receive a login
+/// packet.
+#[derive(Component, Clone, Debug, Default)]
+pub struct RecipeBook {
+    /// All the recipes on the server, indexed by their id. These come from
+    /// the `UpdateRecipes` packet.
+    pub recipes: HashMap<ResourceLocation, Recipe>,
+    /// The ids of the recipes that we've unlocked. The server only lets us
+    /// use [`PlaceRecipeEvent`] for these.
+    pub unlocked: HashSet<ResourceLocation>,
+    /// The recipes that were unlocked recently, which the vanilla client
+    /// highlights in the recipe book.
+    pub highlighted: HashSet<ResourceLocation>,
+    /// Whether the recipe book is open and filtered in each kind of menu, or
+    /// `None` if the server hasn't sent a `Recipe` packet yet.
+    pub settings: Option<RecipeBookSettings>,
+}
+
+impl RecipeBook {
+    /// Replace all the recipes with the ones from an `UpdateRecipes` packet.
+    pub fn update_recipes(&mut self, recipes: &[Recipe]) {
+        self.recipes = recipes
+            .iter()
+            .map(|recipe| (recipe.identifier.clone(), recipe.clone()))
+            .collect();
+    }
+
+    /// Update which recipes are unlocked from a `Recipe` packet.
+    pub fn apply_recipe_packet(&mut self, packet: &ClientboundRecipePacket) {
+        self.settings = Some(packet.settings.clone());
+        match &packet.action {
+            State::Init { to_highlight } => {
+                self.unlocked = packet.recipes.iter().cloned().collect();
+                self.highlighted = to_highlight.iter().cloned().collect();
+            }
+            State::Add => {
+                self.unlocked.extend(packet.recipes.iter().cloned());
+                self.highlighted.extend(packet.recipes.iter().cloned());
+            }
+            State::Remove => {
+                for recipe in &packet.recipes {
+                    self.unlocked.remove(recipe);
+                    self.highlighted.remove(recipe);
+                }
+            }
+        }
+    }
+
+    /// Get a recipe by its id, like `minecraft:stick`.
+    pub fn get(&self, id: &ResourceLocation) -> Option<&Recipe> {
+        self.recipes.get(id)
+    }
+
+    /// Whether we've unlocked the recipe with the given id.
+    pub fn is_unlocked(&self, id: &ResourceLocation) -> bool {
+        self.unlocked.contains(id)
+    }
+
+    /// All the recipes that make the given item. Most items only have one,
+    /// but some (like planks) can be made in several ways.
+    pub fn recipes_for(&self, item: azalea_registry::Item) -> impl Iterator<Item = &Recipe> {
+        self.recipes.values().filter(move |recipe| {
+            recipe
+                .data
+                .result()
+                .map_or(false, |result| result.kind() == item)
+        })
+    }
+}
+
+/// Where each ingredient of a crafting recipe goes in a square crafting grid
+/// with the given width, going left to right and then top to bottom. Slots
+/// that should be left empty are `None`.
+///
+/// Returns `None` if the recipe isn't a normal crafting recipe or doesn't fit
+/// in the grid.
+pub fn crafting_layout(recipe: &RecipeData, grid_size: usize) -> Option<Vec<Option<&Ingredient>>> {
+    let mut layout = vec![None; grid_size * grid_size];
+    match recipe {
+        RecipeData::CraftingShaped(recipe) => {
+            if recipe.width > grid_size || recipe.height > grid_size {
+                return None;
+            }
+            for (i, ingredient) in recipe.ingredients.iter().enumerate() {
+                if !ingredient.is_empty() {
+                    let (x, y) = (i % recipe.width, i / recipe.width);
+                    layout[y * grid_size + x] = Some(ingredient);
+                }
+            }
+        }
+        RecipeData::CraftingShapeless(recipe) => {
+            if recipe.ingredients.len() > layout.len() {
+                return None;
+            }
+            for (slot, ingredient) in layout.iter_mut().zip(&recipe.ingredients) {
+                *slot = Some(ingredient);
+            }
+        }
+        _ => return None,
+    }
+    Some(layout)
+}
+
+/// Ask the server to fill the crafting grid in the open menu with the
+/// ingredients for a recipe, taking them from our inventory. This only works
+/// for recipes that we've unlocked.
+#[derive(Debug)]
+pub struct PlaceRecipeEvent {
+    pub entity: Entity,
+    pub window_id: u8,
+    pub recipe: ResourceLocation,
+    /// Whether to put as many of each ingredient as possible into the grid,
+    /// instead of just enough to craft it once.
+    pub shift_down: bool,
+}
+fn handle_place_recipe_event(
+    mut events: EventReader<PlaceRecipeEvent>,
+    query: Query<(&LocalPlayer, &InventoryComponent)>,
+) {
+    for event in events.iter() {
+        let (local_player, inventory) = query.get(event.entity).unwrap();
+        if inventory.id != event.window_id {
+            warn!(
+                "Tried to place recipe in container with ID {}, but the current container ID is {}",
+                event.window_id, inventory.id
+            );
+            continue;
+        }
+
+        local_player.write_packet(
+            ServerboundPlaceRecipePacket {
+                container_id: event.window_id,
+                recipe: event.recipe.clone(),
+                shift_down: event.shift_down,
+            }
+            .get(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use azalea_inventory::ItemSlot;
+    use azalea_protocol::packets::game::clientbound_update_recipes_packet::{
+        CraftingBookCategory, ShapedRecipe, ShapelessRecipe,
+    };
+    use azalea_registry::Item;
+
+    use super::*;
+
+    fn ingredient(kind: Option<Item>) -> Ingredient {
+        Ingredient {
+            allowed: kind
+                .map(|kind| ItemSlot::new(kind, 1))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_shaped_layout() {
+        // a stick is two planks on top of each other
+        let recipe = RecipeData::CraftingShaped(ShapedRecipe {
+            width: 1,
+            height: 2,
+            group: "sticks".to_string(),
+            category: CraftingBookCategory::Misc,
+            ingredients: vec![
+                ingredient(Some(Item::OakPlanks)),
+                ingredient(Some(Item::OakPlanks)),
+            ],
+            result: ItemSlot::new(Item::Stick, 1),
+            show_notification: true,
+        });
+        let layout = crafting_layout(&recipe, 3).unwrap();
+        let filled_slots = layout
+            .iter()
+            .enumerate()
+            .filter(|(_, ingredient)| ingredient.is_some())
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        assert_eq!(filled_slots, vec![0, 3]);
+        assert!(layout[0].unwrap().test(&ItemSlot::new(Item::OakPlanks, 1)));
+        assert!(!layout[0].unwrap().test(&ItemSlot::new(Item::Stick, 1)));
+        assert_eq!(crafting_layout(&recipe, 2).unwrap().len(), 4);
+
+        // a 3 wide recipe with gaps doesn't fit in the inventory
+        let recipe = RecipeData::CraftingShaped(ShapedRecipe {
+            width: 3,
+            height: 1,
+            group: String::new(),
+            category: CraftingBookCategory::Misc,
+            ingredients: vec![
+                ingredient(Some(Item::Stick)),
+                ingredient(None),
+                ingredient(Some(Item::Stick)),
+            ],
+            result: ItemSlot::new(Item::Ladder, 1),
+            show_notification: true,
+        });
+        assert!(crafting_layout(&recipe, 2).is_none());
+        let layout = crafting_layout(&recipe, 3).unwrap();
+        assert!(layout[0].is_some() && layout[1].is_none() && layout[2].is_some());
+    }
+
+    #[test]
+    fn test_shapeless_layout() {
+        let recipe = RecipeData::CraftingShapeless(ShapelessRecipe {
+            group: String::new(),
+            category: CraftingBookCategory::Misc,
+            ingredients: vec![ingredient(Some(Item::Dirt)); 5],
+            result: ItemSlot::new(Item::CoarseDirt, 1),
+        });
+        assert!(crafting_layout(&recipe, 2).is_none());
+        let layout = crafting_layout(&recipe, 3).unwrap();
+        assert_eq!(layout.iter().flatten().count(), 5);
+        assert_eq!(recipe.result(), Some(&ItemSlot::new(Item::CoarseDirt, 1)));
+    }
+}
@@ -0,0 +1,484 @@
+//! Keep track of the scoreboard, which is what servers use for things like the
+//! sidebar, the numbers below players' names, and teams.
+
+use std::collections::{HashMap, HashSet};
+
+use azalea_chat::{style::ChatFormatting, FormattedText};
+use azalea_protocol::packets::game::{
+    clientbound_set_objective_packet::{self, ClientboundSetObjectivePacket, RenderType},
+    clientbound_set_player_team_packet::{self, ClientboundSetPlayerTeamPacket},
+    clientbound_set_score_packet::{self, ClientboundSetScorePacket},
+};
+use bevy_ecs::{component::Component, entity::Entity};
+
+/// A component with the scoreboard that the server sent us. This is reset
+/// every time we receive a login packet.
+#[derive(Component, Clone, Debug, Default)]
+pub struct Scoreboard {
+    /// The objectives, indexed by their name.
+    pub objectives: HashMap<String, Objective>,
+    /// The scores that each score holder has, indexed by the score holder and
+    /// then by the objective name. Score holders are usually player names, but
+    /// they can also be entity UUIDs or any other string.
+    pub scores: HashMap<String, HashMap<String, i32>>,
+    /// The names of the objectives that are shown in each slot.
+    pub display_slots: HashMap<DisplaySlot, String>,
+    /// The teams, indexed by their name.
+    pub teams: HashMap<String, Team>,
+    /// The name of the team that each player is in.
+    pub teams_by_player: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Objective {
+    pub name: String,
+    pub display_name: FormattedText,
+    pub render_type: RenderType,
+}
+
+#[derive(Clone, Debug)]
+pub struct Team {
+    pub name: String,
+    pub display_name: FormattedText,
+    /// Text that's shown before the names of players in this team.
+    pub prefix: FormattedText,
+    /// Text that's shown after the names of players in this team.
+    pub suffix: FormattedText,
+    pub color: ChatFormatting,
+    pub allow_friendly_fire: bool,
+    pub see_friendly_invisibles: bool,
+    /// Either `always`, `never`, `hideForOtherTeams`, or `hideForOwnTeam`.
+    pub nametag_visibility: String,
+    /// Either `always`, `never`, `pushOtherTeams`, or `pushOwnTeam`.
+    pub collision_rule: String,
+    /// The names of the players (or other score holders) in the team.
+    pub players: HashSet<String>,
+}
+
+/// The places where an objective can be shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DisplaySlot {
+    /// Shown next to player names in the tab list.
+    List,
+    Sidebar,
+    BelowName,
+    /// The sidebar that's only shown to players in a team with this color.
+    TeamSidebar(ChatFormatting),
+}
+
+impl DisplaySlot {
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(DisplaySlot::List),
+            1 => Some(DisplaySlot::Sidebar),
+            2 => Some(DisplaySlot::BelowName),
+            // the team colors are in the same order as the formatters
+            3..=18 => Some(DisplaySlot::TeamSidebar(
+                ChatFormatting::FORMATTERS[id as usize - 3],
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Something about the scoreboard that changed. These are sent as
+/// [`ScoreboardUpdateEvent`]s, and also show up as [`crate::Event`]s.
+#[derive(Clone, Debug)]
+pub enum ScoreboardUpdate {
+    Objective(ObjectiveUpdate),
+    Score(ScoreUpdate),
+    DisplayObjective(DisplayObjectiveUpdate),
+    Team(TeamUpdate),
+}
+
+#[derive(Clone, Debug)]
+pub enum ObjectiveUpdate {
+    Add(Objective),
+    Remove(Objective),
+    Change(Objective),
+}
+
+/// A score was set or removed.
+#[derive(Clone, Debug)]
+pub struct ScoreUpdate {
+    pub owner: String,
+    pub objective: String,
+    /// The new score, or `None` if it was removed.
+    pub score: Option<i32>,
+}
+
+/// The objective that's shown in a display slot changed.
+#[derive(Clone, Debug)]
+pub struct DisplayObjectiveUpdate {
+    pub slot: DisplaySlot,
+    /// The name of the objective that's now shown, or `None` if the slot was
+    /// cleared.
+    pub objective: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub enum TeamUpdate {
+    Add(Team),
+    Remove(Team),
+    Change(Team),
+    Join { team: String, players: Vec<String> },
+    Leave { team: String, players: Vec<String> },
+}
+
+/// Sent when the [`Scoreboard`] of a local player changes.
+#[derive(Clone, Debug)]
+pub struct ScoreboardUpdateEvent {
+    pub entity: Entity,
+    pub update: ScoreboardUpdate,
+}
+
+impl Scoreboard {
+    /// Get the score that the score holder has for an objective.
+    pub fn score(&self, owner: &str, objective: &str) -> Option<i32> {
+        self.scores.get(owner)?.get(objective).copied()
+    }
+
+    /// Get the objective that's shown in a display slot.
+    pub fn objective_in_slot(&self, slot: DisplaySlot) -> Option<&Objective> {
+        self.objectives.get(self.display_slots.get(&slot)?)
+    }
+
+    /// Get the team that a player (or other score holder) is in.
+    pub fn player_team(&self, player: &str) -> Option<&Team> {
+        self.teams.get(self.teams_by_player.get(player)?)
+    }
+
+    /// The score holders and scores that are shown in the sidebar, in the order
+    /// they're shown (the highest score first).
+    ///
+    /// Like in vanilla, this uses the sidebar for our team's color if there is
+    /// one, and at most 15 scores are shown.
+    pub fn sidebar(&self, own_name: &str) -> Vec<(String, i32)> {
+        let team_slot = self
+            .player_team(own_name)
+            .map(|team| DisplaySlot::TeamSidebar(team.color));
+        let Some(objective) = team_slot
+            .and_then(|slot| self.objective_in_slot(slot))
+            .or_else(|| self.objective_in_slot(DisplaySlot::Sidebar))
+        else {
+            return Vec::new();
+        };
+
+        let mut lines = self
+            .scores
+            .iter()
+            // score holders starting with # are hidden
+            .filter(|(owner, _)| !owner.starts_with('#'))
+            .filter_map(|(owner, scores)| Some((owner.clone(), *scores.get(&objective.name)?)))
+            .collect::<Vec<_>>();
+        lines.sort_by(|(a_owner, a_score), (b_owner, b_score)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a_owner.to_lowercase().cmp(&b_owner.to_lowercase()))
+        });
+        lines.truncate(15);
+        lines
+    }
+
+    /// Update the scoreboard from a `SetObjective` packet. Returns `None` if
+    /// it tried to remove an objective that doesn't exist.
+    pub fn apply_set_objective(
+        &mut self,
+        p: &ClientboundSetObjectivePacket,
+    ) -> Option<ObjectiveUpdate> {
+        let objective_name = p.objective_name.clone();
+        Some(match &p.method {
+            clientbound_set_objective_packet::Method::Add(info) => {
+                let objective = Objective {
+                    name: objective_name.clone(),
+                    display_name: info.display_name.clone(),
+                    render_type: info.render_type,
+                };
+                self.objectives.insert(objective_name, objective.clone());
+                ObjectiveUpdate::Add(objective)
+            }
+            clientbound_set_objective_packet::Method::Change(info) => {
+                let objective = Objective {
+                    name: objective_name.clone(),
+                    display_name: info.display_name.clone(),
+                    render_type: info.render_type,
+                };
+                self.objectives.insert(objective_name, objective.clone());
+                ObjectiveUpdate::Change(objective)
+            }
+            clientbound_set_objective_packet::Method::Remove => {
+                let objective = self.objectives.remove(&objective_name)?;
+                // removing an objective also removes its scores and clears the slots
+                // it's shown in
+                for scores in self.scores.values_mut() {
+                    scores.remove(&objective_name);
+                }
+                self.scores.retain(|_, scores| !scores.is_empty());
+                self.display_slots.retain(|_, name| *name != objective_name);
+                ObjectiveUpdate::Remove(objective)
+            }
+        })
+    }
+
+    /// Update the scoreboard from a `SetScore` packet.
+    pub fn apply_set_score(&mut self, p: &ClientboundSetScorePacket) -> Vec<ScoreUpdate> {
+        match p.method {
+            clientbound_set_score_packet::Method::Change { score } => {
+                let Some(objective) = p.objective_name.clone() else {
+                    // vanilla servers always send the objective when changing a score
+                    return Vec::new();
+                };
+                self.scores
+                    .entry(p.owner.clone())
+                    .or_default()
+                    .insert(objective.clone(), score);
+                vec![ScoreUpdate {
+                    owner: p.owner.clone(),
+                    objective,
+                    score: Some(score),
+                }]
+            }
+            clientbound_set_score_packet::Method::Remove => {
+                let Some(scores) = self.scores.get_mut(&p.owner) else {
+                    return Vec::new();
+                };
+                let removed = match &p.objective_name {
+                    Some(objective) => scores
+                        .remove(objective)
+                        .map(|_| vec![objective.clone()])
+                        .unwrap_or_default(),
+                    // no objective means all of the owner's scores are removed
+                    None => scores.drain().map(|(objective, _)| objective).collect(),
+                };
+                if scores.is_empty() {
+                    self.scores.remove(&p.owner);
+                }
+                removed
+                    .into_iter()
+                    .map(|objective| ScoreUpdate {
+                        owner: p.owner.clone(),
+                        objective,
+                        score: None,
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Update the scoreboard from a `SetDisplayObjective` packet. Returns
+    /// `None` if the slot is invalid.
+    pub fn apply_set_display_objective(
+        &mut self,
+        slot: u8,
+        objective_name: &str,
+    ) -> Option<DisplayObjectiveUpdate> {
+        let slot = DisplaySlot::from_id(slot)?;
+        let objective = if objective_name.is_empty() {
+            self.display_slots.remove(&slot);
+            None
+        } else {
+            self.display_slots.insert(slot, objective_name.to_string());
+            Some(objective_name.to_string())
+        };
+        Some(DisplayObjectiveUpdate { slot, objective })
+    }
+
+    /// Update the scoreboard from a `SetPlayerTeam` packet.
+    pub fn apply_set_player_team(
+        &mut self,
+        p: &ClientboundSetPlayerTeamPacket,
+    ) -> Option<TeamUpdate> {
+        let team_name = p.name.clone();
+        Some(match &p.method {
+            clientbound_set_player_team_packet::Method::Add((parameters, players)) => {
+                let mut team = Team {
+                    name: team_name.clone(),
+                    display_name: FormattedText::default(),
+                    prefix: FormattedText::default(),
+                    suffix: FormattedText::default(),
+                    color: ChatFormatting::Reset,
+                    allow_friendly_fire: true,
+                    see_friendly_invisibles: true,
+                    nametag_visibility: "always".to_string(),
+                    collision_rule: "always".to_string(),
+                    players: HashSet::new(),
+                };
+                team.apply_parameters(parameters);
+                self.teams.insert(team_name.clone(), team);
+                self.add_players_to_team(&team_name, players);
+                TeamUpdate::Add(self.teams[&team_name].clone())
+            }
+            clientbound_set_player_team_packet::Method::Remove => {
+                let team = self.teams.remove(&team_name)?;
+                self.teams_by_player.retain(|_, name| *name != team_name);
+                TeamUpdate::Remove(team)
+            }
+            clientbound_set_player_team_packet::Method::Change(parameters) => {
+                let team = self.teams.get_mut(&team_name)?;
+                team.apply_parameters(parameters);
+                TeamUpdate::Change(team.clone())
+            }
+            clientbound_set_player_team_packet::Method::Join(players) => {
+                if !self.teams.contains_key(&team_name) {
+                    return None;
+                }
+                self.add_players_to_team(&team_name, players);
+                TeamUpdate::Join {
+                    team: team_name,
+                    players: players.clone(),
+                }
+            }
+            clientbound_set_player_team_packet::Method::Leave(players) => {
+                let team = self.teams.get_mut(&team_name)?;
+                for player in players {
+                    team.players.remove(player);
+                    if self.teams_by_player.get(player) == Some(&team_name) {
+                        self.teams_by_player.remove(player);
+                    }
+                }
+                TeamUpdate::Leave {
+                    team: team_name,
+                    players: players.clone(),
+                }
+            }
+        })
+    }
+
+    fn add_players_to_team(&mut self, team_name: &str, players: &[String]) {
+        for player in players {
+            // players can only be in one team at a time
+            if let Some(old_team_name) = self
+                .teams_by_player
+                .insert(player.clone(), team_name.to_string())
+            {
+                if let Some(old_team) = self.teams.get_mut(&old_team_name) {
+                    old_team.players.remove(player);
+                }
+            }
+            if let Some(team) = self.teams.get_mut(team_name) {
+                team.players.insert(player.clone());
+            }
+        }
+    }
+}
+
+impl Team {
+    fn apply_parameters(&mut self, parameters: &clientbound_set_player_team_packet::Parameters) {
+        self.display_name = parameters.display_name.clone();
+        self.prefix = parameters.player_prefix.clone();
+        self.suffix = parameters.player_suffix.clone();
+        self.color = parameters.color;
+        self.allow_friendly_fire = parameters.options & 0x01 != 0;
+        self.see_friendly_invisibles = parameters.options & 0x02 != 0;
+        self.nametag_visibility = parameters.nametag_visibility.clone();
+        self.collision_rule = parameters.collision_rule.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azalea_protocol::packets::game::clientbound_set_objective_packet::DisplayInfo;
+
+    fn set_score(scoreboard: &mut Scoreboard, owner: &str, objective: &str, score: i32) {
+        scoreboard.apply_set_score(&ClientboundSetScorePacket {
+            owner: owner.to_string(),
+            method: clientbound_set_score_packet::Method::Change { score },
+            objective_name: Some(objective.to_string()),
+        });
+    }
+
+    #[test]
+    fn test_sidebar() {
+        let mut scoreboard = Scoreboard::default();
+        scoreboard.apply_set_objective(&ClientboundSetObjectivePacket {
+            objective_name: "kills".to_string(),
+            method: clientbound_set_objective_packet::Method::Add(DisplayInfo {
+                display_name: FormattedText::from("Kills"),
+                render_type: RenderType::Integer,
+            }),
+        });
+        scoreboard.apply_set_display_objective(1, "kills");
+        set_score(&mut scoreboard, "bob", "kills", 3);
+        set_score(&mut scoreboard, "alice", "kills", 5);
+        set_score(&mut scoreboard, "Carol", "kills", 3);
+        set_score(&mut scoreboard, "#hidden", "kills", 10);
+
+        assert_eq!(
+            scoreboard.sidebar("bob"),
+            vec![
+                ("alice".to_string(), 5),
+                ("bob".to_string(), 3),
+                ("Carol".to_string(), 3)
+            ]
+        );
+
+        let updates = scoreboard.apply_set_score(&ClientboundSetScorePacket {
+            owner: "alice".to_string(),
+            method: clientbound_set_score_packet::Method::Remove,
+            objective_name: None,
+        });
+        assert_eq!(updates.len(), 1);
+        assert_eq!(scoreboard.score("alice", "kills"), None);
+        assert_eq!(scoreboard.score("bob", "kills"), Some(3));
+
+        scoreboard.apply_set_objective(&ClientboundSetObjectivePacket {
+            objective_name: "kills".to_string(),
+            method: clientbound_set_objective_packet::Method::Remove,
+        });
+        assert!(scoreboard.sidebar("bob").is_empty());
+        assert!(scoreboard.scores.is_empty());
+    }
+
+    #[test]
+    fn test_teams() {
+        let mut scoreboard = Scoreboard::default();
+        let parameters = clientbound_set_player_team_packet::Parameters {
+            display_name: FormattedText::from("Red"),
+            options: 0x02,
+            nametag_visibility: "always".to_string(),
+            collision_rule: "always".to_string(),
+            color: ChatFormatting::Red,
+            player_prefix: FormattedText::from("[RED] "),
+            player_suffix: FormattedText::default(),
+        };
+        scoreboard.apply_set_player_team(&ClientboundSetPlayerTeamPacket {
+            name: "red".to_string(),
+            method: clientbound_set_player_team_packet::Method::Add((
+                parameters.clone(),
+                vec!["bob".to_string()],
+            )),
+        });
+        scoreboard.apply_set_player_team(&ClientboundSetPlayerTeamPacket {
+            name: "blue".to_string(),
+            method: clientbound_set_player_team_packet::Method::Add((
+                clientbound_set_player_team_packet::Parameters {
+                    color: ChatFormatting::Blue,
+                    ..parameters
+                },
+                vec!["alice".to_string()],
+            )),
+        });
+
+        let red = scoreboard.player_team("bob").unwrap();
+        assert_eq!(red.name, "red");
+        assert_eq!(red.prefix.to_string(), "[RED] ");
+        assert!(!red.allow_friendly_fire);
+        assert!(red.see_friendly_invisibles);
+
+        // joining another team leaves the old one
+        scoreboard.apply_set_player_team(&ClientboundSetPlayerTeamPacket {
+            name: "blue".to_string(),
+            method: clientbound_set_player_team_packet::Method::Join(vec!["bob".to_string()]),
+        });
+        assert_eq!(scoreboard.player_team("bob").unwrap().name, "blue");
+        assert!(scoreboard.teams["red"].players.is_empty());
+
+        scoreboard.apply_set_player_team(&ClientboundSetPlayerTeamPacket {
+            name: "blue".to_string(),
+            method: clientbound_set_player_team_packet::Method::Remove,
+        });
+        assert!(scoreboard.player_team("bob").is_none());
+        assert!(scoreboard.player_team("alice").is_none());
+    }
+}
@@ -0,0 +1,143 @@
+//! Keep track of the weather, and advance the time of day in every instance
+//! between `SetTime` packets.
+
+use std::sync::Weak;
+
+use azalea_protocol::packets::game::clientbound_game_event_packet::EventType;
+use azalea_world::InstanceContainer;
+use bevy_app::{App, CoreSchedule, IntoSystemAppConfig, Plugin};
+use bevy_ecs::prelude::*;
+
+/// A plugin that advances the time of day every tick and adds
+/// [`WeatherChangeEvent`].
+pub struct WeatherPlugin;
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<WeatherChangeEvent>()
+            .add_system(tick_world_time.in_schedule(CoreSchedule::FixedUpdate));
+    }
+}
+
+/// A component with the weather in the world that the client is in.
+///
+/// The server changes the rain and thunder levels gradually, so it takes a
+/// few seconds for [`Self::is_raining`] to be true after it starts raining.
+#[derive(Component, Clone, Debug, Default)]
+pub struct Weather {
+    /// Whether the server said it started raining. Use [`Self::is_raining`]
+    /// to check whether it's actually raining hard enough to matter.
+    pub raining: bool,
+    /// How hard it's raining, from 0 to 1.
+    pub rain_level: f32,
+    /// How close it is to being a thunderstorm, from 0 to 1. This only
+    /// matters while it's raining.
+    pub thunder_level: f32,
+}
+
+/// It started or stopped raining or thundering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeatherChange {
+    StartRaining,
+    StopRaining,
+    StartThundering,
+    StopThundering,
+}
+
+impl Weather {
+    /// Whether it's raining. Vanilla uses this for things like whether fish
+    /// bite faster and whether crops get watered.
+    pub fn is_raining(&self) -> bool {
+        self.rain_level > 0.2
+    }
+
+    /// Whether there's a thunderstorm, which means mobs can spawn and players
+    /// can sleep even during the day.
+    pub fn is_thundering(&self) -> bool {
+        self.rain_level * self.thunder_level > 0.9
+    }
+
+    /// Update the weather from a `GameEvent` packet, and return what changed.
+    /// Events that aren't about the weather are ignored.
+    pub fn apply_game_event(&mut self, event: EventType, param: f32) -> Vec<WeatherChange> {
+        let was_raining = self.is_raining();
+        let was_thundering = self.is_thundering();
+
+        match event {
+            EventType::StartRaining => {
+                self.raining = true;
+                self.rain_level = 0.;
+            }
+            EventType::StopRaining => {
+                self.raining = false;
+                self.rain_level = 1.;
+            }
+            EventType::RainLevelChange => self.rain_level = param.clamp(0., 1.),
+            EventType::ThunderLevelChange => self.thunder_level = param.clamp(0., 1.),
+            _ => {}
+        }
+
+        let mut changes = Vec::new();
+        match (was_raining, self.is_raining()) {
+            (false, true) => changes.push(WeatherChange::StartRaining),
+            (true, false) => changes.push(WeatherChange::StopRaining),
+            _ => {}
+        }
+        match (was_thundering, self.is_thundering()) {
+            (false, true) => changes.push(WeatherChange::StartThundering),
+            (true, false) => changes.push(WeatherChange::StopThundering),
+            _ => {}
+        }
+        changes
+    }
+}
+
+/// Sent when it starts or stops raining or thundering for a client.
+#[derive(Clone, Debug)]
+pub struct WeatherChangeEvent {
+    pub entity: Entity,
+    pub change: WeatherChange,
+}
+
+/// Advance the time in every instance by one tick. This is done here instead
+/// of per client so instances that are shared by several clients don't go
+/// faster.
+fn tick_world_time(instance_container: Res<InstanceContainer>) {
+    for instance in instance_container.worlds.values().filter_map(Weak::upgrade) {
+        instance.write().time.tick();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weather_changes() {
+        let mut weather = Weather::default();
+        assert!(weather
+            .apply_game_event(EventType::StartRaining, 0.)
+            .is_empty());
+        assert!(weather.raining);
+        assert!(!weather.is_raining());
+        assert_eq!(
+            weather.apply_game_event(EventType::RainLevelChange, 0.5),
+            vec![WeatherChange::StartRaining]
+        );
+
+        weather.apply_game_event(EventType::ThunderLevelChange, 1.);
+        assert!(!weather.is_thundering());
+        assert_eq!(
+            weather.apply_game_event(EventType::RainLevelChange, 1.),
+            vec![WeatherChange::StartThundering]
+        );
+
+        assert_eq!(
+            weather.apply_game_event(EventType::RainLevelChange, 0.),
+            vec![WeatherChange::StopRaining, WeatherChange::StopThundering]
+        );
+        // other events don't change the weather
+        assert!(weather
+            .apply_game_event(EventType::ChangeGameMode, 1.)
+            .is_empty());
+    }
+}
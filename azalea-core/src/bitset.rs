@@ -18,8 +18,12 @@ impl BitSet {
         }
     }
 
+    /// Whether the bit at the index is set. Like in Java, bits past the end of
+    /// the set are `false`.
     pub fn index(&self, index: usize) -> bool {
-        (self.data[index / 64] & (1u64 << (index % 64))) != 0
+        self.data
+            .get(index / 64)
+            .map_or(false, |word| (word & (1u64 << (index % 64))) != 0)
     }
 
     fn check_range(&self, from_index: usize, to_index: usize) {
@@ -77,10 +81,16 @@ impl BitSet {
 
     /// Returns the maximum potential items in the BitSet. This will be
     /// divisible by 64.
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.data.len() * 64
     }
 
+    /// Whether the BitSet can't hold any items. Note that this isn't the same
+    /// as all the bits being `false`.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
     /// Returns the index of the first bit that is set to `false`
     /// that occurs on or after the specified starting index.
     pub fn next_clear_bit(&self, from_index: usize) -> usize {
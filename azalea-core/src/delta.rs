@@ -75,3 +75,42 @@ impl Vec3 {
         self.multiply(amount, amount, amount)
     }
 }
+
+/// Convert a rotation that the server sent as a byte (where 256 is a full
+/// turn) to degrees.
+pub fn rotation_from_byte(rot: i8) -> f32 {
+    rot as f32 * 360. / 256.
+}
+
+/// Convert a velocity that the server sent as shorts (in 1/8000 of a block
+/// per tick) to blocks per tick.
+pub fn velocity_from_shorts(x: i16, y: i16, z: i16) -> Vec3 {
+    Vec3 {
+        x: x as f64 / 8000.,
+        y: y as f64 / 8000.,
+        z: z as f64 / 8000.,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_from_byte() {
+        assert_eq!(rotation_from_byte(64), 90.);
+        assert_eq!(rotation_from_byte(-128), -180.);
+    }
+
+    #[test]
+    fn test_velocity_from_shorts() {
+        assert_eq!(
+            velocity_from_shorts(8000, -4000, 0),
+            Vec3 {
+                x: 1.,
+                y: -0.5,
+                z: 0.
+            }
+        );
+    }
+}
@@ -219,7 +219,7 @@ impl ChunkSectionPos {
     }
 }
 /// The coordinates of a block inside a chunk.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct ChunkBlockPos {
     pub x: u8,
     pub y: i32,
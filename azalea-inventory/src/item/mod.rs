@@ -21,3 +21,197 @@ impl MaxStackSizeExt for azalea_registry::Item {
         64
     }
 }
+
+/// The kinds of tools that make mining certain blocks faster.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ToolKind {
+    Pickaxe,
+    Axe,
+    Shovel,
+    Hoe,
+    Sword,
+}
+
+/// The material that a tool is made out of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ToolTier {
+    Wood,
+    Stone,
+    Iron,
+    Diamond,
+    Gold,
+    Netherite,
+}
+
+impl ToolTier {
+    /// The mining level of this tier. Some blocks can only be harvested with
+    /// a tool that has at least a certain level.
+    pub fn level(&self) -> u8 {
+        match self {
+            ToolTier::Wood | ToolTier::Gold => 0,
+            ToolTier::Stone => 1,
+            ToolTier::Iron => 2,
+            ToolTier::Diamond => 3,
+            ToolTier::Netherite => 4,
+        }
+    }
+
+    /// How much faster than a hand a tool of this tier mines blocks that it's
+    /// meant for.
+    pub fn speed(&self) -> f32 {
+        match self {
+            ToolTier::Wood => 2.,
+            ToolTier::Stone => 4.,
+            ToolTier::Iron => 6.,
+            ToolTier::Diamond => 8.,
+            ToolTier::Netherite => 9.,
+            ToolTier::Gold => 12.,
+        }
+    }
+
+    /// How many times a tool of this tier can be used before it breaks.
+    pub fn durability(&self) -> u32 {
+        match self {
+            ToolTier::Wood => 59,
+            ToolTier::Stone => 131,
+            ToolTier::Iron => 250,
+            ToolTier::Diamond => 1561,
+            ToolTier::Gold => 32,
+            ToolTier::Netherite => 2031,
+        }
+    }
+
+    /// How much extra damage a tool of this tier does when attacking with it.
+    pub fn attack_damage_bonus(&self) -> f32 {
+        match self {
+            ToolTier::Wood | ToolTier::Gold => 0.,
+            ToolTier::Stone => 1.,
+            ToolTier::Iron => 2.,
+            ToolTier::Diamond => 3.,
+            ToolTier::Netherite => 4.,
+        }
+    }
+}
+
+pub trait ToolExt {
+    /// Get the kind and tier of this tool, or `None` if it's not a tool.
+    ///
+    /// Shears aren't included here since they don't have a tier.
+    fn tool(&self) -> Option<(ToolKind, ToolTier)>;
+
+    /// How many times the item can be used before it breaks, or 0 if it
+    /// doesn't break.
+    ///
+    /// Armor isn't included here yet.
+    fn max_damage(&self) -> u32;
+
+    /// How much damage the item adds to the player's base attack damage of 1,
+    /// without taking enchantments into account.
+    fn attack_damage(&self) -> f32;
+
+    /// How many times per second the player can attack at full strength while
+    /// holding the item. This is 4 for items that aren't weapons or tools.
+    fn attack_speed(&self) -> f32;
+}
+
+impl ToolExt for azalea_registry::Item {
+    fn tool(&self) -> Option<(ToolKind, ToolTier)> {
+        use azalea_registry::Item;
+
+        let (kind, tier) = match self {
+            Item::WoodenPickaxe => (ToolKind::Pickaxe, ToolTier::Wood),
+            Item::StonePickaxe => (ToolKind::Pickaxe, ToolTier::Stone),
+            Item::IronPickaxe => (ToolKind::Pickaxe, ToolTier::Iron),
+            Item::DiamondPickaxe => (ToolKind::Pickaxe, ToolTier::Diamond),
+            Item::GoldenPickaxe => (ToolKind::Pickaxe, ToolTier::Gold),
+            Item::NetheritePickaxe => (ToolKind::Pickaxe, ToolTier::Netherite),
+
+            Item::WoodenAxe => (ToolKind::Axe, ToolTier::Wood),
+            Item::StoneAxe => (ToolKind::Axe, ToolTier::Stone),
+            Item::IronAxe => (ToolKind::Axe, ToolTier::Iron),
+            Item::DiamondAxe => (ToolKind::Axe, ToolTier::Diamond),
+            Item::GoldenAxe => (ToolKind::Axe, ToolTier::Gold),
+            Item::NetheriteAxe => (ToolKind::Axe, ToolTier::Netherite),
+
+            Item::WoodenShovel => (ToolKind::Shovel, ToolTier::Wood),
+            Item::StoneShovel => (ToolKind::Shovel, ToolTier::Stone),
+            Item::IronShovel => (ToolKind::Shovel, ToolTier::Iron),
+            Item::DiamondShovel => (ToolKind::Shovel, ToolTier::Diamond),
+            Item::GoldenShovel => (ToolKind::Shovel, ToolTier::Gold),
+            Item::NetheriteShovel => (ToolKind::Shovel, ToolTier::Netherite),
+
+            Item::WoodenHoe => (ToolKind::Hoe, ToolTier::Wood),
+            Item::StoneHoe => (ToolKind::Hoe, ToolTier::Stone),
+            Item::IronHoe => (ToolKind::Hoe, ToolTier::Iron),
+            Item::DiamondHoe => (ToolKind::Hoe, ToolTier::Diamond),
+            Item::GoldenHoe => (ToolKind::Hoe, ToolTier::Gold),
+            Item::NetheriteHoe => (ToolKind::Hoe, ToolTier::Netherite),
+
+            Item::WoodenSword => (ToolKind::Sword, ToolTier::Wood),
+            Item::StoneSword => (ToolKind::Sword, ToolTier::Stone),
+            Item::IronSword => (ToolKind::Sword, ToolTier::Iron),
+            Item::DiamondSword => (ToolKind::Sword, ToolTier::Diamond),
+            Item::GoldenSword => (ToolKind::Sword, ToolTier::Gold),
+            Item::NetheriteSword => (ToolKind::Sword, ToolTier::Netherite),
+
+            _ => return None,
+        };
+        Some((kind, tier))
+    }
+
+    fn max_damage(&self) -> u32 {
+        use azalea_registry::Item;
+
+        match self {
+            Item::Shears => 238,
+            Item::Trident => 250,
+            Item::Bow => 384,
+            Item::Crossbow => 465,
+            Item::FishingRod => 64,
+            Item::FlintAndSteel => 64,
+            _ => self.tool().map_or(0, |(_, tier)| tier.durability()),
+        }
+    }
+
+    fn attack_damage(&self) -> f32 {
+        if *self == azalea_registry::Item::Trident {
+            return 8.;
+        }
+        let Some((kind, tier)) = self.tool() else {
+            return 0.;
+        };
+        let base_damage = match (kind, tier) {
+            (ToolKind::Sword, _) => 3.,
+            (ToolKind::Pickaxe, _) => 1.,
+            (ToolKind::Shovel, _) => 1.5,
+            (ToolKind::Axe, ToolTier::Wood | ToolTier::Iron | ToolTier::Gold) => 6.,
+            (ToolKind::Axe, ToolTier::Stone) => 7.,
+            (ToolKind::Axe, ToolTier::Diamond | ToolTier::Netherite) => 5.,
+            // hoes cancel out the tier's bonus
+            (ToolKind::Hoe, _) => -tier.attack_damage_bonus(),
+        };
+        base_damage + tier.attack_damage_bonus()
+    }
+
+    fn attack_speed(&self) -> f32 {
+        if *self == azalea_registry::Item::Trident {
+            return 1.1;
+        }
+        let Some((kind, tier)) = self.tool() else {
+            return 4.;
+        };
+        let modifier = match (kind, tier) {
+            (ToolKind::Sword, _) => -2.4,
+            (ToolKind::Pickaxe, _) => -2.8,
+            (ToolKind::Shovel, _) => -3.,
+            (ToolKind::Axe, ToolTier::Wood | ToolTier::Stone) => -3.2,
+            (ToolKind::Axe, ToolTier::Iron) => -3.1,
+            (ToolKind::Axe, _) => -3.,
+            (ToolKind::Hoe, ToolTier::Wood | ToolTier::Gold) => -3.,
+            (ToolKind::Hoe, ToolTier::Stone) => -2.,
+            (ToolKind::Hoe, ToolTier::Iron) => -1.,
+            (ToolKind::Hoe, ToolTier::Diamond | ToolTier::Netherite) => 0.,
+        };
+        4. + modifier
+    }
+}
@@ -46,6 +46,37 @@ impl Menu {
             unreachable!("Called `Menu::as_player` on a menu that wasn't `Player`.")
         }
     }
+
+    /// The width (and height) of the crafting grid in this menu. This is 2 for
+    /// the player's inventory, 3 for crafting tables, and `None` for menus
+    /// that can't craft.
+    pub fn crafting_grid_size(&self) -> Option<usize> {
+        match self {
+            Menu::Player(_) => Some(2),
+            Menu::Crafting { .. } => Some(3),
+            _ => None,
+        }
+    }
+
+    /// The protocol index of the slot where the result of a crafting recipe
+    /// goes, or `None` if the menu can't craft.
+    pub fn crafting_result_slot(&self) -> Option<usize> {
+        match self {
+            Menu::Player(_) => Some(Player::CRAFT_RESULT_SLOT),
+            Menu::Crafting { .. } => Some(Menu::CRAFTING_RESULT_SLOT),
+            _ => None,
+        }
+    }
+
+    /// The protocol indexes of the crafting grid, going left to right and then
+    /// top to bottom. Returns `None` if the menu can't craft.
+    pub fn crafting_grid_slots(&self) -> Option<RangeInclusive<usize>> {
+        match self {
+            Menu::Player(_) => Some(Player::CRAFT_SLOTS),
+            Menu::Crafting { .. } => Some(Menu::CRAFTING_GRID_SLOTS),
+            _ => None,
+        }
+    }
 }
 
 // the player inventory part is always the last 36 slots (except in the Player
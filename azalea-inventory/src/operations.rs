@@ -61,6 +61,8 @@ impl From<QuickMoveClick> for ClickOperation {
 #[derive(Debug, Clone)]
 pub struct SwapClick {
     pub source_slot: u16,
+    /// The index of the hotbar slot (0-8) to swap with, or 40 for the
+    /// offhand. Note that this isn't a protocol index in the menu.
     pub target_slot: u8,
 }
 
@@ -1,7 +1,9 @@
 use azalea_buf::{BufReadError, McBuf, McBufReadable, McBufWritable};
-use azalea_nbt::Nbt;
+use azalea_nbt::{Nbt, NbtList};
 use std::io::{Cursor, Write};
 
+use crate::item::ToolExt;
+
 /// Either an item in an inventory or nothing.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub enum ItemSlot {
@@ -11,6 +13,15 @@ pub enum ItemSlot {
 }
 
 impl ItemSlot {
+    /// A slot with `count` of the item and no NBT.
+    pub fn new(kind: azalea_registry::Item, count: i8) -> Self {
+        ItemSlot::Present(ItemSlotData {
+            kind,
+            count,
+            nbt: Nbt::default(),
+        })
+    }
+
     /// Check if the slot is ItemSlot::Empty, if the count is <= 0, or if the
     /// item is air.
     ///
@@ -123,6 +134,51 @@ impl ItemSlotData {
     pub fn is_same_item_and_nbt(&self, other: &ItemSlotData) -> bool {
         self.kind == other.kind && self.nbt == other.nbt
     }
+
+    /// The level of the given enchantment on this item, or 0 if it doesn't
+    /// have it.
+    pub fn enchantment_level(&self, enchantment: azalea_registry::Enchantment) -> u32 {
+        let Some(tag) = self.nbt.as_compound().and_then(|root| root.get("")) else {
+            return 0;
+        };
+        let Some(Nbt::List(NbtList::Compound(enchantments))) =
+            tag.as_compound().and_then(|tag| tag.get("Enchantments"))
+        else {
+            return 0;
+        };
+        let id = enchantment.to_string();
+        enchantments
+            .iter()
+            .find(|e| matches!(e.get("id"), Some(Nbt::String(e_id)) if *e_id == id))
+            .and_then(|e| match e.get("lvl") {
+                Some(Nbt::Short(lvl)) => Some(*lvl as u32),
+                Some(Nbt::Int(lvl)) => Some(*lvl as u32),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+
+    /// How many times the item has been used, from the `Damage` tag. This is
+    /// 0 for items that don't break.
+    pub fn damage(&self) -> u32 {
+        let Some(tag) = self.nbt.as_compound().and_then(|root| root.get("")) else {
+            return 0;
+        };
+        match tag.as_compound().and_then(|tag| tag.get("Damage")) {
+            Some(Nbt::Int(damage)) => (*damage).max(0) as u32,
+            _ => 0,
+        }
+    }
+
+    /// How many more times the item can be used before it breaks, or `None`
+    /// if it doesn't break.
+    pub fn remaining_durability(&self) -> Option<u32> {
+        let max_damage = self.kind.max_damage();
+        if max_damage == 0 {
+            return None;
+        }
+        Some(max_damage.saturating_sub(self.damage()))
+    }
 }
 
 impl McBufReadable for ItemSlot {
@@ -5,7 +5,11 @@ mod shape;
 mod world_collisions;
 
 use azalea_core::{Axis, Vec3, AABB, EPSILON};
-use azalea_world::{entity, Instance, MoveEntityError};
+use azalea_world::{
+    entity,
+    world_border::{self, BorderBounds},
+    Instance, MoveEntityError,
+};
 pub use blocks::BlockWithShape;
 pub use discrete_voxel_shape::*;
 pub use shape::*;
@@ -66,6 +70,11 @@ fn collide(movement: &Vec3, world: &Instance, physics: &entity::Physics) -> Vec3
     // collided_movement
 }
 
+/// Whether there are no blocks that would collide with the given bounding box.
+pub fn no_collision(world: &Instance, aabb: AABB) -> bool {
+    get_block_collisions(world, aabb).next().is_none()
+}
+
 /// Move an entity by a given delta, checking for collisions.
 pub fn move_colliding(
     _mover_type: &MoverType,
@@ -119,6 +128,7 @@ pub fn move_colliding(
     let vertical_collision = movement.y != collide_result.y;
     let on_ground = vertical_collision && movement.y < 0.;
     physics.on_ground = on_ground;
+    physics.horizontal_collision = horizontal_collision;
 
     // TODO: minecraft checks for a "minor" horizontal collision here
 
@@ -192,7 +202,10 @@ fn collide_bounding_box(
         collision_boxes.extend(entity_collisions);
     }
 
-    // TODO: world border
+    let border_bounds = world.world_border.bounds();
+    if is_inside_close_to_border(&border_bounds, entity_bounding_box) {
+        collision_boxes.extend(world_border_collision_shapes(&border_bounds));
+    }
 
     let block_collisions =
         get_block_collisions(world, entity_bounding_box.expand_towards(movement));
@@ -201,6 +214,35 @@ fn collide_bounding_box(
     collide_with_shapes(movement, *entity_bounding_box, &collision_boxes)
 }
 
+/// Whether the bounding box is inside the world border and close enough to it
+/// that it could collide with it.
+fn is_inside_close_to_border(bounds: &BorderBounds, bounding_box: &AABB) -> bool {
+    let size = bounding_box
+        .get_size(Axis::X)
+        .abs()
+        .max(bounding_box.get_size(Axis::Z).abs())
+        .max(1.);
+    let center = bounding_box.get_center();
+    bounds.distance_to(center.x, center.z) < size * 2. && bounds.contains(center.x, center.z, size)
+}
+
+/// The shapes of the walls around the outside of the world border.
+fn world_border_collision_shapes(bounds: &BorderBounds) -> [VoxelShape; 4] {
+    let min_x = bounds.min_x.floor();
+    let max_x = bounds.max_x.ceil();
+    let min_z = bounds.min_z.floor();
+    let max_z = bounds.max_z.ceil();
+    // the walls don't have to be infinite, just big enough that nothing can get
+    // past them
+    let far = world_border::MAX_SIZE;
+    [
+        box_shape_unchecked(min_x - far, -far, min_z - far, min_x, far, max_z + far),
+        box_shape_unchecked(max_x, -far, min_z - far, max_x + far, far, max_z + far),
+        box_shape_unchecked(min_x, -far, min_z - far, max_x, far, min_z),
+        box_shape_unchecked(min_x, -far, max_z, max_x, far, max_z + far),
+    ]
+}
+
 fn collide_with_shapes(
     movement: &Vec3,
     mut entity_box: AABB,
@@ -5,7 +5,7 @@ pub mod clip;
 pub mod collision;
 
 use azalea_block::{Block, BlockState};
-use azalea_core::{BlockPos, Vec3};
+use azalea_core::{BlockPos, Vec3, AABB};
 use azalea_world::{
     entity::{
         clamp_look_direction, metadata::Sprinting, move_relative, Attributes, Jumping, Local,
@@ -21,7 +21,7 @@ use bevy_ecs::{
     schedule::{IntoSystemConfig, IntoSystemConfigs, SystemSet},
     system::{Query, Res},
 };
-use collision::{move_colliding, MoverType};
+use collision::{move_colliding, no_collision, MoverType};
 
 /// A Bevy [`SystemSet`] for running physics that makes entities do things.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
@@ -47,6 +47,7 @@ impl Plugin for PhysicsPlugin {
 
 /// Move the entity with the given acceleration while handling friction,
 /// gravity, collisions, and some other stuff.
+#[allow(clippy::type_complexity)]
 fn travel(
     mut query: Query<
         (
@@ -55,12 +56,16 @@ fn travel(
             &mut Position,
             &Attributes,
             &WorldName,
+            Option<&Sprinting>,
+            Option<&Jumping>,
         ),
         With<Local>,
     >,
     instance_container: Res<InstanceContainer>,
 ) {
-    for (mut physics, direction, mut position, attributes, world_name) in &mut query {
+    for (mut physics, direction, mut position, attributes, world_name, sprinting, jumping) in
+        &mut query
+    {
         let world_lock = instance_container
             .get(world_name)
             .expect("All entities should be in a valid world");
@@ -75,7 +80,22 @@ fn travel(
         // TODO: slow falling effect
         // let is_falling = self.delta.y <= 0.;
 
-        // TODO: fluids
+        physics.in_water = is_in_water(&world, &physics.bounding_box);
+        let jumping = jumping.map(|j| **j).unwrap_or(false);
+
+        if physics.in_water {
+            travel_in_water(
+                &world,
+                &mut physics,
+                &direction,
+                &mut position,
+                sprinting.map(|s| **s).unwrap_or(false),
+                gravity,
+            );
+            continue;
+        }
+
+        // TODO: lava
 
         // TODO: elytra
 
@@ -102,6 +122,7 @@ fn travel(
             &direction,
             &mut position,
             attributes,
+            jumping,
         );
 
         movement.y -= gravity;
@@ -153,9 +174,12 @@ pub fn ai_step(
 
         if let Some(jumping) = jumping {
             if **jumping {
-                // TODO: jumping in liquids and jump delay
+                // TODO: jump delay
 
-                if physics.on_ground {
+                if physics.in_water {
+                    // swim up
+                    physics.delta.y += 0.04;
+                } else if physics.on_ground {
                     force_jump_events.send(ForceJumpEvent(entity));
                 }
             }
@@ -230,6 +254,7 @@ fn handle_relative_friction_and_calculate_movement(
     direction: &LookDirection,
     position: &mut Position,
     attributes: &Attributes,
+    jumping: bool,
 ) -> Vec3 {
     move_relative(
         physics,
@@ -241,7 +266,10 @@ fn handle_relative_friction_and_calculate_movement(
             z: physics.zza as f64,
         },
     );
-    // entity.delta = entity.handle_on_climbable(entity.delta);
+    let on_climbable = on_climbable(world, position);
+    if on_climbable {
+        handle_on_climbable(physics);
+    }
     move_colliding(
         &MoverType::Own,
         &physics.delta.clone(),
@@ -250,17 +278,160 @@ fn handle_relative_friction_and_calculate_movement(
         physics,
     )
     .expect("Entity should exist.");
-    // let delta_movement = entity.delta;
-    // ladders
-    //   if ((entity.horizontalCollision || entity.jumping) && (entity.onClimbable()
-    // || entity.getFeetBlockState().is(Blocks.POWDER_SNOW) &&
-    // PowderSnowBlock.canEntityWalkOnPowderSnow(entity))) {      var3 = new
-    // Vec3(var3.x, 0.2D, var3.z);   }
+    // climb up ladders when we walk into them or jump
+    if on_climbable && (physics.horizontal_collision || jumping) {
+        physics.delta.y = 0.2;
+    }
     // TODO: powdered snow
 
     physics.delta
 }
 
+/// Slow down the entity so it doesn't slide off or fall too fast while it's
+/// on a ladder.
+fn handle_on_climbable(physics: &mut Physics) {
+    // TODO: sneaking on ladders makes you stop falling
+    let max = 0.15;
+    physics.delta.x = physics.delta.x.clamp(-max, max);
+    physics.delta.z = physics.delta.z.clamp(-max, max);
+    physics.delta.y = physics.delta.y.max(-max);
+}
+
+/// Whether the block at the entity's feet is something it can climb, like a
+/// ladder or vines.
+pub fn on_climbable(world: &Instance, position: &Position) -> bool {
+    let block_pos = BlockPos::from(**position);
+    let block_state = world
+        .chunks
+        .get_block_state(&block_pos)
+        .unwrap_or(BlockState::AIR);
+    is_climbable(block_state)
+}
+
+/// Whether entities can climb up the block.
+///
+/// Vanilla uses the `minecraft:climbable` block tag for this.
+pub fn is_climbable(block_state: BlockState) -> bool {
+    let block = Box::<dyn Block>::from(block_state);
+    matches!(
+        block.id(),
+        "ladder"
+            | "vine"
+            | "scaffolding"
+            | "weeping_vines"
+            | "weeping_vines_plant"
+            | "twisting_vines"
+            | "twisting_vines_plant"
+            | "cave_vines"
+            | "cave_vines_plant"
+    )
+}
+
+/// Whether the block is water that entities can swim in.
+pub fn is_water(block_state: BlockState) -> bool {
+    // TODO: waterlogged blocks
+    let block = Box::<dyn Block>::from(block_state);
+    matches!(block.id(), "water" | "bubble_column")
+}
+
+/// Whether any of the blocks the bounding box is in are water.
+pub fn is_in_water(world: &Instance, bounding_box: &AABB) -> bool {
+    // vanilla also checks the height of the fluid, but we treat water as a full
+    // block
+    let min = BlockPos::new(
+        (bounding_box.min_x + 0.001).floor() as i32,
+        (bounding_box.min_y + 0.001).floor() as i32,
+        (bounding_box.min_z + 0.001).floor() as i32,
+    );
+    let max = BlockPos::new(
+        (bounding_box.max_x - 0.001).floor() as i32,
+        (bounding_box.max_y - 0.001).floor() as i32,
+        (bounding_box.max_z - 0.001).floor() as i32,
+    );
+    for x in min.x..=max.x {
+        for y in min.y..=max.y {
+            for z in min.z..=max.z {
+                let block_state = world
+                    .chunks
+                    .get_block_state(&BlockPos::new(x, y, z))
+                    .unwrap_or(BlockState::AIR);
+                if is_water(block_state) {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Move the entity while it's swimming, which is slower and has much less
+/// gravity than moving on land.
+fn travel_in_water(
+    world: &Instance,
+    physics: &mut Physics,
+    direction: &LookDirection,
+    position: &mut Position,
+    sprinting: bool,
+    gravity: f64,
+) {
+    let start_y = position.y;
+    let slowdown = if sprinting { 0.9 } else { 0.8 };
+    // TODO: depth strider and dolphin's grace
+
+    move_relative(
+        physics,
+        direction,
+        0.02,
+        &Vec3 {
+            x: physics.xxa as f64,
+            y: physics.yya as f64,
+            z: physics.zza as f64,
+        },
+    );
+    move_colliding(
+        &MoverType::Own,
+        &physics.delta.clone(),
+        world,
+        position,
+        physics,
+    )
+    .expect("Entity should exist.");
+
+    let mut movement = physics.delta;
+    if physics.horizontal_collision && on_climbable(world, position) {
+        movement.y = 0.2;
+    }
+    movement.x *= slowdown;
+    movement.y *= 0.8;
+    movement.z *= slowdown;
+
+    if !sprinting {
+        let falling = movement.y <= 0.;
+        movement.y = if falling
+            && (movement.y - 0.005).abs() >= 0.003
+            && (movement.y - gravity / 16.).abs() < 0.003
+        {
+            -0.003
+        } else {
+            movement.y - gravity / 16.
+        };
+    }
+
+    // jump out of the water if we swim into the side of a block
+    if physics.horizontal_collision {
+        let moved_box = physics.bounding_box.move_relative(&Vec3 {
+            x: movement.x,
+            y: movement.y + 0.6 - position.y + start_y,
+            z: movement.z,
+        });
+        if no_collision(world, moved_box) && !is_in_water(world, &moved_box) {
+            movement.y = 0.3;
+        }
+    }
+
+    physics.delta = movement;
+}
+
 // private float getFrictionInfluencedSpeed(float friction) {
 //     return this.onGround ? this.getSpeed() * (0.21600002F / (friction *
 // friction * friction)) : this.flyingSpeed; }
@@ -684,4 +855,155 @@ mod tests {
         let entity_pos = app.world.get::<Position>(entity).unwrap();
         assert_eq!(entity_pos.y, 70.5);
     }
+
+    #[test]
+    fn test_climb_ladder() {
+        let mut app = make_test_app();
+        let world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+        let mut partial_world = PartialInstance::default();
+
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut world_lock.write().chunks,
+        );
+        let entity = app
+            .world
+            .spawn((
+                EntityBundle::new(
+                    Uuid::nil(),
+                    Vec3 {
+                        x: 0.5,
+                        y: 70.,
+                        z: 0.5,
+                    },
+                    azalea_registry::EntityKind::Player,
+                    ResourceLocation::new("minecraft:overworld"),
+                ),
+                MinecraftEntityId(0),
+                Local,
+            ))
+            .id();
+        world_lock.write().chunks.set_block_state(
+            &BlockPos { x: 0, y: 69, z: 0 },
+            azalea_registry::Block::Stone.into(),
+        );
+        for y in 70..76 {
+            world_lock.write().chunks.set_block_state(
+                &BlockPos { x: 0, y, z: 0 },
+                azalea_registry::Block::Ladder.into(),
+            );
+        }
+        **app.world.get_mut::<Jumping>(entity).unwrap() = true;
+
+        for _ in 0..20 {
+            app.world.run_schedule(CoreSchedule::FixedUpdate);
+            app.update();
+        }
+
+        let entity_pos = app.world.get::<Position>(entity).unwrap();
+        // a normal jump wouldn't get us this high
+        assert!(entity_pos.y > 72., "{}", entity_pos.y);
+        assert!(entity_pos.y < 76., "{}", entity_pos.y);
+    }
+
+    #[test]
+    fn test_sink_in_water() {
+        let mut app = make_test_app();
+        let world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+        let mut partial_world = PartialInstance::default();
+
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut world_lock.write().chunks,
+        );
+        let entity = app
+            .world
+            .spawn((
+                EntityBundle::new(
+                    Uuid::nil(),
+                    Vec3 {
+                        x: 0.5,
+                        y: 70.,
+                        z: 0.5,
+                    },
+                    azalea_registry::EntityKind::Player,
+                    ResourceLocation::new("minecraft:overworld"),
+                ),
+                MinecraftEntityId(0),
+                Local,
+            ))
+            .id();
+        for y in 60..72 {
+            world_lock.write().chunks.set_block_state(
+                &BlockPos { x: 0, y, z: 0 },
+                azalea_registry::Block::Water.into(),
+            );
+        }
+
+        for _ in 0..20 {
+            app.world.run_schedule(CoreSchedule::FixedUpdate);
+            app.update();
+        }
+
+        let entity_pos = app.world.get::<Position>(entity).unwrap();
+        // we'd have fallen about 5 blocks if this was air
+        assert!(entity_pos.y < 70., "{}", entity_pos.y);
+        assert!(entity_pos.y > 69., "{}", entity_pos.y);
+    }
+
+    #[test]
+    fn test_world_border_collision() {
+        let mut app = make_test_app();
+        let world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+        let mut partial_world = PartialInstance::default();
+
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut world_lock.write().chunks,
+        );
+        // the border goes from -5 to 5
+        world_lock.write().world_border.set_size(10.);
+        let entity = app
+            .world
+            .spawn((
+                EntityBundle::new(
+                    Uuid::nil(),
+                    Vec3 {
+                        x: 4.,
+                        y: 70.,
+                        z: 0.5,
+                    },
+                    azalea_registry::EntityKind::Player,
+                    ResourceLocation::new("minecraft:overworld"),
+                ),
+                MinecraftEntityId(0),
+                Local,
+            ))
+            .id();
+        app.world.get_mut::<Physics>(entity).unwrap().delta.x = 2.;
+
+        for _ in 0..2 {
+            app.world.run_schedule(CoreSchedule::FixedUpdate);
+            app.update();
+        }
+
+        let entity_pos = app.world.get::<Position>(entity).unwrap();
+        // players are 0.6 blocks wide, so this is as far as we can go
+        assert!((entity_pos.x - 4.7).abs() < 1e-6, "{}", entity_pos.x);
+    }
 }
@@ -1,7 +1,9 @@
 use azalea_buf::McBuf;
-use azalea_core::{ResourceLocation, Vec3};
+use azalea_core::{rotation_from_byte, velocity_from_shorts, ResourceLocation, Vec3};
 use azalea_protocol_macros::ClientboundGamePacket;
-use azalea_world::entity::{metadata::apply_default_metadata, EntityBundle};
+use azalea_world::entity::{
+    metadata::apply_default_metadata, EntityBundle, HeadRotation, LookDirection,
+};
 use uuid::Uuid;
 
 #[derive(Clone, Debug, McBuf, ClientboundGamePacket)]
@@ -42,7 +44,14 @@ impl ClientboundAddEntityPacket {
     /// must apply the metadata after inserting the bundle with
     /// [`Self::apply_metadata`].
     pub fn as_entity_bundle(&self, world_name: ResourceLocation) -> EntityBundle {
-        EntityBundle::new(self.uuid, self.position, self.entity_type, world_name)
+        let mut bundle = EntityBundle::new(self.uuid, self.position, self.entity_type, world_name);
+        bundle.direction = LookDirection {
+            x_rot: rotation_from_byte(self.x_rot),
+            y_rot: rotation_from_byte(self.y_rot),
+        };
+        bundle.head_rotation = HeadRotation(rotation_from_byte(self.y_head_rot));
+        bundle.physics.delta = velocity_from_shorts(self.x_vel, self.y_vel, self.z_vel);
+        bundle
     }
 
     /// Apply the default metadata for the given entity.
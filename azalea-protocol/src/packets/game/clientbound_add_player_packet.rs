@@ -1,8 +1,10 @@
 use azalea_buf::McBuf;
-use azalea_core::{ResourceLocation, Vec3};
+use azalea_core::{rotation_from_byte, ResourceLocation, Vec3};
 use azalea_protocol_macros::ClientboundGamePacket;
 use azalea_registry::EntityKind;
-use azalea_world::entity::{metadata::PlayerMetadataBundle, EntityBundle, PlayerBundle};
+use azalea_world::entity::{
+    metadata::PlayerMetadataBundle, EntityBundle, HeadRotation, LookDirection, PlayerBundle,
+};
 use uuid::Uuid;
 
 /// This packet is sent by the server when a player comes into visible range,
@@ -19,8 +21,16 @@ pub struct ClientboundAddPlayerPacket {
 
 impl ClientboundAddPlayerPacket {
     pub fn as_player_bundle(&self, world_name: ResourceLocation) -> PlayerBundle {
+        let mut entity =
+            EntityBundle::new(self.uuid, self.position, EntityKind::Player, world_name);
+        entity.direction = LookDirection {
+            x_rot: rotation_from_byte(self.x_rot),
+            y_rot: rotation_from_byte(self.y_rot),
+        };
+        // players' heads face the same way as their body when they spawn
+        entity.head_rotation = HeadRotation(entity.direction.y_rot);
         PlayerBundle {
-            entity: EntityBundle::new(self.uuid, self.position, EntityKind::Player, world_name),
+            entity,
             metadata: PlayerMetadataBundle::default(),
         }
     }
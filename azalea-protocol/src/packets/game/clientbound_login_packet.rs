@@ -45,6 +45,8 @@ pub mod registry {
     use azalea_buf::{BufReadError, McBufReadable, McBufWritable};
     use azalea_core::ResourceLocation;
     use azalea_nbt::Nbt;
+    #[cfg(not(feature = "strict_registry"))]
+    use azalea_nbt::NbtList;
     use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
     use std::{collections::HashMap, io::Cursor};
 
@@ -136,6 +138,39 @@ pub mod registry {
         pub damage_type: Nbt,
     }
 
+    impl RegistryRoot {
+        /// Get the names of the biomes in the registry, indexed by the IDs that
+        /// chunks use for them.
+        pub fn biome_names(&self) -> HashMap<u32, ResourceLocation> {
+            #[cfg(feature = "strict_registry")]
+            {
+                self.world_type
+                    .value
+                    .iter()
+                    .map(|biome| (biome.id, biome.name.clone()))
+                    .collect()
+            }
+            #[cfg(not(feature = "strict_registry"))]
+            {
+                let Some(Nbt::List(NbtList::Compound(biomes))) = self
+                    .world_type
+                    .as_compound()
+                    .and_then(|registry| registry.get("value"))
+                else {
+                    return HashMap::new();
+                };
+                biomes
+                    .iter()
+                    .filter_map(|biome| {
+                        let id = *biome.get("id")?.as_int()?;
+                        let name = biome.get("name")?.as_string()?;
+                        Some((id as u32, ResourceLocation::new(name)))
+                    })
+                    .collect()
+            }
+        }
+    }
+
     /// A collection of values for a certain type of registry data.
     #[derive(Debug, Clone, Serialize, Deserialize)]
     #[cfg_attr(feature = "strict_registry", serde(deny_unknown_fields))]
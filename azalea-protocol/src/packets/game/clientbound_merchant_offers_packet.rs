@@ -1,5 +1,5 @@
 use azalea_buf::McBuf;
-use azalea_inventory::ItemSlot;
+use azalea_inventory::{item::MaxStackSizeExt, ItemSlot};
 use azalea_protocol_macros::ClientboundGamePacket;
 
 #[derive(Clone, Debug, McBuf, ClientboundGamePacket)]
@@ -28,3 +28,63 @@ pub struct MerchantOffer {
     pub price_multiplier: f32,
     pub demand: u32,
 }
+
+impl MerchantOffer {
+    /// The first item that has to be paid, with its count changed by the
+    /// demand for the offer and any special price (like from curing a zombie
+    /// villager).
+    pub fn cost_a(&self) -> ItemSlot {
+        let ItemSlot::Present(base_cost_a) = &self.base_cost_a else {
+            return ItemSlot::Empty;
+        };
+        let base_count = base_cost_a.count as i32;
+        let demand_diff =
+            ((base_count * self.demand as i32) as f32 * self.price_multiplier).floor() as i32;
+        let count = (base_count + demand_diff.max(0) + self.special_price_diff)
+            .clamp(1, base_cost_a.kind.max_stack_size() as i32);
+
+        let mut cost_a = base_cost_a.clone();
+        cost_a.count = count as i8;
+        ItemSlot::Present(cost_a)
+    }
+
+    /// Whether the offer has been used too many times and the merchant has to
+    /// restock before it can be used again.
+    pub fn is_out_of_stock(&self) -> bool {
+        self.out_of_stock || self.uses >= self.max_uses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use azalea_registry::Item;
+
+    use super::*;
+
+    #[test]
+    fn test_cost_a() {
+        let mut offer = MerchantOffer {
+            base_cost_a: ItemSlot::new(Item::Wheat, 20),
+            result: ItemSlot::new(Item::Emerald, 1),
+            cost_b: ItemSlot::Empty,
+            out_of_stock: false,
+            uses: 0,
+            max_uses: 16,
+            xp: 2,
+            special_price_diff: 0,
+            price_multiplier: 0.05,
+            demand: 0,
+        };
+        assert_eq!(offer.cost_a(), ItemSlot::new(Item::Wheat, 20));
+
+        offer.demand = 3;
+        assert_eq!(offer.cost_a(), ItemSlot::new(Item::Wheat, 23));
+
+        // a discount can't make it free
+        offer.special_price_diff = -30;
+        assert_eq!(offer.cost_a(), ItemSlot::new(Item::Wheat, 1));
+
+        offer.uses = 16;
+        assert!(offer.is_out_of_stock());
+    }
+}
@@ -2,6 +2,7 @@ use azalea_buf::{BufReadError, McBuf};
 use azalea_buf::{McBufReadable, McBufWritable};
 use azalea_inventory::ItemSlot;
 use azalea_protocol_macros::ClientboundGamePacket;
+use azalea_world::entity::Equipment;
 use std::io::Cursor;
 
 #[derive(Clone, Debug, McBuf, ClientboundGamePacket)]
@@ -16,6 +17,23 @@ pub struct EquipmentSlots {
     pub slots: Vec<(EquipmentSlot, ItemSlot)>,
 }
 
+impl EquipmentSlots {
+    /// Put the items from this packet into an entity's [`Equipment`].
+    pub fn apply_to(&self, equipment: &mut Equipment) {
+        for (slot, item) in &self.slots {
+            let equipment_slot = match slot {
+                EquipmentSlot::MainHand => &mut equipment.main_hand,
+                EquipmentSlot::OffHand => &mut equipment.off_hand,
+                EquipmentSlot::Feet => &mut equipment.feet,
+                EquipmentSlot::Legs => &mut equipment.legs,
+                EquipmentSlot::Chest => &mut equipment.chest,
+                EquipmentSlot::Head => &mut equipment.head,
+            };
+            *equipment_slot = item.clone();
+        }
+    }
+}
+
 impl McBufReadable for EquipmentSlots {
     fn read_from(buf: &mut Cursor<&[u8]>) -> Result<Self, BufReadError> {
         let mut slots = vec![];
@@ -21,7 +21,7 @@ impl McBufReadable for ClientboundSetScorePacket {
         // if it's change, read the score
         let method = match method_id {
             0 => Method::Change {
-                score: u32::var_read_from(buf)?,
+                score: i32::var_read_from(buf)?,
             },
             1 => Method::Remove,
             id => return Err(BufReadError::UnexpectedEnumVariant { id: id as i32 }),
@@ -56,6 +56,6 @@ impl McBufWritable for ClientboundSetScorePacket {
 
 #[derive(Clone, Copy, Debug)]
 pub enum Method {
-    Change { score: u32 },
+    Change { score: i32 },
     Remove,
 }
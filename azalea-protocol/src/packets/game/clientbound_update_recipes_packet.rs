@@ -162,6 +162,43 @@ pub struct Ingredient {
     pub allowed: Vec<ItemSlot>,
 }
 
+impl Ingredient {
+    /// Whether this ingredient doesn't need an item. Shaped recipes use these
+    /// for the gaps in their pattern.
+    pub fn is_empty(&self) -> bool {
+        self.allowed.iter().all(ItemSlot::is_empty)
+    }
+
+    /// Whether the given item can be used as this ingredient. This ignores
+    /// the count and NBT of the item.
+    pub fn test(&self, item: &ItemSlot) -> bool {
+        item.is_present()
+            && self
+                .allowed
+                .iter()
+                .any(|allowed| allowed.kind() == item.kind())
+    }
+}
+
+impl RecipeData {
+    /// The item that this recipe makes. This is `None` for special recipes
+    /// (like dyeing armor) and smithing trims, since their result depends on
+    /// the ingredients.
+    pub fn result(&self) -> Option<&ItemSlot> {
+        match self {
+            RecipeData::CraftingShaped(recipe) => Some(&recipe.result),
+            RecipeData::CraftingShapeless(recipe) => Some(&recipe.result),
+            RecipeData::Smelting(recipe)
+            | RecipeData::Blasting(recipe)
+            | RecipeData::Smoking(recipe)
+            | RecipeData::CampfireCooking(recipe) => Some(&recipe.result),
+            RecipeData::Stonecutting(recipe) => Some(&recipe.result),
+            RecipeData::SmithingTransform(recipe) => Some(&recipe.result),
+            _ => None,
+        }
+    }
+}
+
 impl McBufWritable for Recipe {
     fn write_into(&self, buf: &mut impl Write) -> Result<(), std::io::Error> {
         let recipe_serializer = match &self.data {
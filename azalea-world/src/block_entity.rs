@@ -0,0 +1,379 @@
+//! Block entities are the extra data that some blocks have, like the text on
+//! signs or the patterns on banners.
+
+use std::str::FromStr;
+
+use azalea_chat::FormattedText;
+use azalea_nbt::{Nbt, NbtCompound, NbtList};
+use azalea_registry::{BannerPattern, BlockEntityKind, EntityKind};
+
+/// The data for a block entity, as sent by the server.
+///
+/// The server only sends the parts of the data that the client needs to render
+/// the block, so for example chests won't have their items here.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockEntity {
+    pub kind: BlockEntityKind,
+    /// The raw NBT data. You should usually use the typed accessors like
+    /// [`Self::sign_text`] instead of reading this directly.
+    pub data: Nbt,
+}
+
+/// The text on both sides of a sign or hanging sign.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignText {
+    pub front: SignSide,
+    pub back: SignSide,
+    /// Whether the sign was waxed, which means players can't edit it anymore.
+    pub is_waxed: bool,
+}
+
+/// The text on one side of a sign.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SignSide {
+    /// The four lines of text on the sign.
+    pub messages: [FormattedText; 4],
+    pub color: DyeColor,
+    pub has_glowing_text: bool,
+}
+
+/// A single layer of a banner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BannerLayer {
+    pub pattern: BannerPattern,
+    pub color: DyeColor,
+}
+
+/// The 16 colors of dye, used for things like sign text and banner patterns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DyeColor {
+    White,
+    Orange,
+    Magenta,
+    LightBlue,
+    Yellow,
+    Lime,
+    Pink,
+    Gray,
+    LightGray,
+    Cyan,
+    Purple,
+    Blue,
+    Brown,
+    Green,
+    Red,
+    #[default]
+    Black,
+}
+
+impl DyeColor {
+    const ALL: [DyeColor; 16] = [
+        DyeColor::White,
+        DyeColor::Orange,
+        DyeColor::Magenta,
+        DyeColor::LightBlue,
+        DyeColor::Yellow,
+        DyeColor::Lime,
+        DyeColor::Pink,
+        DyeColor::Gray,
+        DyeColor::LightGray,
+        DyeColor::Cyan,
+        DyeColor::Purple,
+        DyeColor::Blue,
+        DyeColor::Brown,
+        DyeColor::Green,
+        DyeColor::Red,
+        DyeColor::Black,
+    ];
+
+    pub fn from_id(id: u32) -> Option<Self> {
+        Self::ALL.get(id as usize).copied()
+    }
+
+    /// The name of the color, like `light_blue`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DyeColor::White => "white",
+            DyeColor::Orange => "orange",
+            DyeColor::Magenta => "magenta",
+            DyeColor::LightBlue => "light_blue",
+            DyeColor::Yellow => "yellow",
+            DyeColor::Lime => "lime",
+            DyeColor::Pink => "pink",
+            DyeColor::Gray => "gray",
+            DyeColor::LightGray => "light_gray",
+            DyeColor::Cyan => "cyan",
+            DyeColor::Purple => "purple",
+            DyeColor::Blue => "blue",
+            DyeColor::Brown => "brown",
+            DyeColor::Green => "green",
+            DyeColor::Red => "red",
+            DyeColor::Black => "black",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|color| color.name() == name)
+    }
+}
+
+impl BlockEntity {
+    pub fn new(kind: BlockEntityKind, data: Nbt) -> Self {
+        Self { kind, data }
+    }
+
+    /// The compound tag that contains the block entity's data, or `None` if
+    /// the server didn't send any.
+    pub fn tag(&self) -> Option<&NbtCompound> {
+        let root = self.data.as_compound()?;
+        // the root tag is usually wrapped in a compound with an empty name
+        match root.get("") {
+            Some(Nbt::Compound(tag)) => Some(tag),
+            _ => Some(root),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&Nbt> {
+        self.tag()?.get(key)
+    }
+
+    /// The custom name of a block entity that was renamed in an anvil, like
+    /// a chest or banner.
+    pub fn custom_name(&self) -> Option<FormattedText> {
+        parse_json_text(self.get("CustomName")?.as_string()?)
+    }
+
+    /// The text on a sign or hanging sign.
+    pub fn sign_text(&self) -> Option<SignText> {
+        if !matches!(
+            self.kind,
+            BlockEntityKind::Sign | BlockEntityKind::HangingSign
+        ) {
+            return None;
+        }
+        Some(SignText {
+            front: sign_side(self.get("front_text"))?,
+            back: sign_side(self.get("back_text"))?,
+            is_waxed: matches!(self.get("is_waxed"), Some(Nbt::Byte(1))),
+        })
+    }
+
+    /// The type of entity that a mob spawner spawns.
+    pub fn spawner_entity(&self) -> Option<EntityKind> {
+        if self.kind != BlockEntityKind::MobSpawner {
+            return None;
+        }
+        let id = self
+            .get("SpawnData")?
+            .as_compound()?
+            .get("entity")?
+            .as_compound()?
+            .get("id")?
+            .as_string()?;
+        EntityKind::from_str(id).ok()
+    }
+
+    /// The patterns on a banner, from the bottom layer to the top. This
+    /// doesn't include the base color of the banner, which is part of the
+    /// block state.
+    pub fn banner_patterns(&self) -> Option<Vec<BannerLayer>> {
+        if self.kind != BlockEntityKind::Banner {
+            return None;
+        }
+        let layers = match self.get("Patterns") {
+            Some(Nbt::List(NbtList::Compound(layers))) => layers,
+            _ => return Some(Vec::new()),
+        };
+        Some(
+            layers
+                .iter()
+                .filter_map(|layer| {
+                    let pattern = banner_pattern_from_code(layer.get("Pattern")?.as_string()?)?;
+                    let color = DyeColor::from_id(*layer.get("Color")?.as_int()? as u32)?;
+                    Some(BannerLayer { pattern, color })
+                })
+                .collect(),
+        )
+    }
+
+    /// The username of the player whose head this is.
+    pub fn skull_owner(&self) -> Option<String> {
+        if self.kind != BlockEntityKind::Skull {
+            return None;
+        }
+        match self.get("SkullOwner")? {
+            Nbt::String(name) => Some(name.to_string()),
+            Nbt::Compound(profile) => Some(profile.get("Name")?.as_string()?.to_string()),
+            _ => None,
+        }
+    }
+}
+
+fn parse_json_text(json: &str) -> Option<FormattedText> {
+    serde_json::from_str(json).ok()
+}
+
+fn sign_side(tag: Option<&Nbt>) -> Option<SignSide> {
+    // signs that were never edited might not have any text
+    let Some(tag) = tag else {
+        return Some(SignSide::default());
+    };
+    let tag = tag.as_compound()?;
+    let mut side = SignSide {
+        color: tag
+            .get("color")
+            .and_then(|color| DyeColor::from_name(color.as_string()?))
+            .unwrap_or_default(),
+        has_glowing_text: matches!(tag.get("has_glowing_text"), Some(Nbt::Byte(1))),
+        ..Default::default()
+    };
+    if let Some(Nbt::List(NbtList::String(messages))) = tag.get("messages") {
+        for (line, message) in side.messages.iter_mut().zip(messages) {
+            *line = parse_json_text(message).unwrap_or_default();
+        }
+    }
+    Some(side)
+}
+
+/// Banner patterns are stored in NBT as short codes instead of their
+/// registry names.
+fn banner_pattern_from_code(code: &str) -> Option<BannerPattern> {
+    Some(match code {
+        "b" => BannerPattern::Base,
+        "bl" => BannerPattern::SquareBottomLeft,
+        "br" => BannerPattern::SquareBottomRight,
+        "tl" => BannerPattern::SquareTopLeft,
+        "tr" => BannerPattern::SquareTopRight,
+        "bs" => BannerPattern::StripeBottom,
+        "ts" => BannerPattern::StripeTop,
+        "ls" => BannerPattern::StripeLeft,
+        "rs" => BannerPattern::StripeRight,
+        "cs" => BannerPattern::StripeCenter,
+        "ms" => BannerPattern::StripeMiddle,
+        "drs" => BannerPattern::StripeDownright,
+        "dls" => BannerPattern::StripeDownleft,
+        "ss" => BannerPattern::SmallStripes,
+        "cr" => BannerPattern::Cross,
+        "sc" => BannerPattern::StraightCross,
+        "bt" => BannerPattern::TriangleBottom,
+        "tt" => BannerPattern::TriangleTop,
+        "bts" => BannerPattern::TrianglesBottom,
+        "tts" => BannerPattern::TrianglesTop,
+        "ld" => BannerPattern::DiagonalLeft,
+        "rd" => BannerPattern::DiagonalUpRight,
+        "lud" => BannerPattern::DiagonalUpLeft,
+        "rud" => BannerPattern::DiagonalRight,
+        "mc" => BannerPattern::Circle,
+        "mr" => BannerPattern::Rhombus,
+        "vh" => BannerPattern::HalfVertical,
+        "hh" => BannerPattern::HalfHorizontal,
+        "vhr" => BannerPattern::HalfVerticalRight,
+        "hhb" => BannerPattern::HalfHorizontalBottom,
+        "bo" => BannerPattern::Border,
+        "cbo" => BannerPattern::CurlyBorder,
+        "gra" => BannerPattern::Gradient,
+        "gru" => BannerPattern::GradientUp,
+        "bri" => BannerPattern::Bricks,
+        "glb" => BannerPattern::Globe,
+        "cre" => BannerPattern::Creeper,
+        "sku" => BannerPattern::Skull,
+        "flo" => BannerPattern::Flower,
+        "moj" => BannerPattern::Mojang,
+        "pig" => BannerPattern::Piglin,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compound(entries: Vec<(&str, Nbt)>) -> NbtCompound {
+        NbtCompound::from_iter(entries.into_iter().map(|(k, v)| (k.into(), v)))
+    }
+
+    fn root(entries: Vec<(&str, Nbt)>) -> Nbt {
+        Nbt::Compound(compound(vec![("", Nbt::Compound(compound(entries)))]))
+    }
+
+    #[test]
+    fn test_sign_text() {
+        let front = compound(vec![
+            (
+                "messages",
+                Nbt::List(NbtList::String(vec![
+                    r#"{"text":"hello"}"#.into(),
+                    r#""world""#.into(),
+                    r#""""#.into(),
+                    r#""""#.into(),
+                ])),
+            ),
+            ("color", Nbt::String("red".into())),
+            ("has_glowing_text", Nbt::Byte(1)),
+        ]);
+        let sign = BlockEntity::new(
+            BlockEntityKind::Sign,
+            root(vec![
+                ("front_text", Nbt::Compound(front)),
+                ("is_waxed", Nbt::Byte(0)),
+            ]),
+        );
+        let text = sign.sign_text().unwrap();
+        assert_eq!(text.front.messages[0].to_string(), "hello");
+        assert_eq!(text.front.messages[1].to_string(), "world");
+        assert_eq!(text.front.color, DyeColor::Red);
+        assert!(text.front.has_glowing_text);
+        assert_eq!(text.back, SignSide::default());
+        assert!(!text.is_waxed);
+    }
+
+    #[test]
+    fn test_typed_accessors() {
+        let banner = BlockEntity::new(
+            BlockEntityKind::Banner,
+            root(vec![
+                ("CustomName", Nbt::String(r#"{"text":"flag"}"#.into())),
+                (
+                    "Patterns",
+                    Nbt::List(NbtList::Compound(vec![compound(vec![
+                        ("Pattern", Nbt::String("cre".into())),
+                        ("Color", Nbt::Int(5)),
+                    ])])),
+                ),
+            ]),
+        );
+        assert_eq!(banner.custom_name().unwrap().to_string(), "flag");
+        assert_eq!(
+            banner.banner_patterns().unwrap(),
+            vec![BannerLayer {
+                pattern: BannerPattern::Creeper,
+                color: DyeColor::Lime,
+            }]
+        );
+        assert!(banner.sign_text().is_none());
+
+        let spawner = BlockEntity::new(
+            BlockEntityKind::MobSpawner,
+            root(vec![(
+                "SpawnData",
+                Nbt::Compound(compound(vec![(
+                    "entity",
+                    Nbt::Compound(compound(vec![(
+                        "id",
+                        Nbt::String("minecraft:zombie".into()),
+                    )])),
+                )])),
+            )]),
+        );
+        assert_eq!(spawner.spawner_entity(), Some(EntityKind::Zombie));
+
+        let skull = BlockEntity::new(
+            BlockEntityKind::Skull,
+            root(vec![(
+                "SkullOwner",
+                Nbt::Compound(compound(vec![("Name", Nbt::String("Notch".into()))])),
+            )]),
+        );
+        assert_eq!(skull.skull_owner().as_deref(), Some("Notch"));
+    }
+}
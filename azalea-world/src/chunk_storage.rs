@@ -1,6 +1,8 @@
+use crate::block_entity::BlockEntity;
+use crate::light::ChunkLight;
 use crate::palette::PalettedContainer;
 use crate::palette::PalettedContainerType;
-use azalea_block::BlockState;
+use azalea_block::{Block, BlockState};
 use azalea_buf::{BufReadError, McBufReadable, McBufWritable};
 use azalea_core::{BlockPos, ChunkBlockPos, ChunkPos, ChunkSectionBlockPos};
 use log::{debug, trace, warn};
@@ -36,13 +38,16 @@ pub struct ChunkStorage {
     pub chunks: HashMap<ChunkPos, Weak<RwLock<Chunk>>>,
 }
 
-/// A single chunk in a world (16*?*16 blocks). This only contains the blocks
-/// and biomes. You can derive the height of the chunk from the number of
-/// sections, but you need a [`ChunkStorage`] to get the minimum Y
-/// coordinate.
+/// A single chunk in a world (16*?*16 blocks). This only contains the blocks,
+/// biomes, block entities, and light. You can derive the height of the chunk
+/// from the number of sections, but you need a [`ChunkStorage`] to get the
+/// minimum Y coordinate.
 #[derive(Debug)]
 pub struct Chunk {
     pub sections: Vec<Section>,
+    /// The block entities in the chunk, like signs and chests.
+    pub block_entities: HashMap<ChunkBlockPos, BlockEntity>,
+    pub light: ChunkLight,
 }
 
 /// A section of a chunk, i.e. a 16*16*16 block area.
@@ -67,6 +72,8 @@ impl Default for Chunk {
     fn default() -> Self {
         Chunk {
             sections: vec![Section::default(); (384 / 16) as usize],
+            block_entities: HashMap::new(),
+            light: ChunkLight::default(),
         }
     }
 }
@@ -109,10 +116,13 @@ impl PartialChunkStorage {
         Some(chunk.get_and_set(&ChunkBlockPos::from(pos), state, chunk_storage.min_y))
     }
 
+    /// Replace a chunk with the data from a `LevelChunkWithLight` packet and
+    /// the block entities that were sent with it.
     pub fn replace_with_packet_data(
         &mut self,
         pos: &ChunkPos,
         data: &mut Cursor<&[u8]>,
+        block_entities: HashMap<ChunkBlockPos, BlockEntity>,
         chunk_storage: &mut ChunkStorage,
     ) -> Result<(), BufReadError> {
         debug!("Replacing chunk at {:?}", pos);
@@ -125,7 +135,8 @@ impl PartialChunkStorage {
             return Ok(());
         }
 
-        let chunk = Chunk::read_with_dimension_height(data, chunk_storage.height)?;
+        let mut chunk = Chunk::read_with_dimension_height(data, chunk_storage.height)?;
+        chunk.block_entities = block_entities;
 
         trace!("Loaded chunk {:?}", pos);
         self.set(pos, Some(chunk), chunk_storage);
@@ -159,6 +170,28 @@ impl PartialChunkStorage {
         Some(&mut self.chunks[index])
     }
 
+    /// Stop referencing the chunk at the given position, and remove it from the
+    /// shared storage if nothing else is using it. Unlike setting the chunk to
+    /// `None`, this works even if the chunk is outside of the view distance.
+    ///
+    /// Returns whether we had the chunk loaded.
+    pub fn unload(&mut self, pos: &ChunkPos, chunk_storage: &mut ChunkStorage) -> bool {
+        let Some(shared_chunk) = chunk_storage.get(pos) else {
+            return false;
+        };
+        let index = self.get_index(pos);
+        let chunk = &mut self.chunks[index];
+        let was_loaded = chunk
+            .as_ref()
+            .map_or(false, |chunk| Arc::ptr_eq(chunk, &shared_chunk));
+        if was_loaded {
+            *chunk = None;
+        }
+        drop(shared_chunk);
+        chunk_storage.remove_if_unused(pos);
+        was_loaded
+    }
+
     /// Set a chunk in the shared storage and reference it from the limited
     /// storage. Use [`Self::set_with_shared_reference`] if you already have
     /// an `Arc<RwLock<Chunk>>`.
@@ -181,15 +214,18 @@ impl PartialChunkStorage {
         chunk: Option<Arc<RwLock<Chunk>>>,
         chunk_storage: &mut ChunkStorage,
     ) {
+        let removing = chunk.is_none();
         if let Some(chunk) = &chunk {
             chunk_storage.chunks.insert(*pos, Arc::downgrade(chunk));
-        } else {
-            // don't remove it from the shared storage, since it'll be removed
-            // automatically if this was the last reference
         }
         if let Some(chunk_mut) = self.limited_get_mut(pos) {
             *chunk_mut = chunk;
         }
+        if removing {
+            // the chunk is only removed from the shared storage if this was the last
+            // reference to it
+            chunk_storage.remove_if_unused(pos);
+        }
     }
 }
 impl ChunkStorage {
@@ -205,6 +241,40 @@ impl ChunkStorage {
         self.chunks.get(pos).and_then(|chunk| chunk.upgrade())
     }
 
+    /// Forget about the chunk at the given position if nothing is holding a
+    /// reference to it anymore. Returns whether it was removed.
+    pub fn remove_if_unused(&mut self, pos: &ChunkPos) -> bool {
+        if self
+            .chunks
+            .get(pos)
+            .map_or(false, |chunk| chunk.strong_count() == 0)
+        {
+            self.chunks.remove(pos);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The number of chunks that are currently loaded.
+    pub fn loaded_chunk_count(&self) -> usize {
+        self.chunks
+            .values()
+            .filter(|chunk| chunk.strong_count() > 0)
+            .count()
+    }
+
+    /// A rough estimate of how many bytes the loaded chunks are using. This
+    /// doesn't include the memory used by entities.
+    pub fn estimated_memory_usage(&self) -> usize {
+        self.chunks
+            .values()
+            .filter_map(Weak::upgrade)
+            .map(|chunk| chunk.read().estimated_memory_usage())
+            .sum::<usize>()
+            + self.chunks.capacity() * std::mem::size_of::<(ChunkPos, Weak<RwLock<Chunk>>)>()
+    }
+
     pub fn get_block_state(&self, pos: &BlockPos) -> Option<BlockState> {
         let chunk_pos = ChunkPos::from(pos);
         let chunk = self.get(&chunk_pos)?;
@@ -221,6 +291,29 @@ impl ChunkStorage {
         let mut chunk = chunk.write();
         Some(chunk.get_and_set(&ChunkBlockPos::from(pos), state, self.min_y))
     }
+
+    /// Get the block entity at the given position, if there is one.
+    pub fn get_block_entity(&self, pos: &BlockPos) -> Option<BlockEntity> {
+        let chunk = self.get(&ChunkPos::from(pos))?;
+        let chunk = chunk.read();
+        chunk.block_entities.get(&ChunkBlockPos::from(pos)).cloned()
+    }
+
+    /// Set or remove the block entity at the given position. Returns the
+    /// block entity that was there before.
+    pub fn set_block_entity(
+        &self,
+        pos: &BlockPos,
+        block_entity: Option<BlockEntity>,
+    ) -> Option<BlockEntity> {
+        let chunk = self.get(&ChunkPos::from(pos))?;
+        let mut chunk = chunk.write();
+        let chunk_block_pos = ChunkBlockPos::from(pos);
+        match block_entity {
+            Some(block_entity) => chunk.block_entities.insert(chunk_block_pos, block_entity),
+            None => chunk.block_entities.remove(&chunk_block_pos),
+        }
+    }
 }
 
 impl Chunk {
@@ -234,7 +327,11 @@ impl Chunk {
             let section = Section::read_from(buf)?;
             sections.push(section);
         }
-        Ok(Chunk { sections })
+        Ok(Chunk {
+            sections,
+            block_entities: HashMap::new(),
+            light: ChunkLight::default(),
+        })
     }
 
     pub fn get(&self, pos: &ChunkBlockPos, min_y: i32) -> Option<BlockState> {
@@ -253,6 +350,46 @@ impl Chunk {
         Some(section.get(chunk_section_pos))
     }
 
+    /// A rough estimate of how many bytes this chunk is using.
+    pub fn estimated_memory_usage(&self) -> usize {
+        let sections = self
+            .sections
+            .iter()
+            .map(|section| {
+                std::mem::size_of::<Section>()
+                    + section.states.estimated_heap_usage()
+                    + section.biomes.estimated_heap_usage()
+            })
+            .sum::<usize>();
+        let block_entities =
+            self.block_entities.capacity() * std::mem::size_of::<(ChunkBlockPos, BlockEntity)>();
+        std::mem::size_of::<Chunk>() + sections + block_entities + self.light.estimated_heap_usage()
+    }
+
+    /// Get the ID of the biome at a position in the chunk.
+    pub fn get_biome(&self, pos: &ChunkBlockPos, min_y: i32) -> Option<u32> {
+        if pos.y < min_y {
+            return None;
+        }
+        let section = self.sections.get(section_index(pos.y, min_y) as usize)?;
+        // biomes are stored in 4x4x4 cells
+        Some(section.biomes.get(
+            pos.x as usize >> 2,
+            (pos.y.rem_euclid(16) >> 2) as usize,
+            pos.z as usize >> 2,
+        ))
+    }
+
+    /// Replace the biomes in the chunk with the data from a `ChunksBiomes`
+    /// packet, which has a biome container for each section.
+    pub fn read_biomes(&mut self, buf: &mut Cursor<&[u8]>) -> Result<(), BufReadError> {
+        for section in &mut self.sections {
+            section.biomes =
+                PalettedContainer::read_with_type(buf, &PalettedContainerType::Biomes)?;
+        }
+        Ok(())
+    }
+
     pub fn get_and_set(
         &mut self,
         pos: &ChunkBlockPos,
@@ -263,7 +400,9 @@ impl Chunk {
         // TODO: make sure the section exists
         let section = &mut self.sections[section_index as usize];
         let chunk_section_pos = ChunkSectionBlockPos::from(pos);
-        section.get_and_set(chunk_section_pos, state)
+        let previous_state = section.get_and_set(chunk_section_pos, state);
+        self.remove_block_entity_if_replaced(pos, previous_state, state);
+        previous_state
     }
 
     pub fn set(&mut self, pos: &ChunkBlockPos, state: BlockState, min_y: i32) {
@@ -271,7 +410,31 @@ impl Chunk {
         // TODO: make sure the section exists
         let section = &mut self.sections[section_index as usize];
         let chunk_section_pos = ChunkSectionBlockPos::from(pos);
-        section.set(chunk_section_pos, state);
+        if self.block_entities.contains_key(pos) {
+            let previous_state = section.get_and_set(chunk_section_pos, state);
+            self.remove_block_entity_if_replaced(pos, previous_state, state);
+        } else {
+            section.set(chunk_section_pos, state);
+        }
+    }
+
+    /// Like vanilla, a block entity is removed when its block is replaced with
+    /// a different block, but not when only the block's state changes (like
+    /// a chest being opened).
+    fn remove_block_entity_if_replaced(
+        &mut self,
+        pos: &ChunkBlockPos,
+        previous_state: BlockState,
+        state: BlockState,
+    ) {
+        if previous_state == state || !self.block_entities.contains_key(pos) {
+            return;
+        }
+        let previous_block = Box::<dyn Block>::from(previous_state);
+        let block = Box::<dyn Block>::from(state);
+        if previous_block.id() != block.id() {
+            self.block_entities.remove(pos);
+        }
     }
 }
 
@@ -420,4 +583,79 @@ mod tests {
             .get_block_state(&BlockPos { x: 0, y: -65, z: 0 })
             .is_none());
     }
+
+    #[test]
+    fn test_block_entity_removed_when_block_replaced() {
+        let mut chunk_storage = ChunkStorage::default();
+        let mut partial_chunk_storage = PartialChunkStorage::default();
+        partial_chunk_storage.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut chunk_storage,
+        );
+        let pos = BlockPos::new(1, 2, 3);
+        chunk_storage.set_block_state(&pos, azalea_registry::Block::Chest.into());
+        chunk_storage.set_block_entity(
+            &pos,
+            Some(BlockEntity::new(
+                azalea_registry::BlockEntityKind::Chest,
+                azalea_nbt::Nbt::End,
+            )),
+        );
+        assert!(chunk_storage.get_block_entity(&pos).is_some());
+
+        // changing the state of the same block keeps the block entity
+        let other_chest_state = azalea_block::BlockStates::from(azalea_registry::Block::Chest)
+            .into_iter()
+            .find(|&state| state != azalea_registry::Block::Chest.into())
+            .unwrap();
+        chunk_storage.set_block_state(&pos, other_chest_state);
+        assert!(chunk_storage.get_block_entity(&pos).is_some());
+
+        chunk_storage.set_block_state(&pos, BlockState::AIR);
+        assert!(chunk_storage.get_block_entity(&pos).is_none());
+    }
+
+    #[test]
+    fn test_get_biome() {
+        let mut chunk = Chunk::default();
+        // the section with y=0 to 15 in a world that starts at -64
+        chunk.sections[4].biomes.set(1, 2, 3, 7);
+        let min_y = -64;
+        assert_eq!(
+            chunk.get_biome(&ChunkBlockPos::new(4, 8, 12), min_y),
+            Some(7)
+        );
+        assert_eq!(
+            chunk.get_biome(&ChunkBlockPos::new(7, 11, 15), min_y),
+            Some(7)
+        );
+        assert_eq!(
+            chunk.get_biome(&ChunkBlockPos::new(8, 8, 12), min_y),
+            Some(0)
+        );
+        assert_eq!(chunk.get_biome(&ChunkBlockPos::new(0, -65, 0), min_y), None);
+        assert_eq!(chunk.get_biome(&ChunkBlockPos::new(0, 320, 0), min_y), None);
+    }
+
+    #[test]
+    fn test_unload_chunk() {
+        let mut chunk_storage = ChunkStorage::default();
+        let mut first = PartialChunkStorage::default();
+        let mut second = PartialChunkStorage::default();
+        let pos = ChunkPos { x: 1, z: 2 };
+        first.set(&pos, Some(Chunk::default()), &mut chunk_storage);
+        second.set_with_shared_reference(&pos, chunk_storage.get(&pos), &mut chunk_storage);
+
+        assert!(first.unload(&pos, &mut chunk_storage));
+        assert!(!first.unload(&pos, &mut chunk_storage));
+        // the other client still has it loaded
+        assert_eq!(chunk_storage.loaded_chunk_count(), 1);
+        assert!(chunk_storage.estimated_memory_usage() > 0);
+
+        // unloading works even after the view center moved away
+        second.view_center = ChunkPos { x: 100, z: 100 };
+        assert!(second.unload(&pos, &mut chunk_storage));
+        assert!(chunk_storage.chunks.is_empty());
+    }
 }
@@ -8,7 +8,7 @@ use std::{
     sync::{Arc, Weak},
 };
 
-use crate::{entity::WorldName, ChunkStorage, Instance};
+use crate::{entity::WorldName, ChunkStorage, Instance, WorldBorder, WorldTime};
 
 /// A container of [`Instance`]s (aka worlds). Instances are stored as a Weak
 /// pointer here, so if no clients are using an instance it will be forgotten.
@@ -41,6 +41,16 @@ impl InstanceContainer {
         self.worlds.get(name).and_then(|world| world.upgrade())
     }
 
+    /// A rough estimate of how many bytes the chunks in all the instances are
+    /// using. Chunks that are shared between clients are only counted once.
+    pub fn estimated_memory_usage(&self) -> usize {
+        self.worlds
+            .values()
+            .filter_map(Weak::upgrade)
+            .map(|world| world.read().chunks.estimated_memory_usage())
+            .sum()
+    }
+
     /// Add an empty world to the container (or not if it already exists) and
     /// returns a strong reference to the world.
     #[must_use = "the world will be immediately forgotten if unused"]
@@ -70,6 +80,9 @@ impl InstanceContainer {
                 chunks: ChunkStorage::new(height, min_y),
                 entities_by_chunk: HashMap::new(),
                 entity_by_id: IntMap::default(),
+                biome_names: HashMap::new(),
+                world_border: WorldBorder::default(),
+                time: WorldTime::default(),
             }));
             self.worlds.insert(name, Arc::downgrade(&world));
             world
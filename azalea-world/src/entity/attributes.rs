@@ -6,6 +6,7 @@ use std::{
 };
 
 use azalea_buf::{BufReadError, McBuf, McBufReadable, McBufWritable};
+use azalea_registry::Attribute;
 use bevy_ecs::component::Component;
 use thiserror::Error;
 use uuid::{uuid, Uuid};
@@ -13,6 +14,46 @@ use uuid::{uuid, Uuid};
 #[derive(Clone, Debug, Component)]
 pub struct Attributes {
     pub speed: AttributeInstance,
+    /// How many times per second the entity can attack at full strength.
+    pub attack_speed: AttributeInstance,
+    /// The attributes that azalea doesn't use itself, like max health and
+    /// armor. These are only present if the server sent them.
+    pub other: HashMap<Attribute, AttributeInstance>,
+}
+
+impl Attributes {
+    /// Get an attribute, or `None` if the entity doesn't have it.
+    pub fn get(&self, attribute: Attribute) -> Option<&AttributeInstance> {
+        match attribute {
+            Attribute::GenericMovementSpeed => Some(&self.speed),
+            Attribute::GenericAttackSpeed => Some(&self.attack_speed),
+            _ => self.other.get(&attribute),
+        }
+    }
+
+    /// Replace the base value and modifiers of an attribute, adding it if the
+    /// entity didn't have it yet. This is what happens when the server sends
+    /// an `UpdateAttributes` packet.
+    pub fn set(
+        &mut self,
+        attribute: Attribute,
+        base: f64,
+        modifiers: impl IntoIterator<Item = AttributeModifier>,
+    ) {
+        let instance = match attribute {
+            Attribute::GenericMovementSpeed => &mut self.speed,
+            Attribute::GenericAttackSpeed => &mut self.attack_speed,
+            _ => self
+                .other
+                .entry(attribute)
+                .or_insert_with(|| AttributeInstance::new(base)),
+        };
+        instance.base = base;
+        instance.modifiers_by_uuid = modifiers
+            .into_iter()
+            .map(|modifier| (modifier.uuid, modifier))
+            .collect();
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -33,21 +74,37 @@ impl AttributeInstance {
         }
     }
 
+    /// The value of the attribute after applying all the modifiers.
+    ///
+    /// Like vanilla, all the additions are applied first, then the base
+    /// multipliers, and then the total multipliers.
     pub fn calculate(&self) -> f64 {
-        let mut total = self.base;
-        for modifier in self.modifiers_by_uuid.values() {
-            match modifier.operation {
-                AttributeModifierOperation::Addition => total += modifier.amount,
-                AttributeModifierOperation::MultiplyBase => total += self.base * modifier.amount,
-                _ => {}
-            }
-            if let AttributeModifierOperation::MultiplyTotal = modifier.operation {
-                total *= 1.0 + modifier.amount;
-            }
+        let base = self.base
+            + self
+                .amounts(AttributeModifierOperation::Addition)
+                .sum::<f64>();
+        let mut total = base;
+        for amount in self.amounts(AttributeModifierOperation::MultiplyBase) {
+            total += base * amount;
+        }
+        for amount in self.amounts(AttributeModifierOperation::MultiplyTotal) {
+            total *= 1.0 + amount;
         }
         total
     }
 
+    fn amounts(&self, operation: AttributeModifierOperation) -> impl Iterator<Item = f64> + '_ {
+        self.modifiers_by_uuid
+            .values()
+            .filter(move |modifier| modifier.operation == operation)
+            .map(|modifier| modifier.amount)
+    }
+
+    /// The modifiers that are currently applied to this attribute.
+    pub fn modifiers(&self) -> impl Iterator<Item = &AttributeModifier> {
+        self.modifiers_by_uuid.values()
+    }
+
     /// Add a new modifier to this attribute.
     pub fn insert(&mut self, modifier: AttributeModifier) -> Result<(), AlreadyPresentError> {
         if self
@@ -76,7 +133,7 @@ pub struct AttributeModifier {
     pub operation: AttributeModifierOperation,
 }
 
-#[derive(Clone, Debug, Copy, McBuf)]
+#[derive(Clone, Debug, Copy, PartialEq, Eq, McBuf)]
 pub enum AttributeModifierOperation {
     Addition,
     MultiplyBase,
@@ -114,3 +171,64 @@ impl McBufWritable for AttributeModifier {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modifier(amount: f64, operation: AttributeModifierOperation) -> AttributeModifier {
+        AttributeModifier {
+            // the amounts are different in the tests so we can use them as the uuid
+            uuid: Uuid::from_u128(amount.to_bits() as u128),
+            name: "test".to_string(),
+            amount,
+            operation,
+        }
+    }
+
+    #[test]
+    fn test_calculate_order() {
+        let mut attribute = AttributeInstance::new(0.1);
+        // the multipliers are always applied after the additions, no matter what
+        // order they were added in
+        attribute
+            .insert(modifier(1., AttributeModifierOperation::MultiplyTotal))
+            .unwrap();
+        attribute
+            .insert(modifier(0.5, AttributeModifierOperation::MultiplyBase))
+            .unwrap();
+        attribute
+            .insert(modifier(0.1, AttributeModifierOperation::Addition))
+            .unwrap();
+        // ((0.1 + 0.1) + 0.2 * 0.5) * 2
+        assert!((attribute.calculate() - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_set_attribute() {
+        let mut attributes = Attributes {
+            speed: AttributeInstance::new(0.1),
+            attack_speed: AttributeInstance::new(4.),
+            other: HashMap::new(),
+        };
+        attributes.set(
+            Attribute::GenericMovementSpeed,
+            0.1,
+            [modifier(0.2, AttributeModifierOperation::MultiplyTotal)],
+        );
+        assert!((attributes.speed.calculate() - 0.12).abs() < 1e-9);
+        // setting it again replaces the old modifiers
+        attributes.set(Attribute::GenericMovementSpeed, 0.1, []);
+        assert_eq!(attributes.speed.calculate(), 0.1);
+
+        assert!(attributes.get(Attribute::GenericMaxHealth).is_none());
+        attributes.set(Attribute::GenericMaxHealth, 20., []);
+        assert_eq!(
+            attributes
+                .get(Attribute::GenericMaxHealth)
+                .unwrap()
+                .calculate(),
+            20.
+        );
+    }
+}
@@ -4,12 +4,13 @@
 use crate::{
     deduplicate_entities, deduplicate_local_entities,
     entity::{
-        self, add_dead, update_bounding_box, EntityUuid, MinecraftEntityId, Position, WorldName,
+        self, add_dead, tick_active_effects, update_bounding_box, EntityUuid, MinecraftEntityId,
+        Position, WorldName,
     },
     update_entity_by_id_index, update_uuid_index, InstanceContainer, PartialInstance,
 };
 use azalea_core::ChunkPos;
-use bevy_app::{App, CoreSet, Plugin};
+use bevy_app::{App, CoreSchedule, CoreSet, IntoSystemAppConfig, Plugin};
 use bevy_ecs::{
     component::Component,
     entity::Entity,
@@ -77,6 +78,7 @@ impl Plugin for EntityPlugin {
             update_bounding_box,
             clamp_look_direction,
         ))
+        .add_system(tick_active_effects.in_schedule(CoreSchedule::FixedUpdate))
         .init_resource::<EntityInfos>();
     }
 }
@@ -319,7 +321,6 @@ fn remove_despawned_entities_from_indexes(
         // and now remove the entity from the ecs
         commands.entity(entity).despawn();
         debug!("Despawned entity {entity:?} because it was not loaded by anything.");
-        return;
     }
 }
 
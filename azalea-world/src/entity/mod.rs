@@ -12,6 +12,7 @@ use self::{attributes::AttributeInstance, metadata::Health};
 pub use attributes::Attributes;
 use azalea_block::BlockState;
 use azalea_core::{BlockPos, ChunkPos, ResourceLocation, Vec3, AABB};
+use azalea_inventory::ItemSlot;
 use bevy_ecs::{
     bundle::Bundle,
     component::Component,
@@ -26,7 +27,7 @@ pub use info::{
     clamp_look_direction, EntityInfos, EntityPlugin, EntityUpdateSet, LoadedBy, PartialEntityInfos,
     RelativeEntityUpdate,
 };
-use std::fmt::Debug;
+use std::{collections::HashMap, f64::consts::PI, fmt::Debug};
 use uuid::Uuid;
 
 /// An entity ID used by Minecraft. These are not guaranteed to be unique in
@@ -84,6 +85,17 @@ pub fn view_vector(look_direction: &LookDirection) -> Vec3 {
     }
 }
 
+/// Return the (`y_rot`, `x_rot`) that would make a client at `current` be
+/// looking at `target`.
+pub fn direction_looking_at(current: &Vec3, target: &Vec3) -> (f32, f32) {
+    // borrowed from mineflayer's Bot.lookAt because i didn't want to do math
+    let delta = target - current;
+    let y_rot = (PI - f64::atan2(-delta.x, -delta.z)) * (180.0 / PI);
+    let ground_distance = f64::sqrt(delta.x * delta.x + delta.z * delta.z);
+    let x_rot = f64::atan2(delta.y, ground_distance) * -(180.0 / PI);
+    (y_rot as f32, x_rot as f32)
+}
+
 /// Get the position of the block below the entity, but a little lower.
 pub fn on_pos_legacy(chunk_storage: &ChunkStorage, position: &Position) -> BlockPos {
     on_pos(0.2, chunk_storage, position)
@@ -216,10 +228,18 @@ pub struct LookDirection {
     pub y_rot: f32,
 }
 
+/// A component that contains the direction an entity's head is facing, in
+/// degrees. This is the same as [`LookDirection::y_rot`] for players, but mobs
+/// can turn their head without turning their body.
+#[derive(Debug, Component, Clone, Copy, Default, PartialEq, Deref, DerefMut)]
+pub struct HeadRotation(pub f32);
+
 /// The physics data relating to the entity, such as position, velocity, and
 /// bounding box.
 #[derive(Debug, Component)]
 pub struct Physics {
+    /// The velocity of the entity, in blocks per tick. For entities that
+    /// aren't ours, this is the last velocity that the server sent.
     pub delta: Vec3,
 
     /// X acceleration.
@@ -231,6 +251,11 @@ pub struct Physics {
 
     pub on_ground: bool,
     pub last_on_ground: bool,
+    /// Whether the entity walked into a wall during the last tick.
+    pub horizontal_collision: bool,
+    /// Whether the entity's bounding box was touching water at the start of
+    /// the last tick.
+    pub in_water: bool,
 
     /// The width and height of the entity.
     pub dimensions: EntityDimensions,
@@ -241,6 +266,73 @@ pub struct Physics {
     pub has_impulse: bool,
 }
 
+/// The mob effects (like speed or haste) that an entity currently has, and
+/// their amplifiers.
+///
+/// Note that an amplifier of 0 means level 1 of the effect.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut)]
+pub struct ActiveEffects(pub HashMap<azalea_registry::MobEffect, MobEffectData>);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MobEffectData {
+    pub amplifier: u8,
+    /// How many ticks are left until the effect runs out. This goes down every
+    /// tick, and is [`Self::INFINITE_DURATION`] for effects that don't run
+    /// out.
+    pub duration_ticks: u32,
+}
+
+impl MobEffectData {
+    /// The duration that the server sends for effects that last forever, like
+    /// the ones from beacons.
+    pub const INFINITE_DURATION: u32 = u32::MAX;
+
+    pub fn is_infinite(&self) -> bool {
+        self.duration_ticks == Self::INFINITE_DURATION
+    }
+}
+
+impl ActiveEffects {
+    /// The amplifier of the effect, or `None` if we don't have it.
+    pub fn amplifier(&self, effect: azalea_registry::MobEffect) -> Option<u8> {
+        self.get(&effect).map(|data| data.amplifier)
+    }
+
+    /// Count down the durations of the effects. The effects aren't removed when
+    /// they run out, the server tells us when to do that.
+    pub fn tick(&mut self) {
+        for data in self.values_mut() {
+            if !data.is_infinite() {
+                data.duration_ticks = data.duration_ticks.saturating_sub(1);
+            }
+        }
+    }
+}
+
+/// A system that counts down the durations in [`ActiveEffects`].
+pub fn tick_active_effects(mut query: Query<&mut ActiveEffects>) {
+    for mut active_effects in &mut query {
+        // don't trigger change detection if there's nothing to tick
+        if !active_effects.is_empty() {
+            active_effects.tick();
+        }
+    }
+}
+
+/// The items that an entity is holding and wearing.
+///
+/// The server doesn't send this for our own player, so use the inventory for
+/// that instead.
+#[derive(Component, Clone, Debug, Default)]
+pub struct Equipment {
+    pub main_hand: ItemSlot,
+    pub off_hand: ItemSlot,
+    pub head: ItemSlot,
+    pub chest: ItemSlot,
+    pub legs: ItemSlot,
+    pub feet: ItemSlot,
+}
+
 /// Marker component for entities that are dead.
 ///
 /// "Dead" means that the entity has 0 health.
@@ -308,8 +400,11 @@ pub struct EntityBundle {
     pub last_sent_position: LastSentPosition,
     pub physics: Physics,
     pub direction: LookDirection,
+    pub head_rotation: HeadRotation,
     pub eye_height: EyeHeight,
     pub attributes: Attributes,
+    pub active_effects: ActiveEffects,
+    pub equipment: Equipment,
     pub jumping: Jumping,
 }
 
@@ -342,6 +437,8 @@ impl EntityBundle {
 
                 on_ground: false,
                 last_on_ground: false,
+                horizontal_collision: false,
+                in_water: false,
 
                 // TODO: have this be based on the entity type
                 bounding_box: dimensions.make_bounding_box(&pos),
@@ -351,12 +448,17 @@ impl EntityBundle {
             },
             eye_height: EyeHeight(eye_height),
             direction: LookDirection::default(),
+            head_rotation: HeadRotation::default(),
 
             attributes: Attributes {
                 // TODO: do the correct defaults for everything, some
                 // entities have different defaults
                 speed: AttributeInstance::new(0.1),
+                attack_speed: AttributeInstance::new(4.0),
+                other: HashMap::new(),
             },
+            active_effects: ActiveEffects::default(),
+            equipment: Equipment::default(),
 
             jumping: Jumping(false),
         }
@@ -4,12 +4,16 @@
 #![feature(provide_any)]
 
 mod bit_storage;
+pub mod block_entity;
 mod chunk_storage;
 mod container;
 pub mod entity;
 pub mod iterators;
+pub mod light;
 pub mod palette;
+pub mod time;
 mod world;
+pub mod world_border;
 
 use std::backtrace::Backtrace;
 
@@ -17,7 +21,9 @@ pub use bit_storage::BitStorage;
 pub use chunk_storage::{Chunk, ChunkStorage, PartialChunkStorage, Section};
 pub use container::*;
 use thiserror::Error;
+pub use time::WorldTime;
 pub use world::*;
+pub use world_border::WorldBorder;
 
 #[derive(Error, Debug)]
 pub enum MoveEntityError {
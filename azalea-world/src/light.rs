@@ -0,0 +1,234 @@
+//! Block light and sky light, as sent by the server.
+//!
+//! Light isn't recalculated client-side, so after a block changes the light
+//! levels around it are only correct once the server sends a `LightUpdate`
+//! packet (which it does right after the block update).
+
+use std::fmt::Debug;
+
+use azalea_core::{BitSet, ChunkBlockPos};
+
+/// The number of bytes in a [`DataLayer`], which is 4 bits for each of the
+/// 4096 blocks in a section.
+pub const DATA_LAYER_SIZE: usize = 2048;
+
+/// The light levels for every block in a 16x16x16 section, stored as 4 bits
+/// per block.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DataLayer {
+    data: Box<[u8; DATA_LAYER_SIZE]>,
+}
+
+impl DataLayer {
+    /// Make a data layer where every block has the same light level.
+    pub fn filled(level: u8) -> Self {
+        let level = level & 0xf;
+        Self {
+            data: Box::new([level | (level << 4); DATA_LAYER_SIZE]),
+        }
+    }
+
+    /// Make a data layer from the bytes sent by the server. Returns `None` if
+    /// there isn't exactly [`DATA_LAYER_SIZE`] bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            data: Box::new(bytes.try_into().ok()?),
+        })
+    }
+
+    fn index(x: u8, y: u8, z: u8) -> usize {
+        ((y as usize & 0xf) << 8) | ((z as usize & 0xf) << 4) | (x as usize & 0xf)
+    }
+
+    /// Get the light level at the given coordinates in the section.
+    pub fn get(&self, x: u8, y: u8, z: u8) -> u8 {
+        let index = Self::index(x, y, z);
+        (self.data[index >> 1] >> ((index & 1) * 4)) & 0xf
+    }
+
+    /// Set the light level at the given coordinates in the section.
+    pub fn set(&mut self, x: u8, y: u8, z: u8, level: u8) {
+        let index = Self::index(x, y, z);
+        let shift = (index & 1) * 4;
+        let byte = &mut self.data[index >> 1];
+        *byte = (*byte & !(0xf << shift)) | ((level & 0xf) << shift);
+    }
+}
+
+impl Debug for DataLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DataLayer").finish_non_exhaustive()
+    }
+}
+
+/// The light data for a chunk.
+///
+/// There's one more layer below and above the sections in the chunk, since
+/// light can spread into the chunk from there. This means the layer at index
+/// 0 is for the section below the bottom of the world. Layers are `None` if
+/// the server didn't send any data for them.
+#[derive(Clone, Debug, Default)]
+pub struct ChunkLight {
+    pub sky: Vec<Option<DataLayer>>,
+    pub block: Vec<Option<DataLayer>>,
+}
+
+impl ChunkLight {
+    /// Update the sky light from the data in a `LightUpdate` or
+    /// `LevelChunkWithLight` packet.
+    pub fn update_sky_light(
+        &mut self,
+        y_mask: &BitSet,
+        empty_y_mask: &BitSet,
+        updates: &[Vec<u8>],
+    ) {
+        update_layers(&mut self.sky, y_mask, empty_y_mask, updates);
+    }
+
+    /// Update the block light from the data in a `LightUpdate` or
+    /// `LevelChunkWithLight` packet.
+    pub fn update_block_light(
+        &mut self,
+        y_mask: &BitSet,
+        empty_y_mask: &BitSet,
+        updates: &[Vec<u8>],
+    ) {
+        update_layers(&mut self.block, y_mask, empty_y_mask, updates);
+    }
+
+    /// A rough estimate of how many bytes the light data is using on the heap.
+    pub fn estimated_heap_usage(&self) -> usize {
+        let layers = self.sky.iter().chain(&self.block);
+        (self.sky.capacity() + self.block.capacity()) * std::mem::size_of::<Option<DataLayer>>()
+            + layers.flatten().count() * DATA_LAYER_SIZE
+    }
+
+    /// Get the block light level at a position in the chunk, from 0 to 15.
+    pub fn get_block_light(&self, pos: &ChunkBlockPos, min_y: i32) -> u8 {
+        let Some(layer_index) = layer_index(pos.y, min_y) else {
+            return 0;
+        };
+        match self.block.get(layer_index) {
+            Some(Some(layer)) => layer.get(pos.x, (pos.y & 0xf) as u8, pos.z),
+            _ => 0,
+        }
+    }
+
+    /// Get the sky light level at a position in the chunk, from 0 to 15.
+    ///
+    /// Like vanilla, sections without data take their light from the bottom
+    /// of the nearest section above them that has data, or are fully lit if
+    /// there isn't one. If the chunk doesn't have any sky light data at all
+    /// (like in the nether), this is always 0.
+    pub fn get_sky_light(&self, pos: &ChunkBlockPos, min_y: i32) -> u8 {
+        if self.sky.iter().all(Option::is_none) {
+            return 0;
+        }
+        let Some(layer_index) = layer_index(pos.y, min_y) else {
+            return 0;
+        };
+        if let Some(Some(layer)) = self.sky.get(layer_index) {
+            return layer.get(pos.x, (pos.y & 0xf) as u8, pos.z);
+        }
+        self.sky
+            .iter()
+            .skip(layer_index + 1)
+            .flatten()
+            .next()
+            .map_or(15, |layer| layer.get(pos.x, 0, pos.z))
+    }
+}
+
+/// The index of the light layer that a y coordinate is in.
+fn layer_index(y: i32, min_y: i32) -> Option<usize> {
+    let index = y.div_floor(16) - min_y.div_floor(16) + 1;
+    usize::try_from(index).ok()
+}
+
+fn update_layers(
+    layers: &mut Vec<Option<DataLayer>>,
+    y_mask: &BitSet,
+    empty_y_mask: &BitSet,
+    updates: &[Vec<u8>],
+) {
+    let mut updates = updates.iter();
+    for index in 0..y_mask.len().max(empty_y_mask.len()) {
+        let layer = if y_mask.index(index) {
+            let Some(bytes) = updates.next() else {
+                break;
+            };
+            DataLayer::from_bytes(bytes)
+        } else if empty_y_mask.index(index) {
+            Some(DataLayer::filled(0))
+        } else {
+            continue;
+        };
+        if layers.len() <= index {
+            layers.resize(index + 1, None);
+        }
+        layers[index] = layer;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mask(indexes: &[usize]) -> BitSet {
+        let mut mask = BitSet::new(64);
+        for &index in indexes {
+            mask.set(index);
+        }
+        mask
+    }
+
+    #[test]
+    fn test_data_layer() {
+        let mut layer = DataLayer::filled(0);
+        layer.set(1, 2, 3, 14);
+        layer.set(0, 2, 3, 7);
+        assert_eq!(layer.get(1, 2, 3), 14);
+        assert_eq!(layer.get(0, 2, 3), 7);
+        assert_eq!(layer.get(2, 2, 3), 0);
+        assert!(DataLayer::from_bytes(&[0; 10]).is_none());
+    }
+
+    #[test]
+    fn test_chunk_light() {
+        let mut light = ChunkLight::default();
+        let min_y = -64;
+
+        let mut block_layer = DataLayer::filled(0);
+        block_layer.set(4, 5, 6, 13);
+        // layer 1 is the bottom section of the world
+        light.update_block_light(&mask(&[1]), &BitSet::new(64), &[block_layer.data.to_vec()]);
+        assert_eq!(
+            light.get_block_light(&ChunkBlockPos::new(4, -64 + 5, 6), min_y),
+            13
+        );
+        assert_eq!(
+            light.get_block_light(&ChunkBlockPos::new(4, 0, 6), min_y),
+            0
+        );
+
+        assert_eq!(light.get_sky_light(&ChunkBlockPos::new(0, 0, 0), min_y), 0);
+        let mut sky_layer = DataLayer::filled(15);
+        sky_layer.set(3, 0, 3, 8);
+        light.update_sky_light(&mask(&[6]), &mask(&[2]), &[sky_layer.data.to_vec()]);
+        // inside the section that was sent
+        assert_eq!(light.get_sky_light(&ChunkBlockPos::new(3, 16, 3), min_y), 8);
+        // the section below has no data, so it uses the bottom of the one above
+        assert_eq!(light.get_sky_light(&ChunkBlockPos::new(3, 5, 3), min_y), 8);
+        assert_eq!(light.get_sky_light(&ChunkBlockPos::new(2, 5, 3), min_y), 15);
+        // empty sections are dark
+        assert_eq!(
+            light.get_sky_light(&ChunkBlockPos::new(2, -40, 3), min_y),
+            0
+        );
+        // sections above all the data are fully lit
+        assert_eq!(
+            light.get_sky_light(&ChunkBlockPos::new(2, 200, 3), min_y),
+            15
+        );
+    }
+}
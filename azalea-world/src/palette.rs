@@ -61,6 +61,17 @@ impl PalettedContainer {
         })
     }
 
+    /// A rough estimate of how many bytes this container is using on the heap.
+    pub fn estimated_heap_usage(&self) -> usize {
+        let palette = match &self.palette {
+            Palette::SingleValue(_) | Palette::Global => 0,
+            Palette::Linear(values) | Palette::Hashmap(values) => {
+                values.capacity() * std::mem::size_of::<u32>()
+            }
+        };
+        palette + self.storage.data.capacity() * std::mem::size_of::<u64>()
+    }
+
     /// Calculates the index of the given coordinates.
     pub fn index_from_coords(&self, x: usize, y: usize, z: usize) -> usize {
         let size_bits = self.container_type.size_bits();
@@ -102,8 +113,10 @@ impl PalettedContainer {
     /// Sets the id at the given coordinates and return the previous id
     pub fn get_and_set(&mut self, x: usize, y: usize, z: usize, value: u32) -> u32 {
         let paletted_value = self.id_for(value);
-        self.storage
-            .get_and_set(self.index_from_coords(x, y, z), paletted_value as u64) as u32
+        let previous_paletted_value = self
+            .storage
+            .get_and_set(self.index_from_coords(x, y, z), paletted_value as u64);
+        self.palette.value_for(previous_paletted_value as usize)
     }
 
     /// Sets the id at the given index and return the previous id. You probably
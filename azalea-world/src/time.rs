@@ -0,0 +1,95 @@
+//! The time of day in an [`Instance`](crate::Instance).
+
+/// The number of ticks in a Minecraft day.
+pub const TICKS_PER_DAY: u64 = 24000;
+
+/// The time in an [`Instance`](crate::Instance), which the server sends every
+/// second and which we advance every tick in between.
+#[derive(Clone, Debug)]
+pub struct WorldTime {
+    /// The number of ticks since the world was created. This always goes up by
+    /// one every tick.
+    pub game_time: u64,
+    /// The number of ticks since the first sunrise, which can be changed with
+    /// the `/time` command. Use [`Self::time_of_day`] if you want the time
+    /// within the current day.
+    pub day_time: u64,
+    /// Whether the time of day changes by itself. This is false if the
+    /// `doDaylightCycle` gamerule is off.
+    pub daylight_cycle: bool,
+}
+
+impl Default for WorldTime {
+    fn default() -> Self {
+        Self {
+            game_time: 0,
+            day_time: 0,
+            daylight_cycle: true,
+        }
+    }
+}
+
+impl WorldTime {
+    /// Update the time from a `SetTime` packet. The server sends a negative
+    /// day time when the daylight cycle is off.
+    pub fn update(&mut self, game_time: u64, day_time: i64) {
+        self.game_time = game_time;
+        self.day_time = day_time.unsigned_abs();
+        self.daylight_cycle = day_time >= 0;
+    }
+
+    /// Advance the time by one tick.
+    pub fn tick(&mut self) {
+        self.game_time += 1;
+        if self.daylight_cycle {
+            self.day_time += 1;
+        }
+    }
+
+    /// The number of ticks since the start of the current day, from 0 to
+    /// 23999. 0 is sunrise, 6000 is noon, 12000 is sunset and 18000 is
+    /// midnight.
+    pub fn time_of_day(&self) -> u64 {
+        self.day_time % TICKS_PER_DAY
+    }
+
+    /// The number of days that have passed.
+    pub fn day(&self) -> u64 {
+        self.day_time / TICKS_PER_DAY
+    }
+
+    /// The phase of the moon, from 0 (full moon) to 7.
+    pub fn moon_phase(&self) -> u64 {
+        self.day() % 8
+    }
+
+    /// Whether it's late enough that players can sleep in beds, assuming the
+    /// weather is clear. Beds can also be used during thunderstorms.
+    pub fn is_night(&self) -> bool {
+        (12542..=23459).contains(&self.time_of_day())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_world_time() {
+        let mut time = WorldTime::default();
+        time.update(100, 3 * TICKS_PER_DAY as i64 + 12541);
+        assert_eq!(time.day(), 3);
+        assert_eq!(time.moon_phase(), 3);
+        assert!(!time.is_night());
+        time.tick();
+        assert!(time.is_night());
+        assert_eq!(time.game_time, 101);
+
+        // the daylight cycle is off, so only the game time goes up
+        time.update(200, -13000);
+        time.tick();
+        assert_eq!(time.game_time, 201);
+        assert_eq!(time.day_time, 13000);
+        assert!(!time.daylight_cycle);
+    }
+}
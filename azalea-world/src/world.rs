@@ -4,10 +4,10 @@ use crate::{
     },
     iterators::ChunkIterator,
     palette::Palette,
-    ChunkStorage, InstanceContainer, PartialChunkStorage,
+    ChunkStorage, InstanceContainer, PartialChunkStorage, WorldBorder, WorldTime,
 };
 use azalea_block::{BlockState, BlockStates};
-use azalea_core::{BlockPos, ChunkPos};
+use azalea_core::{BlockPos, ChunkBlockPos, ChunkPos, ResourceLocation};
 use bevy_ecs::{
     entity::Entity,
     query::{Changed, With, Without},
@@ -183,6 +183,16 @@ pub struct Instance {
 
     /// An index of Minecraft entity IDs to Azalea ECS entities.
     pub entity_by_id: IntMap<MinecraftEntityId, Entity>,
+
+    /// The names of the biomes that the server told us about, indexed by the
+    /// IDs that chunks use for them.
+    pub biome_names: HashMap<u32, ResourceLocation>,
+
+    /// The world border, which entities can't move past.
+    pub world_border: WorldBorder,
+
+    /// The time of day, which we advance every tick.
+    pub time: WorldTime,
 }
 
 impl Instance {
@@ -191,6 +201,53 @@ impl Instance {
         self.entity_by_id.get(entity_id).copied()
     }
 
+    /// Get the block light level at a position, from 0 to 15. This is the light
+    /// from things like torches. Returns `None` if the chunk isn't loaded.
+    pub fn get_block_light(&self, pos: &BlockPos) -> Option<u8> {
+        let chunk = self.chunks.get(&ChunkPos::from(pos))?;
+        let chunk = chunk.read();
+        Some(
+            chunk
+                .light
+                .get_block_light(&ChunkBlockPos::from(pos), self.chunks.min_y),
+        )
+    }
+
+    /// Get the sky light level at a position, from 0 to 15. This doesn't
+    /// depend on the time of day. Returns `None` if the chunk isn't loaded.
+    pub fn get_sky_light(&self, pos: &BlockPos) -> Option<u8> {
+        let chunk = self.chunks.get(&ChunkPos::from(pos))?;
+        let chunk = chunk.read();
+        Some(
+            chunk
+                .light
+                .get_sky_light(&ChunkBlockPos::from(pos), self.chunks.min_y),
+        )
+    }
+
+    /// Get the ID of the biome at a position. Biomes are stored in 4x4x4
+    /// cells, so this is the same for every position in a cell. Returns `None`
+    /// if the chunk isn't loaded.
+    pub fn get_biome_id(&self, pos: &BlockPos) -> Option<u32> {
+        let chunk = self.chunks.get(&ChunkPos::from(pos))?;
+        let chunk = chunk.read();
+        chunk.get_biome(&ChunkBlockPos::from(pos), self.chunks.min_y)
+    }
+
+    /// Get the name of the biome at a position, like `minecraft:plains`.
+    /// Returns `None` if the chunk isn't loaded or the server didn't tell us
+    /// about the biome.
+    ///
+    /// ```
+    /// # fn example(client: &azalea_client::Client) {
+    /// let biome = client.world().read().get_biome(&client.position().into());
+    /// # }
+    /// ```
+    pub fn get_biome(&self, pos: &BlockPos) -> Option<ResourceLocation> {
+        let id = self.get_biome_id(pos)?;
+        self.biome_names.get(&id).cloned()
+    }
+
     /// Find the coordinates of a block in the world.
     ///
     /// Note that this is sorted by `x+y+z` and not `x^2+y^2+z^2`, for
@@ -323,6 +380,9 @@ impl From<ChunkStorage> for Instance {
             chunks,
             entities_by_chunk: HashMap::new(),
             entity_by_id: IntMap::default(),
+            biome_names: HashMap::new(),
+            world_border: WorldBorder::default(),
+            time: WorldTime::default(),
         }
     }
 }
@@ -0,0 +1,229 @@
+//! The world border, which is the square at the edge of the world that
+//! entities can't go past.
+
+use std::time::{Duration, Instant};
+
+use azalea_core::BlockPos;
+
+/// The largest size that a world border can be, in blocks.
+pub const MAX_SIZE: f64 = 59_999_968.;
+
+/// The default value for [`WorldBorder::absolute_max_size`].
+pub const DEFAULT_ABSOLUTE_MAX_SIZE: u32 = 29_999_984;
+
+/// The world border of an [`Instance`](crate::Instance).
+///
+/// The server can make the border grow or shrink over time, so the size of
+/// the border depends on when you check it.
+#[derive(Clone, Debug)]
+pub struct WorldBorder {
+    pub center_x: f64,
+    pub center_z: f64,
+    pub extent: BorderExtent,
+    /// The border can't go further than this many blocks from 0,0, no matter
+    /// what its size is.
+    pub absolute_max_size: u32,
+    /// How many blocks away from the border the screen starts turning red.
+    pub warning_blocks: u32,
+    /// If the border is shrinking, the screen turns red this many seconds
+    /// before the border would reach the player.
+    pub warning_time: u32,
+}
+
+/// The size of a [`WorldBorder`], which is either staying the same or moving
+/// from one size to another.
+#[derive(Clone, Debug)]
+pub enum BorderExtent {
+    Static {
+        size: f64,
+    },
+    Moving {
+        from: f64,
+        to: f64,
+        start: Instant,
+        duration: Duration,
+    },
+}
+
+impl Default for WorldBorder {
+    fn default() -> Self {
+        Self {
+            center_x: 0.,
+            center_z: 0.,
+            extent: BorderExtent::Static { size: MAX_SIZE },
+            absolute_max_size: DEFAULT_ABSOLUTE_MAX_SIZE,
+            warning_blocks: 5,
+            warning_time: 15,
+        }
+    }
+}
+
+impl WorldBorder {
+    /// The length of a side of the border right now, in blocks.
+    pub fn size(&self) -> f64 {
+        self.size_at(Instant::now())
+    }
+
+    /// The length of a side of the border at the given time, in blocks.
+    pub fn size_at(&self, now: Instant) -> f64 {
+        match self.extent {
+            BorderExtent::Static { size } => size,
+            BorderExtent::Moving {
+                from,
+                to,
+                start,
+                duration,
+            } => {
+                let progress =
+                    now.saturating_duration_since(start).as_secs_f64() / duration.as_secs_f64();
+                if progress < 1. {
+                    from + (to - from) * progress
+                } else {
+                    to
+                }
+            }
+        }
+    }
+
+    /// Whether the border is currently growing or shrinking.
+    pub fn is_moving(&self) -> bool {
+        match self.extent {
+            BorderExtent::Static { .. } => false,
+            BorderExtent::Moving {
+                start, duration, ..
+            } => start.elapsed() < duration,
+        }
+    }
+
+    /// Immediately change the size of the border.
+    pub fn set_size(&mut self, size: f64) {
+        self.extent = BorderExtent::Static { size };
+    }
+
+    /// Make the border start moving from one size to another, which will take
+    /// `duration`.
+    pub fn lerp_size_between(&mut self, from: f64, to: f64, duration: Duration) {
+        self.extent = if from == to || duration.is_zero() {
+            BorderExtent::Static { size: to }
+        } else {
+            BorderExtent::Moving {
+                from,
+                to,
+                start: Instant::now(),
+                duration,
+            }
+        };
+    }
+
+    /// The edges of the border right now.
+    pub fn bounds(&self) -> BorderBounds {
+        self.bounds_at(Instant::now())
+    }
+
+    /// The edges of the border at the given time.
+    pub fn bounds_at(&self, now: Instant) -> BorderBounds {
+        let half_size = self.size_at(now) / 2.;
+        let absolute_max_size = self.absolute_max_size as f64;
+        let clamp = |coordinate: f64| coordinate.clamp(-absolute_max_size, absolute_max_size);
+        BorderBounds {
+            min_x: clamp(self.center_x - half_size),
+            max_x: clamp(self.center_x + half_size),
+            min_z: clamp(self.center_z - half_size),
+            max_z: clamp(self.center_z + half_size),
+        }
+    }
+
+    /// Whether the block at the given position is inside the border right now.
+    pub fn is_within_bounds(&self, pos: &BlockPos) -> bool {
+        self.bounds().is_within_bounds(pos)
+    }
+}
+
+/// The edges of a [`WorldBorder`] at a certain time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BorderBounds {
+    pub min_x: f64,
+    pub max_x: f64,
+    pub min_z: f64,
+    pub max_z: f64,
+}
+
+impl BorderBounds {
+    /// Whether any part of the block at the given position is inside the
+    /// border.
+    pub fn is_within_bounds(&self, pos: &BlockPos) -> bool {
+        (pos.x + 1) as f64 > self.min_x
+            && (pos.x as f64) < self.max_x
+            && (pos.z + 1) as f64 > self.min_z
+            && (pos.z as f64) < self.max_z
+    }
+
+    /// Whether the given point is inside the border, or at most `margin`
+    /// blocks outside of it.
+    pub fn contains(&self, x: f64, z: f64, margin: f64) -> bool {
+        x >= self.min_x - margin
+            && x < self.max_x + margin
+            && z >= self.min_z - margin
+            && z < self.max_z + margin
+    }
+
+    /// The distance from the given point to the nearest edge of the border.
+    /// This is negative if the point is outside the border.
+    pub fn distance_to(&self, x: f64, z: f64) -> f64 {
+        (x - self.min_x)
+            .min(self.max_x - x)
+            .min(z - self.min_z)
+            .min(self.max_z - z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut border = WorldBorder {
+            center_x: 10.,
+            center_z: -5.,
+            ..Default::default()
+        };
+        border.set_size(20.);
+        let bounds = border.bounds();
+        assert_eq!(
+            bounds,
+            BorderBounds {
+                min_x: 0.,
+                max_x: 20.,
+                min_z: -15.,
+                max_z: 5.,
+            }
+        );
+        assert!(bounds.is_within_bounds(&BlockPos::new(0, 64, 4)));
+        assert!(!bounds.is_within_bounds(&BlockPos::new(-1, 64, 0)));
+        assert!(!bounds.is_within_bounds(&BlockPos::new(20, 64, 0)));
+        assert_eq!(bounds.distance_to(2., 0.), 2.);
+        assert_eq!(bounds.distance_to(-1., 0.), -1.);
+
+        // the border can't go past the absolute max size
+        border.absolute_max_size = 8;
+        assert_eq!(border.bounds().max_x, 8.);
+    }
+
+    #[test]
+    fn test_lerp_size() {
+        let mut border = WorldBorder::default();
+        border.lerp_size_between(100., 50., Duration::from_secs(10));
+        assert!(border.is_moving());
+        let BorderExtent::Moving { start, .. } = border.extent else {
+            panic!("border should be moving");
+        };
+        assert_eq!(border.size_at(start), 100.);
+        assert_eq!(border.size_at(start + Duration::from_secs(5)), 75.);
+        assert_eq!(border.size_at(start + Duration::from_secs(20)), 50.);
+
+        border.lerp_size_between(50., 50., Duration::from_secs(10));
+        assert!(!border.is_moving());
+        assert_eq!(border.size(), 50.);
+    }
+}
@@ -7,7 +7,7 @@ use azalea::entity::metadata::Player;
 use azalea::entity::{EyeHeight, Position};
 use azalea::interact::HitResultComponent;
 use azalea::inventory::ItemSlot;
-use azalea::pathfinder::BlockPosGoal;
+use azalea::pathfinder::goals::BlockPosGoal;
 use azalea::protocol::packets::game::ClientboundGamePacket;
 use azalea::{prelude::*, swarm::prelude::*, BlockPos, GameProfileComponent, WalkDirection};
 use azalea::{Account, Client, Event};
@@ -120,7 +120,7 @@ async fn handle(mut bot: Client, event: Event, _state: State) -> anyhow::Result<
                         let entity_pos = bot.entity_component::<Position>(entity);
                         let target_pos: BlockPos = entity_pos.into();
                         println!("going to {target_pos:?}");
-                        bot.goto(BlockPosGoal::from(target_pos));
+                        bot.start_goto(BlockPosGoal::from(target_pos));
                     }
                     "look" => {
                         let entity_pos = bot
@@ -159,7 +159,7 @@ async fn handle(mut bot: Client, event: Event, _state: State) -> anyhow::Result<
                             .find_block(bot.position(), &azalea::Block::DiamondBlock.into());
                         if let Some(target_pos) = target_pos {
                             // +1 to stand on top of the block
-                            bot.goto(BlockPosGoal::from(target_pos.up(1)));
+                            bot.start_goto(BlockPosGoal::from(target_pos.up(1)));
                         } else {
                             bot.chat("no diamond block found");
                         }
@@ -173,7 +173,10 @@ async fn handle(mut bot: Client, event: Event, _state: State) -> anyhow::Result<
                             bot.chat("no lever found");
                             return Ok(())
                         };
-                        bot.goto(BlockPosGoal::from(target_pos));
+                        if let Err(e) = bot.goto(BlockPosGoal::from(target_pos)).await {
+                            bot.chat(&format!("couldn't get to the lever: {e}"));
+                            return Ok(());
+                        }
                         bot.look_at(target_pos.center());
                         bot.block_interact(target_pos);
                     }
@@ -45,16 +45,9 @@ async fn handle(bot: Client, event: Event, state: State) -> anyhow::Result<()> {
                     .await;
                 chest.close().await;
 
-                let crafting_table = bot
-                    .open_crafting_table(&bot.world.find_block(azalea::Block::CraftingTable))
-                    .await
-                    .unwrap();
-                bot.craft(&crafting_table, &bot.recipe_for("minecraft:sticks"))
-                    .await?;
-                let pickaxe = bot
-                    .craft(&crafting_table, &bot.recipe_for("minecraft:wooden_pickaxe"))
-                    .await?;
-                crafting_table.close().await;
+                bot.craft("minecraft:stick", 1).await?;
+                bot.craft("minecraft:wooden_pickaxe", 1).await?;
+                let pickaxe = azalea::Item::WoodenPickaxe;
 
                 bot.hold(&pickaxe);
 
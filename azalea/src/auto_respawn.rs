@@ -1,14 +1,16 @@
 use crate::app::{App, Plugin};
-use azalea_client::packet_handling::DeathEvent;
+use azalea_client::packet_handling::{DeathEvent, WinGameEvent};
 use azalea_client::respawn::{perform_respawn, PerformRespawnEvent};
 use bevy_ecs::prelude::*;
 
-/// A plugin that makes [`DeathEvent`]s send [`PerformRespawnEvent`]s.
+/// A plugin that makes [`DeathEvent`]s and [`WinGameEvent`]s send
+/// [`PerformRespawnEvent`]s, so we respawn after dying or after the credits.
 #[derive(Clone, Default)]
 pub struct AutoRespawnPlugin;
 impl Plugin for AutoRespawnPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(auto_respawn.before(perform_respawn));
+        app.add_system(auto_respawn.before(perform_respawn))
+            .add_system(respawn_after_credits.before(perform_respawn));
     }
 }
 
@@ -22,3 +24,16 @@ fn auto_respawn(
         });
     }
 }
+
+// the vanilla client respawns once the credits are closed, but nobody's
+// watching them so we can skip straight to respawning
+fn respawn_after_credits(
+    mut events: EventReader<WinGameEvent>,
+    mut perform_respawn_events: EventWriter<PerformRespawnEvent>,
+) {
+    for event in events.iter() {
+        perform_respawn_events.send(PerformRespawnEvent {
+            entity: event.entity,
+        });
+    }
+}
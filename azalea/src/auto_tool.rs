@@ -0,0 +1,271 @@
+//! Pick the best tool for mining a block, or the best weapon for attacking.
+
+use std::ops::RangeInclusive;
+
+use azalea_block::{Block, BlockState};
+use azalea_client::{
+    inventory::{ContainerClickEvent, InventoryComponent},
+    mining::{destroy_progress, is_correct_tool_for_drops, MiningModifiers},
+    Client,
+};
+use azalea_inventory::{item::ToolExt, operations::SwapClick, ItemSlot, Menu};
+use azalea_registry::Enchantment;
+
+pub trait AutoToolClientExt {
+    fn best_tool_for(&self, block_state: BlockState) -> Option<usize>;
+    fn best_weapon(&self) -> Option<usize>;
+    fn hold_best_tool_for(&self, block_state: BlockState, move_to_hotbar: bool) -> bool;
+    fn hold_best_weapon(&self, move_to_hotbar: bool) -> bool;
+    fn hold_item_in_slot(&self, slot: usize) -> bool;
+}
+
+impl AutoToolClientExt for Client {
+    /// The slot in our inventory (including the hotbar) with the item that
+    /// mines the block the fastest, as a protocol index in the current menu.
+    /// Returns `None` if nothing is better than an empty hand.
+    fn best_tool_for(&self, block_state: BlockState) -> Option<usize> {
+        let menu = self.menu();
+        best_tool_in_slots(&menu, menu.player_slots_range(), block_state)
+    }
+
+    /// The slot in our inventory (including the hotbar) with the item that
+    /// does the most damage over time, as a protocol index in the current
+    /// menu. Returns `None` if nothing is better than an empty hand.
+    fn best_weapon(&self) -> Option<usize> {
+        let menu = self.menu();
+        best_weapon_in_slots(&menu, menu.player_slots_range())
+    }
+
+    /// Hold the best tool for mining the block. If `move_to_hotbar` is true
+    /// then tools in the main part of the inventory are moved into the
+    /// selected hotbar slot, otherwise only the hotbar is searched. Returns
+    /// whether we found a tool to hold.
+    ///
+    /// ```
+    /// # use azalea::prelude::*;
+    /// # fn example(bot: azalea::Client, pos: azalea::BlockPos) {
+    /// let block_state = bot
+    ///     .world()
+    ///     .read()
+    ///     .chunks
+    ///     .get_block_state(&pos)
+    ///     .unwrap_or_default();
+    /// bot.hold_best_tool_for(block_state, true);
+    /// bot.start_mining_block(pos);
+    /// # }
+    /// ```
+    fn hold_best_tool_for(&self, block_state: BlockState, move_to_hotbar: bool) -> bool {
+        let menu = self.menu();
+        let slots = searched_slots(&menu, move_to_hotbar);
+        best_tool_in_slots(&menu, slots, block_state)
+            .map_or(false, |slot| self.hold_item_in_slot(slot))
+    }
+
+    /// Hold the best weapon. If `move_to_hotbar` is true then weapons in the
+    /// main part of the inventory are moved into the selected hotbar slot,
+    /// otherwise only the hotbar is searched. Returns whether we found a
+    /// weapon to hold.
+    fn hold_best_weapon(&self, move_to_hotbar: bool) -> bool {
+        let menu = self.menu();
+        let slots = searched_slots(&menu, move_to_hotbar);
+        best_weapon_in_slots(&menu, slots).map_or(false, |slot| self.hold_item_in_slot(slot))
+    }
+
+    /// Hold the item in the given slot of the current menu. Hotbar slots are
+    /// selected, and other slots in our inventory are swapped into the
+    /// selected hotbar slot. Returns false if the slot isn't in our inventory.
+    ///
+    /// The server has to send us the new inventory before
+    /// [`InventoryComponent::held_item`] changes when the item is swapped.
+    fn hold_item_in_slot(&self, slot: usize) -> bool {
+        let mut ecs = self.ecs.lock();
+        let inventory = self.query::<&InventoryComponent>(&mut ecs);
+        let menu = inventory.menu();
+        if menu.is_hotbar_slot(slot) {
+            let hotbar_slot = (slot - menu.hotbar_slots_range().start()) as u8;
+            drop(ecs);
+            self.set_selected_hotbar_slot(hotbar_slot);
+            return true;
+        }
+        if !menu.player_slots_range().contains(&slot) {
+            return false;
+        }
+
+        let window_id = inventory.id;
+        let target_slot = inventory.selected_hotbar_slot;
+        ecs.send_event(ContainerClickEvent {
+            entity: self.entity,
+            window_id,
+            operation: SwapClick {
+                source_slot: slot as u16,
+                target_slot,
+            }
+            .into(),
+        });
+        true
+    }
+}
+
+/// The slots that we look in when choosing an item to hold.
+fn searched_slots(menu: &Menu, move_to_hotbar: bool) -> RangeInclusive<usize> {
+    if move_to_hotbar {
+        menu.player_slots_range()
+    } else {
+        menu.hotbar_slots_range()
+    }
+}
+
+/// The slot with the item that mines the block the fastest, preferring items
+/// that make it drop something. Returns `None` if nothing in the slots is
+/// better than an empty hand.
+pub fn best_tool_in_slots(
+    menu: &Menu,
+    slots: RangeInclusive<usize>,
+    block_state: BlockState,
+) -> Option<usize> {
+    let block = Box::<dyn Block>::from(block_state);
+    let block = block.as_ref();
+    best_item_in_slots(menu, slots, |item| {
+        (
+            is_correct_tool_for_drops(block, item.kind()),
+            destroy_progress(block, item, &MiningModifiers::default()),
+        )
+    })
+}
+
+/// The slot with the item that does the most damage per second if we attack
+/// whenever our attack is fully charged. Returns `None` if nothing in the
+/// slots is better than an empty hand.
+pub fn best_weapon_in_slots(menu: &Menu, slots: RangeInclusive<usize>) -> Option<usize> {
+    best_item_in_slots(menu, slots, |item| {
+        let ItemSlot::Present(item) = item else {
+            return 4.;
+        };
+        let mut damage = 1. + item.kind.attack_damage();
+        let sharpness = item.enchantment_level(Enchantment::Sharpness);
+        if sharpness > 0 {
+            damage += 0.5 * sharpness as f32 + 0.5;
+        }
+        damage * item.kind.attack_speed()
+    })
+}
+
+/// The slot with the highest score, skipping items that are about to break.
+/// When items score the same, the one that doesn't break or has the most
+/// durability left is picked. Returns `None` if no item scores higher than an
+/// empty hand.
+fn best_item_in_slots<S: PartialOrd>(
+    menu: &Menu,
+    slots: RangeInclusive<usize>,
+    score: impl Fn(&ItemSlot) -> S,
+) -> Option<usize> {
+    let empty_hand_score = score(&ItemSlot::Empty);
+
+    let mut best: Option<(usize, S, u32)> = None;
+    for i in slots {
+        let Some(item @ ItemSlot::Present(item_data)) = menu.slot(i) else {
+            continue;
+        };
+        let durability = item_data.remaining_durability().unwrap_or(u32::MAX);
+        // using the item would break it
+        if durability <= 1 {
+            continue;
+        }
+        let item_score = score(item);
+        if item_score <= empty_hand_score {
+            continue;
+        }
+        let is_better = match &best {
+            None => true,
+            Some((_, best_score, best_durability)) => {
+                item_score > *best_score
+                    || (item_score == *best_score && durability > *best_durability)
+            }
+        };
+        if is_better {
+            best = Some((i, item_score, durability));
+        }
+    }
+    best.map(|(i, _, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azalea_inventory::{ItemSlotData, Player};
+    use azalea_nbt::{Nbt, NbtCompound};
+    use azalea_registry::Item;
+
+    fn item(kind: Item, damage: i32) -> ItemSlot {
+        let tag = NbtCompound::from_iter([("Damage".into(), Nbt::Int(damage))]);
+        ItemSlot::Present(ItemSlotData {
+            kind,
+            count: 1,
+            nbt: Nbt::Compound(NbtCompound::from_iter([("".into(), Nbt::Compound(tag))])),
+        })
+    }
+
+    fn menu_with(items: &[(usize, ItemSlot)]) -> Menu {
+        let mut menu = Menu::Player(Player::default());
+        for (i, item) in items {
+            *menu.slot_mut(*i).unwrap() = item.clone();
+        }
+        menu
+    }
+
+    #[test]
+    fn test_best_tool() {
+        let menu = menu_with(&[
+            (9, item(Item::DiamondShovel, 0)),
+            (10, item(Item::GoldenPickaxe, 0)),
+            (11, item(Item::IronPickaxe, 0)),
+            (12, item(Item::Dirt, 0)),
+        ]);
+        let slots = menu.player_slots_range();
+
+        // gold is faster but can't mine diamond ore
+        let diamond_ore = azalea_registry::Block::DiamondOre.into();
+        assert_eq!(
+            best_tool_in_slots(&menu, slots.clone(), diamond_ore),
+            Some(11)
+        );
+        let stone = azalea_registry::Block::Stone.into();
+        assert_eq!(best_tool_in_slots(&menu, slots.clone(), stone), Some(10));
+        let dirt = azalea_registry::Block::Dirt.into();
+        assert_eq!(best_tool_in_slots(&menu, slots.clone(), dirt), Some(9));
+        // everything breaks torches instantly
+        let torch = azalea_registry::Block::Torch.into();
+        assert_eq!(best_tool_in_slots(&menu, slots, torch), None);
+    }
+
+    #[test]
+    fn test_best_tool_durability() {
+        let menu = menu_with(&[
+            (9, item(Item::IronPickaxe, 249)),
+            (10, item(Item::IronPickaxe, 200)),
+            (11, item(Item::IronPickaxe, 100)),
+        ]);
+        let stone = azalea_registry::Block::Stone.into();
+        assert_eq!(
+            best_tool_in_slots(&menu, menu.player_slots_range(), stone),
+            Some(11)
+        );
+    }
+
+    #[test]
+    fn test_best_weapon() {
+        let menu = menu_with(&[
+            (9, item(Item::DiamondAxe, 0)),
+            (10, item(Item::IronSword, 0)),
+            (11, item(Item::Stick, 0)),
+        ]);
+        // swords attack faster than axes so they do more damage over time
+        assert_eq!(
+            best_weapon_in_slots(&menu, menu.player_slots_range()),
+            Some(10)
+        );
+
+        let menu = menu_with(&[(11, item(Item::Stick, 0))]);
+        assert_eq!(best_weapon_in_slots(&menu, menu.player_slots_range()), None);
+    }
+}
@@ -11,9 +11,8 @@ use crate::ecs::{
 };
 use azalea_core::Vec3;
 use azalea_physics::{force_jump_listener, PhysicsSet};
-use azalea_world::entity::{clamp_look_direction, EyeHeight, LookDirection};
+use azalea_world::entity::{clamp_look_direction, direction_looking_at, EyeHeight, LookDirection};
 use azalea_world::entity::{metadata::Player, Jumping, Local, Position};
-use std::f64::consts::PI;
 
 use crate::pathfinder::PathfinderPlugin;
 
@@ -115,17 +114,6 @@ fn look_at_listener(
     }
 }
 
-/// Return the (`y_rot`, `x_rot`) that would make a client at `current` be
-/// looking at `target`.
-fn direction_looking_at(current: &Vec3, target: &Vec3) -> (f32, f32) {
-    // borrowed from mineflayer's Bot.lookAt because i didn't want to do math
-    let delta = target - current;
-    let y_rot = (PI - f64::atan2(-delta.x, -delta.z)) * (180.0 / PI);
-    let ground_distance = f64::sqrt(delta.x * delta.x + delta.z * delta.z);
-    let x_rot = f64::atan2(delta.y, ground_distance) * -(180.0 / PI);
-    (y_rot as f32, x_rot as f32)
-}
-
 /// A [`PluginGroup`] for the plugins that add extra bot functionality to the
 /// client.
 pub struct DefaultBotPlugins;
@@ -11,6 +11,7 @@ use azalea_protocol::packets::game::ClientboundGamePacket;
 use bevy_app::{App, Plugin};
 use bevy_ecs::{component::Component, prelude::EventReader, system::Commands};
 use std::fmt::Debug;
+use tokio::sync::broadcast::error::RecvError;
 
 pub struct ContainerPlugin;
 impl Plugin for ContainerPlugin {
@@ -48,31 +49,7 @@ impl ContainerClientExt for Client {
             .entity_mut(self.entity)
             .insert(WaitingForInventoryOpen);
         self.block_interact(pos);
-
-        let mut receiver = {
-            let ecs = self.ecs.lock();
-            let tick_broadcast = ecs.resource::<TickBroadcast>();
-            tick_broadcast.subscribe()
-        };
-        while receiver.recv().await.is_ok() {
-            let ecs = self.ecs.lock();
-            if ecs.get::<WaitingForInventoryOpen>(self.entity).is_none() {
-                break;
-            }
-        }
-
-        let ecs = self.ecs.lock();
-        let inventory = ecs
-            .get::<InventoryComponent>(self.entity)
-            .expect("no inventory");
-        if inventory.id == 0 {
-            None
-        } else {
-            Some(ContainerHandle {
-                id: inventory.id,
-                client: self.clone(),
-            })
-        }
+        wait_for_container_open(self).await
     }
 
     /// Open the player's inventory. This will return None if another
@@ -98,13 +75,17 @@ impl ContainerClientExt for Client {
     }
 }
 
+/// How many ticks we wait for the server to update a container after we click
+/// in it before giving up, for use with [`ContainerHandle::wait_for_menu`].
+pub(crate) const MAX_TICKS_WAITING: usize = 20;
+
 /// A handle to the open container. The container will be closed once this is
 /// dropped.
 pub struct ContainerHandle {
     /// The id of the container. If this is 0, that means it's the player's
     /// inventory.
-    id: u8,
-    client: Client,
+    pub(crate) id: u8,
+    pub(crate) client: Client,
 }
 impl Drop for ContainerHandle {
     fn drop(&mut self) {
@@ -163,6 +144,75 @@ impl ContainerHandle {
             operation,
         });
     }
+
+    /// Get a property of the container that the server sent, like how far
+    /// along a furnace is. Returns `None` if the container is closed or the
+    /// server hasn't sent the property.
+    pub fn data(&self, id: u16) -> Option<u16> {
+        let ecs = self.client.ecs.lock();
+        let inventory = ecs
+            .get::<InventoryComponent>(self.client.entity)
+            .expect("no inventory");
+        if inventory.id != self.id {
+            return None;
+        }
+        inventory.container_data.get(&id).copied()
+    }
+
+    /// Wait until the condition is true for the menu, checking every tick for
+    /// at most `max_ticks` ticks. Returns whether the condition became true,
+    /// or `None` if the container was closed.
+    pub(crate) async fn wait_for_menu(
+        &self,
+        max_ticks: usize,
+        condition: impl Fn(&Menu) -> bool,
+    ) -> Option<bool> {
+        let mut receiver = {
+            let ecs = self.client.ecs.lock();
+            ecs.resource::<TickBroadcast>().subscribe()
+        };
+        for _ in 0..max_ticks {
+            match receiver.recv().await {
+                Ok(()) | Err(RecvError::Lagged(_)) => {}
+                // the client was disconnected
+                Err(RecvError::Closed) => return None,
+            }
+            if condition(&self.menu()?) {
+                return Some(true);
+            }
+        }
+        Some(false)
+    }
+}
+
+/// Wait until the server opens the container that we're waiting for (after
+/// inserting [`WaitingForInventoryOpen`]), and return a handle to it. Returns
+/// `None` if the server didn't open a container.
+pub(crate) async fn wait_for_container_open(client: &Client) -> Option<ContainerHandle> {
+    let mut receiver = {
+        let ecs = client.ecs.lock();
+        let tick_broadcast = ecs.resource::<TickBroadcast>();
+        tick_broadcast.subscribe()
+    };
+    while receiver.recv().await.is_ok() {
+        let ecs = client.ecs.lock();
+        if ecs.get::<WaitingForInventoryOpen>(client.entity).is_none() {
+            break;
+        }
+    }
+
+    let ecs = client.ecs.lock();
+    let inventory = ecs
+        .get::<InventoryComponent>(client.entity)
+        .expect("no inventory");
+    if inventory.id == 0 {
+        None
+    } else {
+        Some(ContainerHandle {
+            id: inventory.id,
+            client: client.clone(),
+        })
+    }
 }
 
 #[derive(Component, Debug)]
@@ -0,0 +1,256 @@
+//! Craft items with a crafting table or the crafting grid in our inventory.
+
+use azalea_client::{
+    interact::pick_range,
+    recipe_book::{crafting_layout, PlaceRecipeEvent, RecipeBook},
+    Client, LocalGameMode,
+};
+use azalea_core::{BlockPos, ResourceLocation};
+use azalea_inventory::{
+    operations::{PickupClick, QuickMoveClick},
+    ItemSlot, Menu,
+};
+use azalea_protocol::packets::game::clientbound_update_recipes_packet::Ingredient;
+use azalea_world::entity::{EyeHeight, Position};
+use thiserror::Error;
+
+use crate::container::{ContainerClientExt, ContainerHandle, MAX_TICKS_WAITING};
+
+pub trait CraftingClientExt {
+    async fn craft(&mut self, recipe_id: &str, count: u32) -> Result<u32, CraftError>;
+}
+
+impl CraftingClientExt for Client {
+    /// Craft a recipe `count` times, and return how many items were made.
+    ///
+    /// Recipes that fit in a 2x2 grid are crafted in our inventory, and other
+    /// recipes use the nearest crafting table that we can reach. If you
+    /// already have a crafting table open, use [`ContainerHandle::craft`]
+    /// instead.
+    ///
+    /// ```
+    /// # use azalea::prelude::*;
+    /// # async fn example(mut bot: azalea::Client) {
+    /// match bot.craft("minecraft:stick", 2).await {
+    ///     Ok(crafted) => bot.chat(&format!("I made {crafted} sticks")),
+    ///     Err(e) => bot.chat(&format!("I couldn't make sticks: {e}")),
+    /// }
+    /// # }
+    /// ```
+    async fn craft(&mut self, recipe_id: &str, count: u32) -> Result<u32, CraftError> {
+        let recipe_id = ResourceLocation::new(recipe_id);
+        let fits_in_inventory = {
+            let mut ecs = self.ecs.lock();
+            let recipe_book = self.query::<&RecipeBook>(&mut ecs);
+            let recipe = recipe_book
+                .get(&recipe_id)
+                .ok_or(CraftError::UnknownRecipe)?;
+            crafting_layout(&recipe.data, 2).is_some()
+        };
+
+        if fits_in_inventory {
+            if let Some(inventory) = self.open_inventory() {
+                return inventory.craft(&recipe_id, count).await;
+            }
+        }
+
+        let crafting_table =
+            find_crafting_table_in_reach(self).ok_or(CraftError::NoCraftingTable)?;
+        let crafting_table = self
+            .open_container(crafting_table)
+            .await
+            .ok_or(CraftError::NoCraftingTable)?;
+        crafting_table.craft(&recipe_id, count).await
+    }
+}
+
+/// The position of the nearest crafting table that's close enough for us to
+/// open.
+fn find_crafting_table_in_reach(client: &Client) -> Option<BlockPos> {
+    let (position, eye_height, game_mode) = {
+        let mut ecs = client.ecs.lock();
+        let (position, eye_height, game_mode) =
+            client.query::<(&Position, &EyeHeight, &LocalGameMode)>(&mut ecs);
+        (*position, **eye_height, game_mode.current)
+    };
+    let eye_position = position.up(eye_height as f64);
+
+    let crafting_table = client
+        .world()
+        .read()
+        .find_block(eye_position, &azalea_registry::Block::CraftingTable.into())?;
+    if eye_position.distance_to(&crafting_table.center()) > pick_range(game_mode) {
+        return None;
+    }
+    Some(crafting_table)
+}
+
+impl ContainerHandle {
+    /// Craft a recipe `count` times in this container, which has to be our
+    /// inventory or a crafting table. Returns how many items were made.
+    ///
+    /// If we've unlocked the recipe then the server puts the ingredients in
+    /// the grid for us, otherwise we move them there ourselves. The results
+    /// are shift-clicked into our inventory. This stops early if we run out
+    /// of ingredients or space in the inventory.
+    pub async fn craft(&self, recipe_id: &ResourceLocation, count: u32) -> Result<u32, CraftError> {
+        let (recipe, unlocked) = {
+            let mut ecs = self.client.ecs.lock();
+            let recipe_book = self.client.query::<&RecipeBook>(&mut ecs);
+            let recipe = recipe_book
+                .get(recipe_id)
+                .ok_or(CraftError::UnknownRecipe)?
+                .clone();
+            (recipe, recipe_book.is_unlocked(recipe_id))
+        };
+
+        let menu = self.menu().ok_or(CraftError::ContainerClosed)?;
+        let (Some(grid_size), Some(result_slot), Some(grid_slots)) = (
+            menu.crafting_grid_size(),
+            menu.crafting_result_slot(),
+            menu.crafting_grid_slots(),
+        ) else {
+            return Err(CraftError::NotACraftingGrid);
+        };
+        let layout = crafting_layout(&recipe.data, grid_size).ok_or(CraftError::DoesNotFit)?;
+
+        // anything that's already in the grid would get in the way
+        self.clear_crafting_grid(&menu);
+
+        let mut crafted = 0;
+        let mut stopped_because = None;
+        for _ in 0..count {
+            if unlocked {
+                self.client.ecs.lock().send_event(PlaceRecipeEvent {
+                    entity: self.client.entity,
+                    window_id: self.id,
+                    recipe: recipe_id.clone(),
+                    shift_down: false,
+                });
+            } else {
+                let menu = self.menu().ok_or(CraftError::ContainerClosed)?;
+                if !self.place_ingredients(&menu, &layout, *grid_slots.start()) {
+                    stopped_because = Some(CraftError::MissingIngredients);
+                    break;
+                }
+            }
+
+            // the server doesn't put anything in the grid if we don't have the
+            // ingredients, so the result never shows up
+            let has_result = self
+                .wait_for_menu(MAX_TICKS_WAITING, |menu| {
+                    menu.slot(result_slot).map_or(false, ItemSlot::is_present)
+                })
+                .await
+                .ok_or(CraftError::ContainerClosed)?;
+            if !has_result {
+                stopped_because = Some(CraftError::MissingIngredients);
+                break;
+            }
+
+            let result_count = self
+                .menu()
+                .ok_or(CraftError::ContainerClosed)?
+                .slot(result_slot)
+                .map_or(0, ItemSlot::count);
+            self.click(QuickMoveClick::Left {
+                slot: result_slot as u16,
+            });
+            let took_result = self
+                .wait_for_menu(MAX_TICKS_WAITING, |menu| {
+                    menu.slot(result_slot).map_or(true, ItemSlot::is_empty)
+                })
+                .await
+                .ok_or(CraftError::ContainerClosed)?;
+            if !took_result {
+                stopped_because = Some(CraftError::InventoryFull);
+                break;
+            }
+            crafted += result_count.max(0) as u32;
+        }
+
+        // put back any ingredients we didn't use, and things like the empty
+        // buckets left over from cake
+        if let Some(menu) = self.menu() {
+            self.clear_crafting_grid(&menu);
+        }
+
+        match stopped_because {
+            Some(error) if crafted == 0 => Err(error),
+            _ => Ok(crafted),
+        }
+    }
+
+    /// Shift-click everything in the crafting grid back into our inventory.
+    fn clear_crafting_grid(&self, menu: &Menu) {
+        let Some(grid_slots) = menu.crafting_grid_slots() else {
+            return;
+        };
+        for slot in grid_slots {
+            if menu.slot(slot).map_or(false, ItemSlot::is_present) {
+                self.click(QuickMoveClick::Left { slot: slot as u16 });
+            }
+        }
+    }
+
+    /// Move one of each ingredient from our inventory into the crafting grid.
+    /// Returns false without clicking anything if we don't have all the
+    /// ingredients.
+    fn place_ingredients(
+        &self,
+        menu: &Menu,
+        layout: &[Option<&Ingredient>],
+        first_grid_slot: usize,
+    ) -> bool {
+        let slots = menu.slots();
+        let mut remaining = slots.iter().map(ItemSlot::count).collect::<Vec<_>>();
+
+        let mut moves = Vec::new();
+        for (i, ingredient) in layout.iter().enumerate() {
+            let Some(ingredient) = ingredient else {
+                continue;
+            };
+            let Some(source_slot) = menu
+                .player_slots_range()
+                .find(|&slot| remaining[slot] > 0 && ingredient.test(&slots[slot]))
+            else {
+                return false;
+            };
+            remaining[source_slot] -= 1;
+            moves.push((source_slot as u16, (first_grid_slot + i) as u16));
+        }
+
+        for (source_slot, grid_slot) in moves {
+            // pick up the stack, put one item in the grid, and put the rest back
+            self.click(PickupClick::Left {
+                slot: Some(source_slot),
+            });
+            self.click(PickupClick::Right {
+                slot: Some(grid_slot),
+            });
+            self.click(PickupClick::Left {
+                slot: Some(source_slot),
+            });
+        }
+        true
+    }
+}
+
+/// The reason that we couldn't craft anything.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CraftError {
+    #[error("The server doesn't have a recipe with that id")]
+    UnknownRecipe,
+    #[error("The recipe doesn't fit in this crafting grid")]
+    DoesNotFit,
+    #[error("The container isn't our inventory or a crafting table")]
+    NotACraftingGrid,
+    #[error("There's no crafting table close enough to use")]
+    NoCraftingTable,
+    #[error("The container was closed")]
+    ContainerClosed,
+    #[error("We don't have the ingredients for the recipe")]
+    MissingIngredients,
+    #[error("There's no space in the inventory for the result")]
+    InventoryFull,
+}
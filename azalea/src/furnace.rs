@@ -0,0 +1,314 @@
+//! Smelt items with furnaces, blast furnaces and smokers.
+
+use azalea_client::Client;
+use azalea_core::BlockPos;
+use azalea_inventory::{
+    item::MaxStackSizeExt,
+    operations::{PickupClick, QuickMoveClick},
+    ItemSlot, Menu,
+};
+use thiserror::Error;
+
+use crate::container::{ContainerClientExt, ContainerHandle, MAX_TICKS_WAITING};
+
+/// The ids of the container properties that furnaces send. These are the same
+/// for blast furnaces and smokers.
+mod data {
+    /// The number of ticks left until the current fuel runs out.
+    pub const LIT_TIME: u16 = 0;
+    /// The number of ticks that the current fuel lasts in total.
+    pub const LIT_DURATION: u16 = 1;
+    /// The number of ticks that the current item has been cooking for.
+    pub const COOKING_PROGRESS: u16 = 2;
+    /// The number of ticks that it takes to cook the current item.
+    pub const COOKING_TOTAL_TIME: u16 = 3;
+}
+
+pub trait FurnaceClientExt {
+    async fn open_furnace(&mut self, pos: BlockPos) -> Option<FurnaceHandle>;
+}
+
+impl FurnaceClientExt for Client {
+    /// Open a furnace, blast furnace or smoker in the world. Returns `None` if
+    /// it couldn't be opened or if the block isn't a furnace.
+    ///
+    /// ```
+    /// # use azalea::prelude::*;
+    /// # async fn example(mut bot: azalea::Client, pos: azalea::BlockPos) {
+    /// let Some(furnace) = bot.open_furnace(pos).await else {
+    ///     return;
+    /// };
+    /// furnace.load_ingredient(azalea::Item::RawIron, 8).await.unwrap();
+    /// furnace.load_fuel(azalea::Item::Coal, 1).await.unwrap();
+    /// furnace.wait_for_result(8).await.unwrap();
+    /// furnace.take_result().await.unwrap();
+    /// # }
+    /// ```
+    async fn open_furnace(&mut self, pos: BlockPos) -> Option<FurnaceHandle> {
+        self.open_container(pos).await?.into_furnace().ok()
+    }
+}
+
+impl ContainerHandle {
+    /// Turn this into a [`FurnaceHandle`] if it's a furnace, blast furnace or
+    /// smoker. Otherwise, the container handle is returned back.
+    pub fn into_furnace(self) -> Result<FurnaceHandle, ContainerHandle> {
+        match self.menu() {
+            Some(Menu::Furnace { .. } | Menu::BlastFurnace { .. } | Menu::Smoker { .. }) => {
+                Ok(FurnaceHandle { container: self })
+            }
+            _ => Err(self),
+        }
+    }
+}
+
+/// A handle to an open furnace, blast furnace or smoker. The container will be
+/// closed once this is dropped.
+#[derive(Debug)]
+pub struct FurnaceHandle {
+    container: ContainerHandle,
+}
+
+impl FurnaceHandle {
+    // blast furnaces and smokers have their slots in the same places
+    const INGREDIENT_SLOT: usize = Menu::FURNACE_INGREDIENT_SLOT;
+    const FUEL_SLOT: usize = Menu::FURNACE_FUEL_SLOT;
+    const RESULT_SLOT: usize = Menu::FURNACE_RESULT_SLOT;
+
+    /// The container handle for the furnace, if you want to click it
+    /// directly.
+    pub fn container(&self) -> &ContainerHandle {
+        &self.container
+    }
+
+    /// The item that's being smelted, or `None` if the furnace is closed.
+    pub fn ingredient(&self) -> Option<ItemSlot> {
+        self.slot(Self::INGREDIENT_SLOT)
+    }
+    /// The item that's being burned, or `None` if the furnace is closed.
+    pub fn fuel(&self) -> Option<ItemSlot> {
+        self.slot(Self::FUEL_SLOT)
+    }
+    /// The items that have been smelted, or `None` if the furnace is closed.
+    pub fn result(&self) -> Option<ItemSlot> {
+        self.slot(Self::RESULT_SLOT)
+    }
+
+    fn slot(&self, index: usize) -> Option<ItemSlot> {
+        self.container.menu()?.slot(index).cloned()
+    }
+
+    /// Whether the furnace is currently burning fuel.
+    pub fn is_lit(&self) -> bool {
+        self.fuel_remaining() > 0
+    }
+
+    /// The number of ticks until the fuel that's burning runs out. This
+    /// doesn't include the fuel that's still in the fuel slot.
+    pub fn fuel_remaining(&self) -> u16 {
+        self.container.data(data::LIT_TIME).unwrap_or_default()
+    }
+
+    /// How much of the fuel that's burning is left, from 0 to 1.
+    pub fn fuel_progress(&self) -> f32 {
+        let lit_duration = self.container.data(data::LIT_DURATION).unwrap_or_default();
+        if lit_duration == 0 {
+            return 0.;
+        }
+        self.fuel_remaining() as f32 / lit_duration as f32
+    }
+
+    /// How close the current item is to being done, from 0 to 1.
+    pub fn cook_progress(&self) -> f32 {
+        let progress = self
+            .container
+            .data(data::COOKING_PROGRESS)
+            .unwrap_or_default();
+        let total_time = self
+            .container
+            .data(data::COOKING_TOTAL_TIME)
+            .unwrap_or_default();
+        if total_time == 0 {
+            return 0.;
+        }
+        (progress as f32 / total_time as f32).min(1.)
+    }
+
+    /// Move up to `count` of the item from our inventory into the ingredient
+    /// slot, and return how many were moved.
+    pub async fn load_ingredient(
+        &self,
+        item: azalea_registry::Item,
+        count: u32,
+    ) -> Result<u32, FurnaceError> {
+        self.load(Self::INGREDIENT_SLOT, item, count).await
+    }
+
+    /// Move up to `count` of the item from our inventory into the fuel slot,
+    /// and return how many were moved.
+    pub async fn load_fuel(
+        &self,
+        item: azalea_registry::Item,
+        count: u32,
+    ) -> Result<u32, FurnaceError> {
+        self.load(Self::FUEL_SLOT, item, count).await
+    }
+
+    async fn load(
+        &self,
+        target_slot: usize,
+        item: azalea_registry::Item,
+        count: u32,
+    ) -> Result<u32, FurnaceError> {
+        let menu = self.container.menu().ok_or(FurnaceError::ContainerClosed)?;
+        let target = menu
+            .slot(target_slot)
+            .ok_or(FurnaceError::ContainerClosed)?;
+        if target.is_present() && target.kind() != item {
+            return Err(FurnaceError::SlotOccupied);
+        }
+        let space = (item.max_stack_size() - target.count()).max(0) as u32;
+        let mut left_to_move = count.min(space);
+
+        let count_in_inventory = |menu: &Menu| {
+            let slots = menu.slots();
+            slots[menu.player_slots_range()]
+                .iter()
+                .filter(|slot| slot.kind() == item)
+                .map(|slot| slot.count().max(0) as u32)
+                .sum::<u32>()
+        };
+        let count_before = count_in_inventory(&menu);
+        if count_before == 0 {
+            return Err(FurnaceError::MissingItem);
+        }
+
+        let slots = menu.slots();
+        let mut moved = 0;
+        for source_slot in menu.player_slots_range() {
+            if left_to_move == 0 {
+                break;
+            }
+            let source = &slots[source_slot];
+            if source.kind() != item {
+                continue;
+            }
+            let source_count = source.count().max(0) as u32;
+            let moving = source_count.min(left_to_move);
+
+            let source_slot = source_slot as u16;
+            let target_slot = target_slot as u16;
+            self.container.click(PickupClick::Left {
+                slot: Some(source_slot),
+            });
+            if moving == source_count {
+                self.container.click(PickupClick::Left {
+                    slot: Some(target_slot),
+                });
+            } else {
+                // right clicking puts down one item at a time
+                for _ in 0..moving {
+                    self.container.click(PickupClick::Right {
+                        slot: Some(target_slot),
+                    });
+                }
+                self.container.click(PickupClick::Left {
+                    slot: Some(source_slot),
+                });
+            }
+
+            left_to_move -= moving;
+            moved += moving;
+        }
+
+        // the furnace might use the items before we check, so look at our
+        // inventory instead of the slot we put them in
+        let confirmed = self
+            .container
+            .wait_for_menu(MAX_TICKS_WAITING, |menu| {
+                count_in_inventory(menu) + moved <= count_before
+            })
+            .await
+            .ok_or(FurnaceError::ContainerClosed)?;
+        if !confirmed {
+            return Err(FurnaceError::Rejected);
+        }
+        Ok(moved)
+    }
+
+    /// Wait until there are at least `count` items in the result slot, or
+    /// until the furnace stops smelting because it ran out of ingredients or
+    /// fuel. Returns the items in the result slot.
+    pub async fn wait_for_result(&self, count: u32) -> Result<ItemSlot, FurnaceError> {
+        let mut ticks_unlit = 0;
+        loop {
+            let done = self
+                .container
+                .wait_for_menu(1, |menu| {
+                    let result_count = menu.slot(Self::RESULT_SLOT).map_or(0, ItemSlot::count);
+                    let has_ingredient = menu
+                        .slot(Self::INGREDIENT_SLOT)
+                        .map_or(false, ItemSlot::is_present);
+                    result_count.max(0) as u32 >= count || !has_ingredient
+                })
+                .await
+                .ok_or(FurnaceError::ContainerClosed)?;
+            if done {
+                break;
+            }
+
+            // the furnace doesn't get lit until the tick after it has fuel and
+            // something to smelt, so give it a bit of time
+            if self.is_lit() {
+                ticks_unlit = 0;
+            } else {
+                ticks_unlit += 1;
+                if ticks_unlit > MAX_TICKS_WAITING {
+                    break;
+                }
+            }
+        }
+        self.result().ok_or(FurnaceError::ContainerClosed)
+    }
+
+    /// Shift-click the result into our inventory, and return how many items
+    /// were taken.
+    pub async fn take_result(&self) -> Result<u32, FurnaceError> {
+        let result = self.result().ok_or(FurnaceError::ContainerClosed)?;
+        if result.is_empty() {
+            return Ok(0);
+        }
+
+        self.container.click(QuickMoveClick::Left {
+            slot: Self::RESULT_SLOT as u16,
+        });
+        let taken = self
+            .container
+            .wait_for_menu(MAX_TICKS_WAITING, |menu| {
+                menu.slot(Self::RESULT_SLOT).map_or(0, ItemSlot::count) < result.count()
+            })
+            .await
+            .ok_or(FurnaceError::ContainerClosed)?;
+        if !taken {
+            return Err(FurnaceError::InventoryFull);
+        }
+
+        let left = self.result().ok_or(FurnaceError::ContainerClosed)?;
+        Ok((result.count() - left.count()).max(0) as u32)
+    }
+}
+
+/// The reason that we couldn't move items into or out of a furnace.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FurnaceError {
+    #[error("The furnace was closed")]
+    ContainerClosed,
+    #[error("The item isn't in the inventory")]
+    MissingItem,
+    #[error("There's already a different item in the slot")]
+    SlotOccupied,
+    #[error("The server didn't move the items")]
+    Rejected,
+    #[error("There's no space in the inventory for the result")]
+    InventoryFull,
+}
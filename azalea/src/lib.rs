@@ -4,11 +4,16 @@
 #![feature(async_fn_in_trait)]
 
 mod auto_respawn;
+pub mod auto_tool;
 mod bot;
 mod container;
+pub mod crafting;
+pub mod furnace;
+pub mod merchant;
 pub mod pathfinder;
 pub mod prelude;
 pub mod swarm;
+pub mod transactions;
 
 use app::{App, Plugin, PluginGroup};
 pub use azalea_auth as auth;
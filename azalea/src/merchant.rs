@@ -0,0 +1,257 @@
+//! Trade with villagers and wandering traders.
+
+use azalea_client::{
+    merchant::{MerchantOffers, SelectTradeEvent},
+    Client,
+};
+use azalea_inventory::{
+    item::MaxStackSizeExt,
+    operations::{PickupClick, QuickMoveClick},
+    ItemSlot, Menu,
+};
+use azalea_protocol::packets::game::{
+    clientbound_merchant_offers_packet::MerchantOffer, serverbound_interact_packet::InteractionHand,
+};
+use bevy_ecs::entity::Entity;
+use thiserror::Error;
+
+use crate::container::{
+    wait_for_container_open, ContainerHandle, WaitingForInventoryOpen, MAX_TICKS_WAITING,
+};
+
+pub trait MerchantClientExt {
+    async fn open_merchant(&mut self, entity: Entity) -> Option<MerchantHandle>;
+}
+
+impl MerchantClientExt for Client {
+    /// Right click a villager or wandering trader and wait for its trades.
+    /// Returns `None` if it didn't open a merchant menu.
+    ///
+    /// ```
+    /// # use azalea::prelude::*;
+    /// # async fn example(mut bot: azalea::Client, villager: azalea::ecs::entity::Entity) {
+    /// let Some(merchant) = bot.open_merchant(villager).await else {
+    ///     return;
+    /// };
+    /// for (i, offer) in merchant.offers().iter().enumerate() {
+    ///     if offer.result.kind() == azalea::Item::Emerald {
+    ///         merchant.trade(i, 4).await.ok();
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    async fn open_merchant(&mut self, entity: Entity) -> Option<MerchantHandle> {
+        self.ecs
+            .lock()
+            .entity_mut(self.entity)
+            .insert(WaitingForInventoryOpen);
+        self.interact_entity(entity, InteractionHand::MainHand);
+        let merchant = wait_for_container_open(self).await?.into_merchant().ok()?;
+
+        // the offers are sent right after the menu is opened
+        let got_offers = merchant
+            .container
+            .wait_for_menu(MAX_TICKS_WAITING, |_| merchant.merchant_offers().is_some())
+            .await?;
+        got_offers.then_some(merchant)
+    }
+}
+
+impl ContainerHandle {
+    /// Turn this into a [`MerchantHandle`] if it's a villager or wandering
+    /// trader. Otherwise, the container handle is returned back.
+    pub fn into_merchant(self) -> Result<MerchantHandle, ContainerHandle> {
+        match self.menu() {
+            Some(Menu::Merchant { .. }) => Ok(MerchantHandle { container: self }),
+            _ => Err(self),
+        }
+    }
+}
+
+/// A handle to an open villager or wandering trader. The container will be
+/// closed once this is dropped.
+#[derive(Debug)]
+pub struct MerchantHandle {
+    container: ContainerHandle,
+}
+
+impl MerchantHandle {
+    const PAYMENT_SLOTS: std::ops::RangeInclusive<usize> = Menu::MERCHANT_PAYMENTS_SLOTS;
+    const RESULT_SLOT: usize = Menu::MERCHANT_RESULT_SLOT;
+
+    /// The container handle for the merchant, if you want to click it
+    /// directly.
+    pub fn container(&self) -> &ContainerHandle {
+        &self.container
+    }
+
+    /// The offers and level of this merchant, or `None` if it's closed or the
+    /// server hasn't sent them yet.
+    pub fn merchant_offers(&self) -> Option<MerchantOffers> {
+        let offers = self.container.client.get_component::<MerchantOffers>()?;
+        (offers.container_id == self.container.id).then_some(offers)
+    }
+
+    /// The trades that this merchant offers. This is empty if the merchant is
+    /// closed.
+    pub fn offers(&self) -> Vec<MerchantOffer> {
+        self.merchant_offers()
+            .map(|offers| offers.offers)
+            .unwrap_or_default()
+    }
+
+    /// The level of the villager, from 1 (novice) to 5 (master).
+    pub fn villager_level(&self) -> u32 {
+        self.merchant_offers()
+            .map(|offers| offers.villager_level)
+            .unwrap_or_default()
+    }
+
+    /// Pick a trade, which makes the server move the items to pay for it from
+    /// our inventory into the payment slots.
+    pub fn select_trade(&self, index: usize) {
+        self.container
+            .client
+            .ecs
+            .lock()
+            .send_event(SelectTradeEvent {
+                entity: self.container.client.entity,
+                window_id: self.container.id,
+                index: index as u32,
+            });
+    }
+
+    /// Use the trade at the given index `times` times, and return how many
+    /// trades were done. This stops early if we run out of items to pay with,
+    /// if the trade runs out of stock, or if there's no space in our inventory.
+    pub async fn trade(&self, index: usize, times: u32) -> Result<u32, TradeError> {
+        let offer = self
+            .offers()
+            .get(index)
+            .cloned()
+            .ok_or(TradeError::UnknownOffer)?;
+        let costs = [offer.cost_a(), offer.cost_b.clone()];
+
+        let mut traded = 0;
+        let mut stopped_because = None;
+        for _ in 0..times {
+            if offer.uses + traded >= offer.max_uses || offer.out_of_stock {
+                stopped_because = Some(TradeError::OutOfStock);
+                break;
+            }
+
+            let menu = self.container.menu().ok_or(TradeError::ContainerClosed)?;
+            if !costs.iter().all(|cost| has_items(&menu, cost)) {
+                stopped_because = Some(TradeError::MissingItems);
+                break;
+            }
+
+            self.select_trade(index);
+            let has_result = self
+                .container
+                .wait_for_menu(MAX_TICKS_WAITING, |menu| {
+                    menu.slot(Self::RESULT_SLOT)
+                        .map_or(false, |result| result.kind() == offer.result.kind())
+                })
+                .await
+                .ok_or(TradeError::ContainerClosed)?;
+            if !has_result {
+                stopped_because = Some(TradeError::MissingItems);
+                break;
+            }
+
+            // shift clicking the result would do as many trades as it could, so we
+            // pick it up and put it in the inventory ourselves
+            let menu = self.container.menu().ok_or(TradeError::ContainerClosed)?;
+            let result = menu.slot(Self::RESULT_SLOT).cloned().unwrap_or_default();
+            let Some(target_slot) = slot_for_item(&menu, &result) else {
+                stopped_because = Some(TradeError::InventoryFull);
+                break;
+            };
+            let count_before = menu.slot(target_slot).map_or(0, ItemSlot::count);
+            self.container.click(PickupClick::Left {
+                slot: Some(Self::RESULT_SLOT as u16),
+            });
+            self.container.click(PickupClick::Left {
+                slot: Some(target_slot as u16),
+            });
+            let took_result = self
+                .container
+                .wait_for_menu(MAX_TICKS_WAITING, |menu| {
+                    menu.slot(target_slot).map_or(0, ItemSlot::count) > count_before
+                })
+                .await
+                .ok_or(TradeError::ContainerClosed)?;
+            if !took_result {
+                stopped_because = Some(TradeError::Rejected);
+                break;
+            }
+            traded += 1;
+        }
+
+        // put whatever's left in the payment slots back in our inventory
+        if let Some(menu) = self.container.menu() {
+            for slot in Self::PAYMENT_SLOTS {
+                if menu.slot(slot).map_or(false, ItemSlot::is_present) {
+                    self.container
+                        .click(QuickMoveClick::Left { slot: slot as u16 });
+                }
+            }
+        }
+
+        match stopped_because {
+            Some(error) if traded == 0 => Err(error),
+            _ => Ok(traded),
+        }
+    }
+}
+
+/// Whether there's enough of the item in our inventory and the payment slots.
+fn has_items(menu: &Menu, cost: &ItemSlot) -> bool {
+    if cost.is_empty() {
+        return true;
+    }
+    let slots = menu.slots();
+    let count = menu
+        .player_slots_range()
+        .chain(MerchantHandle::PAYMENT_SLOTS)
+        .filter(|&i| slots[i].kind() == cost.kind())
+        .map(|i| slots[i].count() as i32)
+        .sum::<i32>();
+    count >= cost.count() as i32
+}
+
+/// The slot in our inventory where the whole item can be put, preferring
+/// stacks of the same item over empty slots.
+fn slot_for_item(menu: &Menu, item: &ItemSlot) -> Option<usize> {
+    let ItemSlot::Present(item) = item else {
+        return None;
+    };
+    let slots = menu.slots();
+    menu.player_slots_range()
+        .find(|&i| match &slots[i] {
+            ItemSlot::Present(slot) => {
+                slot.is_same_item_and_nbt(item)
+                    && slot.count as i16 + item.count as i16 <= item.kind.max_stack_size() as i16
+            }
+            ItemSlot::Empty => false,
+        })
+        .or_else(|| menu.player_slots_range().find(|&i| slots[i].is_empty()))
+}
+
+/// The reason that we couldn't trade with a merchant.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeError {
+    #[error("The merchant doesn't have an offer at that index")]
+    UnknownOffer,
+    #[error("The merchant was closed")]
+    ContainerClosed,
+    #[error("The offer is out of stock")]
+    OutOfStock,
+    #[error("We don't have the items to pay for the trade")]
+    MissingItems,
+    #[error("There's no space in the inventory for the result")]
+    InventoryFull,
+    #[error("The server didn't do the trade")]
+    Rejected,
+}
@@ -1,13 +1,48 @@
-use std::{cmp::Reverse, collections::HashMap, fmt::Debug, hash::Hash, ops::Add};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    ops::Add,
+    time::{Duration, Instant},
+};
 
 use priority_queue::PriorityQueue;
 
+/// How much work [`a_star`] is allowed to do before it gives up on reaching
+/// the goal and returns the best partial path it found instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// The maximum number of nodes that can be visited.
+    pub max_nodes: usize,
+    /// The maximum amount of time that the search can take.
+    pub timeout: Duration,
+}
+
+/// A path that was found by [`a_star`].
+#[derive(Debug)]
+pub struct PathResult<N> {
+    /// The nodes in the path, including the start.
+    pub path: Vec<N>,
+    /// Whether the path doesn't actually reach the goal and only gets us as
+    /// close as we could find. This happens if we hit the [`SearchLimits`],
+    /// or if the goal can't be reached from the part of the world we know
+    /// about.
+    pub is_partial: bool,
+}
+
+/// Find the cheapest path from `start` to a node where `success` returns true.
+///
+/// If the goal can't be reached within the limits, a partial path to the node
+/// that's closest to the goal (according to the heuristic) is returned. This
+/// is `None` if we couldn't get any closer to the goal than the start.
 pub fn a_star<N, W, HeuristicFn, SuccessorsFn, SuccessFn>(
     start: N,
     heuristic: HeuristicFn,
     successors: SuccessorsFn,
     success: SuccessFn,
-) -> Option<Vec<N>>
+    limits: SearchLimits,
+) -> Option<PathResult<N>>
 where
     N: Eq + Hash + Copy + Debug,
     W: PartialOrd + Default + Copy + num_traits::Bounded + Debug + Add<Output = W>,
@@ -15,6 +50,8 @@ where
     SuccessorsFn: Fn(&N) -> Vec<Edge<N, W>>,
     SuccessFn: Fn(&N) -> bool,
 {
+    let start_time = Instant::now();
+
     let mut open_set = PriorityQueue::new();
     open_set.push(start, Reverse(Weight(W::default())));
     let mut nodes: HashMap<N, Node<N, W>> = HashMap::new();
@@ -28,9 +65,24 @@ where
         },
     );
 
+    let mut best_node = start;
+    let mut best_heuristic = heuristic(&start);
+    let mut visited_nodes = 0;
+
     while let Some((current_node, _)) = open_set.pop() {
         if success(&current_node) {
-            return Some(reconstruct_path(&nodes, current_node));
+            return Some(PathResult {
+                path: reconstruct_path(&nodes, current_node),
+                is_partial: false,
+            });
+        }
+
+        visited_nodes += 1;
+        // checking the time is relatively slow so we don't do it every node
+        if visited_nodes >= limits.max_nodes
+            || (visited_nodes % 256 == 0 && start_time.elapsed() > limits.timeout)
+        {
+            break;
         }
 
         let current_g_score = nodes
@@ -45,7 +97,8 @@ where
                 .map(|n| n.g_score)
                 .unwrap_or(W::max_value());
             if tentative_g_score < neighbor_g_score {
-                let f_score = tentative_g_score + heuristic(&neighbor.target);
+                let neighbor_heuristic = heuristic(&neighbor.target);
+                let f_score = tentative_g_score + neighbor_heuristic;
                 nodes.insert(
                     neighbor.target,
                     Node {
@@ -56,11 +109,22 @@ where
                     },
                 );
                 open_set.push(neighbor.target, Reverse(Weight(f_score)));
+
+                if neighbor_heuristic < best_heuristic {
+                    best_heuristic = neighbor_heuristic;
+                    best_node = neighbor.target;
+                }
             }
         }
     }
 
-    None
+    if best_node == start {
+        return None;
+    }
+    Some(PathResult {
+        path: reconstruct_path(&nodes, best_node),
+        is_partial: true,
+    })
 }
 
 fn reconstruct_path<N, W>(nodes: &HashMap<N, Node<N, W>>, current: N) -> Vec<N>
@@ -109,3 +173,101 @@ impl<W: PartialOrd + Debug> PartialOrd for Weight<W> {
         self.0.partial_cmp(&other.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A grid where you can walk in the four cardinal directions, except
+    /// through walls.
+    fn grid_successors(walls: &[(i32, i32)], node: &(i32, i32)) -> Vec<Edge<(i32, i32), f32>> {
+        [(1, 0), (-1, 0), (0, 1), (0, -1)]
+            .into_iter()
+            .map(|(x, y)| (node.0 + x, node.1 + y))
+            .filter(|target| !walls.contains(target))
+            .map(|target| Edge { target, cost: 1. })
+            .collect()
+    }
+
+    fn distance(from: &(i32, i32), to: (i32, i32)) -> f32 {
+        ((from.0 - to.0).abs() + (from.1 - to.1).abs()) as f32
+    }
+
+    const NO_LIMITS: SearchLimits = SearchLimits {
+        max_nodes: usize::MAX,
+        timeout: Duration::MAX,
+    };
+
+    #[test]
+    fn test_full_path() {
+        let goal = (3, 0);
+        let result = a_star(
+            (0, 0),
+            |n| distance(n, goal),
+            |n| grid_successors(&[(1, 0)], n),
+            |n| *n == goal,
+            NO_LIMITS,
+        )
+        .unwrap();
+        assert!(!result.is_partial);
+        assert_eq!(result.path.first(), Some(&(0, 0)));
+        assert_eq!(result.path.last(), Some(&goal));
+        // we had to go around the wall
+        assert_eq!(result.path.len(), 6);
+    }
+
+    #[test]
+    fn test_partial_path_when_out_of_nodes() {
+        let goal = (1000, 0);
+        let result = a_star(
+            (0, 0),
+            |n| distance(n, goal),
+            |n| grid_successors(&[], n),
+            |n| *n == goal,
+            SearchLimits {
+                max_nodes: 10,
+                ..NO_LIMITS
+            },
+        )
+        .unwrap();
+        assert!(result.is_partial);
+        assert_eq!(result.path.first(), Some(&(0, 0)));
+        assert_eq!(result.path.last(), Some(&(9, 0)));
+    }
+
+    #[test]
+    fn test_partial_path_when_unreachable() {
+        // we're in a 3 block long corridor and the goal is behind the end of it
+        let walls = [
+            (-1, 0),
+            (0, 1),
+            (0, -1),
+            (1, 1),
+            (1, -1),
+            (2, 1),
+            (2, -1),
+            (3, 0),
+        ];
+        let goal = (5, 0);
+        let result = a_star(
+            (0, 0),
+            |n| distance(n, goal),
+            |n| grid_successors(&walls, n),
+            |n| *n == goal,
+            NO_LIMITS,
+        )
+        .unwrap();
+        assert!(result.is_partial);
+        assert_eq!(result.path, vec![(0, 0), (1, 0), (2, 0)]);
+
+        // and if we're already as close as we can get there's no path at all
+        let result = a_star(
+            (2, 0),
+            |n| distance(n, goal),
+            |n| grid_successors(&walls, n),
+            |n| *n == goal,
+            NO_LIMITS,
+        );
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,281 @@
+//! The goals that the pathfinder can try to reach.
+//!
+//! Every goal has a heuristic that never overestimates the cost of reaching
+//! it (so the paths the pathfinder finds are optimal).
+
+use azalea_core::{BlockPos, Vec3};
+
+use super::{
+    moves::{ASCEND_COST, DESCEND_COST, DIAGONAL_COST, WALK_ONE_BLOCK_COST},
+    Node,
+};
+
+pub trait Goal {
+    /// An estimate of the cost of getting from the node to the goal. This
+    /// must never be more than the real cost.
+    fn heuristic(&self, n: &Node) -> f32;
+    /// Whether the node satisfies the goal.
+    fn success(&self, n: &Node) -> bool;
+}
+
+/// The lowest possible cost of moving the given number of blocks horizontally.
+fn xz_heuristic(dx: f32, dz: f32) -> f32 {
+    let x = dx.abs();
+    let z = dz.abs();
+    let (diagonal, straight) = if x < z { (x, z - x) } else { (z, x - z) };
+    diagonal * DIAGONAL_COST + straight * WALK_ONE_BLOCK_COST
+}
+
+/// The lowest possible cost of moving the given number of blocks vertically.
+/// A positive `dy` means we have to go up.
+fn y_heuristic(dy: f32) -> f32 {
+    if dy > 0. {
+        dy * ASCEND_COST
+    } else {
+        -dy * DESCEND_COST
+    }
+}
+
+/// The lowest possible cost of moving by the given offset.
+///
+/// Moving up or down always has to happen at the same time as moving
+/// horizontally, so we take the larger of the two costs instead of adding
+/// them.
+fn xyz_heuristic(dx: f32, dy: f32, dz: f32) -> f32 {
+    f32::max(xz_heuristic(dx, dz), y_heuristic(dy))
+}
+
+/// Move `delta` towards zero by `amount`, without crossing zero. This is used
+/// for goals that are satisfied anywhere in an area.
+fn shrink_towards_zero(delta: f32, amount: f32) -> f32 {
+    if delta > 0. {
+        f32::max(delta - amount, 0.)
+    } else {
+        f32::min(delta + amount, 0.)
+    }
+}
+
+/// Move to the given block position.
+pub struct BlockPosGoal {
+    pub pos: BlockPos,
+}
+impl Goal for BlockPosGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        let dx = (self.pos.x - n.pos.x) as f32;
+        let dy = (self.pos.y - n.pos.y) as f32;
+        let dz = (self.pos.z - n.pos.z) as f32;
+        xyz_heuristic(dx, dy, dz)
+    }
+    fn success(&self, n: &Node) -> bool {
+        n.pos == self.pos
+    }
+}
+impl From<BlockPos> for BlockPosGoal {
+    fn from(pos: BlockPos) -> Self {
+        Self { pos }
+    }
+}
+
+/// Move to the given X and Z coordinates, at any Y coordinate.
+pub struct XZGoal {
+    pub x: i32,
+    pub z: i32,
+}
+impl Goal for XZGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        let dx = (self.x - n.pos.x) as f32;
+        let dz = (self.z - n.pos.z) as f32;
+        xz_heuristic(dx, dz)
+    }
+    fn success(&self, n: &Node) -> bool {
+        n.pos.x == self.x && n.pos.z == self.z
+    }
+}
+
+/// Move to the given Y coordinate, at any X and Z coordinates.
+pub struct YGoal {
+    pub y: i32,
+}
+impl Goal for YGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        let dy = (self.y - n.pos.y) as f32;
+        y_heuristic(dy)
+    }
+    fn success(&self, n: &Node) -> bool {
+        n.pos.y == self.y
+    }
+}
+
+/// Get within the given radius of a position. The distance is measured from
+/// the center of the block that the bot's feet are in.
+pub struct RadiusGoal {
+    pub pos: Vec3,
+    pub radius: f32,
+}
+impl Goal for RadiusGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        let n = n.pos.center();
+        let dx = shrink_towards_zero((self.pos.x - n.x) as f32, self.radius);
+        let dy = shrink_towards_zero((self.pos.y - n.y) as f32, self.radius);
+        let dz = shrink_towards_zero((self.pos.z - n.z) as f32, self.radius);
+        xyz_heuristic(dx, dy, dz)
+    }
+    fn success(&self, n: &Node) -> bool {
+        let n = n.pos.center();
+        let radius = self.radius as f64;
+        n.distance_to_sqr(&self.pos) <= radius * radius
+    }
+}
+
+/// Get close enough to a block that we can interact with it (i.e. mine it or
+/// right click it) in survival mode.
+pub struct ReachBlockGoal {
+    pub pos: BlockPos,
+}
+impl ReachBlockGoal {
+    /// How far away a player's eyes can be from a block in survival mode while
+    /// still being able to interact with it.
+    pub const REACH_DISTANCE: f64 = 4.5;
+    /// The eye height of a player that's standing normally.
+    const EYE_HEIGHT: f64 = 1.62;
+}
+impl Goal for ReachBlockGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        // these are the furthest away a node can be from the block on each axis while
+        // still succeeding, see `success`
+        let dx = shrink_towards_zero((self.pos.x - n.pos.x) as f32, 5.);
+        let dy = (self.pos.y - n.pos.y) as f32;
+        let dy = if dy > 0. {
+            shrink_towards_zero(dy, 6.)
+        } else {
+            shrink_towards_zero(dy, 3.)
+        };
+        let dz = shrink_towards_zero((self.pos.z - n.pos.z) as f32, 5.);
+        xyz_heuristic(dx, dy, dz)
+    }
+    fn success(&self, n: &Node) -> bool {
+        // we can't be standing inside of the block
+        if n.pos == self.pos || n.pos.up(1) == self.pos {
+            return false;
+        }
+        let eye_position = n.pos.center().up(Self::EYE_HEIGHT - 0.5);
+        // the distance is measured to the closest point of the block
+        let closest_point = Vec3 {
+            x: eye_position
+                .x
+                .clamp(self.pos.x as f64, (self.pos.x + 1) as f64),
+            y: eye_position
+                .y
+                .clamp(self.pos.y as f64, (self.pos.y + 1) as f64),
+            z: eye_position
+                .z
+                .clamp(self.pos.z as f64, (self.pos.z + 1) as f64),
+        };
+        eye_position.distance_to_sqr(&closest_point) <= Self::REACH_DISTANCE * Self::REACH_DISTANCE
+    }
+}
+
+/// Get to any node that doesn't satisfy the given goal. For example,
+/// `InverseGoal(RadiusGoal { .. })` will make the bot get away from a
+/// position.
+///
+/// Since we can't know where the closest node that doesn't satisfy the inner
+/// goal is, the heuristic for this is always 0.
+pub struct InverseGoal<T: Goal>(pub T);
+impl<T: Goal> Goal for InverseGoal<T> {
+    fn heuristic(&self, _n: &Node) -> f32 {
+        0.
+    }
+    fn success(&self, n: &Node) -> bool {
+        !self.0.success(n)
+    }
+}
+
+/// Get to a node that satisfies either of the goals.
+pub struct OrGoal<T: Goal, U: Goal>(pub T, pub U);
+impl<T: Goal, U: Goal> Goal for OrGoal<T, U> {
+    fn heuristic(&self, n: &Node) -> f32 {
+        f32::min(self.0.heuristic(n), self.1.heuristic(n))
+    }
+    fn success(&self, n: &Node) -> bool {
+        self.0.success(n) || self.1.success(n)
+    }
+}
+
+/// Get to a node that satisfies both of the goals.
+pub struct AndGoal<T: Goal, U: Goal>(pub T, pub U);
+impl<T: Goal, U: Goal> Goal for AndGoal<T, U> {
+    fn heuristic(&self, n: &Node) -> f32 {
+        f32::max(self.0.heuristic(n), self.1.heuristic(n))
+    }
+    fn success(&self, n: &Node) -> bool {
+        self.0.success(n) && self.1.success(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pathfinder::VerticalVel;
+
+    fn node(x: i32, y: i32, z: i32) -> Node {
+        Node {
+            pos: BlockPos::new(x, y, z),
+            vertical_vel: VerticalVel::None,
+        }
+    }
+
+    #[test]
+    fn test_block_pos_goal_heuristic() {
+        let goal = BlockPosGoal::from(BlockPos::new(0, 0, 0));
+        assert_eq!(goal.heuristic(&node(0, 0, 0)), 0.);
+        assert_eq!(goal.heuristic(&node(3, 0, 0)), 3. * WALK_ONE_BLOCK_COST);
+        assert_eq!(goal.heuristic(&node(2, 0, 2)), 2. * DIAGONAL_COST);
+        assert_eq!(goal.heuristic(&node(0, -4, 1)), 4. * ASCEND_COST);
+    }
+
+    #[test]
+    fn test_radius_goal() {
+        let goal = RadiusGoal {
+            pos: Vec3::new(0.5, 0.5, 0.5),
+            radius: 3.,
+        };
+        assert!(goal.success(&node(3, 0, 0)));
+        assert!(!goal.success(&node(3, 0, 1)));
+        assert_eq!(goal.heuristic(&node(3, 0, 0)), 0.);
+        assert_eq!(goal.heuristic(&node(5, 0, 0)), 2. * WALK_ONE_BLOCK_COST);
+    }
+
+    #[test]
+    fn test_reach_block_goal() {
+        let goal = ReachBlockGoal {
+            pos: BlockPos::new(0, 0, 0),
+        };
+        assert!(!goal.success(&node(0, 0, 0)));
+        assert!(!goal.success(&node(0, -1, 0)));
+        assert!(goal.success(&node(0, 1, 0)));
+        assert!(goal.success(&node(4, 0, 0)));
+        assert!(!goal.success(&node(6, 0, 0)));
+        // nodes that succeed must have a heuristic of 0
+        assert_eq!(goal.heuristic(&node(4, 0, 0)), 0.);
+        assert_eq!(goal.heuristic(&node(0, -5, 0)), 0.);
+    }
+
+    #[test]
+    fn test_combined_goals() {
+        let goal = OrGoal(
+            BlockPosGoal::from(BlockPos::new(10, 0, 0)),
+            BlockPosGoal::from(BlockPos::new(-2, 0, 0)),
+        );
+        assert!(goal.success(&node(-2, 0, 0)));
+        assert_eq!(goal.heuristic(&node(0, 0, 0)), 2. * WALK_ONE_BLOCK_COST);
+
+        let goal = AndGoal(XZGoal { x: 1, z: 1 }, YGoal { y: 5 });
+        assert!(!goal.success(&node(1, 0, 1)));
+        assert!(goal.success(&node(1, 5, 1)));
+
+        let goal = InverseGoal(XZGoal { x: 0, z: 0 });
+        assert!(!goal.success(&node(0, 10, 0)));
+        assert!(goal.success(&node(1, 0, 0)));
+    }
+}
@@ -1,8 +1,11 @@
 mod astar;
+pub mod goals;
 mod moves;
 
+pub use goals::*;
+
 use crate::bot::{JumpEvent, LookAtEvent};
-use crate::pathfinder::astar::a_star;
+use crate::pathfinder::astar::{a_star, SearchLimits};
 use crate::{SprintDirection, WalkDirection};
 
 use crate::app::{App, CoreSchedule, IntoSystemAppConfig, Plugin};
@@ -15,20 +18,30 @@ use crate::ecs::{
     system::{Commands, Query, Res},
 };
 use astar::Edge;
-use azalea_client::{StartSprintEvent, StartWalkEvent};
-use azalea_core::{BlockPos, CardinalDirection};
+use azalea_client::interact::{BlockInteractEvent, HitResultComponent};
+use azalea_client::inventory::{InventoryComponent, ItemSlot, SetSelectedHotbarSlotEvent};
+use azalea_client::mining::{StartMiningBlockEvent, StopMiningBlockEvent};
+use azalea_client::packet_handling::BlockUpdateEvent;
+use azalea_client::{StartSprintEvent, StartWalkEvent, TickBroadcast};
+use azalea_core::{BlockPos, Direction, Vec3};
 use azalea_physics::PhysicsSet;
+use azalea_registry::Item;
 use azalea_world::entity::metadata::Player;
 use azalea_world::entity::Local;
 use azalea_world::{
     entity::{Physics, Position, WorldName},
-    InstanceContainer,
+    Instance, InstanceContainer,
 };
 use bevy_tasks::{AsyncComputeTaskPool, Task};
 use futures_lite::future;
-use log::{debug, error};
-use std::collections::VecDeque;
+use log::{debug, error, warn};
+use parking_lot::RwLock;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{self, AtomicU64};
 use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::broadcast::error::RecvError;
 
 #[derive(Clone, Default)]
 pub struct PathfinderPlugin;
@@ -36,6 +49,10 @@ impl Plugin for PathfinderPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<GotoEvent>()
             .add_event::<PathFoundEvent>()
+            .add_event::<GoalReachedEvent>()
+            .add_event::<PathFailedEvent>()
+            .add_event::<StopPathfindingEvent>()
+            .add_event::<RecalculatePathEvent>()
             .add_system(
                 // Adding `.in_schedule(CoreSchedule::FixedUpdate)` makes a system run every
                 // Minecraft tick (every 50 milliseconds).
@@ -46,7 +63,20 @@ impl Plugin for PathfinderPlugin {
             .add_system(goto_listener)
             .add_system(add_default_pathfinder)
             .add_system(handle_tasks.before(path_found_listener))
-            .add_system(path_found_listener);
+            .add_system(path_found_listener)
+            .add_system(continue_partial_paths.after(path_found_listener))
+            .add_system(stop_pathfinding_listener.after(goto_listener))
+            .add_system(check_path_after_block_updates.before(goto_listener))
+            .add_system(
+                recalculate_path_listener
+                    .after(check_path_after_block_updates)
+                    .after(goto_listener),
+            )
+            .add_system(
+                record_goto_outcome
+                    .after(handle_tasks)
+                    .after(stop_pathfinding_listener),
+            );
     }
 }
 
@@ -54,143 +84,578 @@ impl Plugin for PathfinderPlugin {
 #[derive(Component, Default)]
 pub struct Pathfinder {
     pub path: VecDeque<Node>,
+    /// Whether [`Self::path`] ends before the goal, see
+    /// [`PathFoundEvent::is_partial`].
+    is_path_partial: bool,
+    /// The goal that we're currently trying to reach, or `None` if we're not
+    /// pathfinding anywhere.
+    pub goal: Option<Arc<dyn Goal + Send + Sync>>,
+    /// The id of the most recent [`GotoEvent`] that this entity received.
+    pub goto_id: Option<GotoId>,
+    /// How the last goto that finished ended. This is what [`GotoHandle`]s
+    /// look at to know when they're done.
+    pub last_outcome: Option<(GotoId, Result<(), GotoError>)>,
+    /// The block that we're currently mining so we can get to the next node.
+    mining: Option<BlockPos>,
+    /// The number of ticks to wait before trying to place a block again, so
+    /// we don't spam the server while it's confirming the last one.
+    place_cooldown: u32,
+    /// The last node in the path that we reached, which is where the next
+    /// node in the path is reached from.
+    last_node: Option<Node>,
+    /// How many ticks it's been since we last reached a node. If this gets too
+    /// high, we're probably stuck and the path is recalculated.
+    ticks_without_progress: u32,
+    /// How many times in a row we've recalculated the path because we were
+    /// stuck.
+    stuck_replans: u32,
+}
+
+/// If we go this many ticks without reaching the next node in the path (and
+/// without mining anything), we assume that we're stuck.
+const STUCK_TICKS: u32 = 60;
+/// How many times we recalculate the path after getting stuck before giving
+/// up on the goal.
+const MAX_STUCK_REPLANS: u32 = 3;
+
+/// Start calculating the rest of a partial path when there's this many nodes
+/// left in it.
+const PLAN_AHEAD_NODES: usize = 10;
+
+/// How far past the center of the block we're standing on we walk before
+/// placing a block in front of us. We have to be past the edge to see the side
+/// of the block, and we'd fall off at 0.8.
+const BRIDGE_EDGE_DISTANCE: f64 = 0.65;
+
+/// What the pathfinder is allowed to do to reach its goal. By default it only
+/// walks, so it never changes the world.
+///
+/// ```
+/// # use azalea::prelude::*;
+/// # use azalea::pathfinder::PathfinderSettings;
+/// # fn example(bot: &Client) {
+/// bot.set_pathfinder_settings(PathfinderSettings {
+///     allow_mining: true,
+///     allow_placing: true,
+///     ..Default::default()
+/// });
+/// # }
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct PathfinderSettings {
+    /// Whether we can break blocks that are in the way. Blocks are broken with
+    /// [`StartMiningBlockEvent`], so this only works if the mining plugin from
+    /// `azalea_client` is added (it is by default). When this is false the
+    /// pathfinder never plans to break blocks and it recalculates the path
+    /// instead of breaking a block that ends up in the way.
+    pub allow_mining: bool,
+    /// Whether we can place blocks to bridge over gaps and pillar up.
+    pub allow_placing: bool,
+    /// The blocks that we're allowed to place if [`Self::allow_placing`] is
+    /// true. They only get used if they're in our hotbar.
+    pub scaffolding_blocks: Vec<Item>,
+    /// How much health (in half hearts) we're willing to lose from a single
+    /// fall. Falls of 3 blocks or less and falls into water don't hurt, so
+    /// those are always allowed.
+    pub max_fall_damage: u32,
+    /// The maximum number of nodes to look at when calculating a path. If we
+    /// can't reach the goal in time, we walk as close to it as we got and keep
+    /// calculating from there.
+    pub max_search_nodes: usize,
+    /// The maximum amount of time to spend calculating a path, see
+    /// [`Self::max_search_nodes`].
+    pub max_search_time: Duration,
 }
+impl Default for PathfinderSettings {
+    fn default() -> Self {
+        Self {
+            allow_mining: false,
+            allow_placing: false,
+            scaffolding_blocks: vec![
+                Item::Dirt,
+                Item::Cobblestone,
+                Item::CobbledDeepslate,
+                Item::Netherrack,
+            ],
+            max_fall_damage: 0,
+            max_search_nodes: 100_000,
+            max_search_time: Duration::from_secs(1),
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 fn add_default_pathfinder(
     mut commands: Commands,
-    mut query: Query<Entity, (Without<Pathfinder>, With<Local>, With<Player>)>,
+    mut query: Query<
+        (Entity, Option<&PathfinderSettings>),
+        (Without<Pathfinder>, With<Local>, With<Player>),
+    >,
 ) {
-    for entity in &mut query {
-        commands.entity(entity).insert(Pathfinder::default());
+    for (entity, settings) in &mut query {
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(Pathfinder::default());
+        if settings.is_none() {
+            entity_commands.insert(PathfinderSettings::default());
+        }
     }
 }
 
 pub trait PathfinderClientExt {
-    fn goto(&self, goal: impl Goal + Send + Sync + 'static);
+    async fn goto(&self, goal: impl Goal + Send + Sync + 'static) -> Result<(), GotoError>;
+    fn start_goto(&self, goal: impl Goal + Send + Sync + 'static) -> GotoHandle;
+    fn stop_pathfinding(&self);
+    fn set_pathfinder_settings(&self, settings: PathfinderSettings);
 }
 
 impl PathfinderClientExt for azalea_client::Client {
+    /// Pathfind to the given goal and wait until we either get there or fail
+    /// to.
+    ///
+    /// Calling `goto` (or [`Self::start_goto`]) again while this is still
+    /// running will make this return [`GotoError::Interrupted`].
+    ///
     /// ```
     /// # use azalea::prelude::*;
-    /// # use azalea::{BlockPos, pathfinder::BlockPosGoal};
+    /// # use azalea::{BlockPos, pathfinder::goals::BlockPosGoal};
+    /// # async fn example(bot: &Client) {
+    /// if bot.goto(BlockPosGoal::from(BlockPos::new(0, 70, 0))).await.is_err() {
+    ///     bot.chat("I couldn't get there :(");
+    /// }
+    /// # }
+    /// ```
+    async fn goto(&self, goal: impl Goal + Send + Sync + 'static) -> Result<(), GotoError> {
+        self.start_goto(goal).wait().await
+    }
+
+    /// Start pathfinding to the given goal without waiting for it to finish.
+    /// You can use the returned [`GotoHandle`] to wait for it later, or just
+    /// drop it if you don't care.
+    ///
+    /// ```
+    /// # use azalea::prelude::*;
+    /// # use azalea::{BlockPos, pathfinder::goals::BlockPosGoal};
     /// # fn example(bot: &Client) {
-    /// bot.goto(BlockPosGoal::from(BlockPos::new(0, 70, 0)));
+    /// bot.start_goto(BlockPosGoal::from(BlockPos::new(0, 70, 0)));
     /// # }
     /// ```
-    fn goto(&self, goal: impl Goal + Send + Sync + 'static) {
+    fn start_goto(&self, goal: impl Goal + Send + Sync + 'static) -> GotoHandle {
+        let id = GotoId::new();
         self.ecs.lock().send_event(GotoEvent {
             entity: self.entity,
             goal: Arc::new(goal),
+            id,
         });
+        GotoHandle {
+            id,
+            client: self.clone(),
+        }
+    }
+
+    /// Stop pathfinding and stop walking. Any [`GotoHandle`]s that were waiting
+    /// will return [`GotoError::Interrupted`].
+    fn stop_pathfinding(&self) {
+        self.ecs.lock().send_event(StopPathfindingEvent {
+            entity: self.entity,
+        });
+    }
+
+    /// Change what the pathfinder is allowed to do. This only affects paths
+    /// that are calculated after it's called.
+    fn set_pathfinder_settings(&self, settings: PathfinderSettings) {
+        self.ecs.lock().entity_mut(self.entity).insert(settings);
+    }
+}
+
+/// A unique identifier for a single call to [`PathfinderClientExt::goto`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GotoId(u64);
+impl GotoId {
+    /// Make a new `GotoId` that's greater than every id that was made before
+    /// it.
+    pub fn new() -> Self {
+        static NEXT_GOTO_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_GOTO_ID.fetch_add(1, atomic::Ordering::Relaxed))
+    }
+}
+impl Default for GotoId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The reason that a goto didn't reach its goal.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GotoError {
+    #[error("No path to the goal could be found")]
+    NoPath,
+    #[error("Timed out before reaching the goal")]
+    Timeout,
+    #[error("Interrupted by another goal or by stopping the pathfinder")]
+    Interrupted,
+}
+
+/// A handle to a goto that was started with
+/// [`PathfinderClientExt::start_goto`]. Dropping this won't stop the
+/// pathfinder.
+pub struct GotoHandle {
+    id: GotoId,
+    client: azalea_client::Client,
+}
+impl GotoHandle {
+    /// Get the unique id for this goto.
+    pub fn id(&self) -> GotoId {
+        self.id
+    }
+
+    /// Returns the outcome of the goto, or `None` if we're still going.
+    pub fn outcome(&self) -> Option<Result<(), GotoError>> {
+        let ecs = self.client.ecs.lock();
+        let Some(pathfinder) = ecs.get::<Pathfinder>(self.client.entity) else {
+            // the pathfinder component might not have been added yet
+            return None;
+        };
+        if let Some((id, outcome)) = pathfinder.last_outcome {
+            if id == self.id {
+                return Some(outcome);
+            }
+        }
+        match pathfinder.goto_id {
+            // a newer goto replaced us
+            Some(current_id) if current_id > self.id => Some(Err(GotoError::Interrupted)),
+            _ => None,
+        }
+    }
+
+    /// Wait until we reach the goal or fail to.
+    pub async fn wait(self) -> Result<(), GotoError> {
+        let mut receiver = {
+            let ecs = self.client.ecs.lock();
+            let tick_broadcast = ecs.resource::<TickBroadcast>();
+            tick_broadcast.subscribe()
+        };
+        loop {
+            if let Some(outcome) = self.outcome() {
+                return outcome;
+            }
+            match receiver.recv().await {
+                Ok(()) | Err(RecvError::Lagged(_)) => {}
+                // the client was dropped
+                Err(RecvError::Closed) => return Err(GotoError::Interrupted),
+            }
+        }
+    }
+
+    /// Like [`Self::wait`], but stop pathfinding and return
+    /// [`GotoError::Timeout`] if we haven't reached the goal after the given
+    /// duration.
+    pub async fn wait_timeout(self, timeout: Duration) -> Result<(), GotoError> {
+        let client = self.client.clone();
+        let id = self.id;
+        match tokio::time::timeout(timeout, self.wait()).await {
+            Ok(outcome) => outcome,
+            Err(_) => {
+                let is_still_current = client
+                    .ecs
+                    .lock()
+                    .get::<Pathfinder>(client.entity)
+                    .map(|pathfinder| pathfinder.goto_id == Some(id))
+                    .unwrap_or_default();
+                if is_still_current {
+                    client.stop_pathfinding();
+                }
+                Err(GotoError::Timeout)
+            }
+        }
     }
 }
+
 pub struct GotoEvent {
     pub entity: Entity,
     pub goal: Arc<dyn Goal + Send + Sync>,
+    /// The id that's used to tell this goto apart from other ones. Use
+    /// [`GotoId::new`] to make one.
+    pub id: GotoId,
 }
 pub struct PathFoundEvent {
     pub entity: Entity,
+    /// The id of the goto that this path was calculated for.
+    pub goto_id: GotoId,
     pub path: VecDeque<Node>,
+    /// Whether the path only gets us closer to the goal instead of all the way
+    /// there. The rest of the path is calculated once we get close to the end
+    /// of this one.
+    pub is_partial: bool,
+}
+/// Sent when an entity reaches the end of its path.
+pub struct GoalReachedEvent {
+    pub entity: Entity,
+    pub goto_id: GotoId,
+}
+/// Sent when the pathfinder couldn't find any path to the goal, or when it
+/// can't follow the path that it found.
+pub struct PathFailedEvent {
+    pub entity: Entity,
+    pub goto_id: GotoId,
+}
+/// Stop pathfinding and clear the current path.
+pub struct StopPathfindingEvent {
+    pub entity: Entity,
+}
+/// Calculate a new path to the current goal from where we are now. We keep
+/// following the old path until the new one is found.
+pub struct RecalculatePathEvent {
+    pub entity: Entity,
 }
 
 #[derive(Component)]
-pub struct ComputePath(Task<Option<PathFoundEvent>>);
+pub struct ComputePath {
+    pub entity: Entity,
+    pub goto_id: GotoId,
+    task: Task<Option<PathFoundEvent>>,
+}
 
+#[allow(clippy::type_complexity)]
 fn goto_listener(
     mut commands: Commands,
     mut events: EventReader<GotoEvent>,
-    mut query: Query<(&Position, &WorldName)>,
+    compute_path_tasks: Query<(Entity, &ComputePath)>,
+    mut query: Query<(
+        &Position,
+        &WorldName,
+        &mut Pathfinder,
+        &PathfinderSettings,
+        Option<&InventoryComponent>,
+    )>,
     instance_container: Res<InstanceContainer>,
 ) {
-    let thread_pool = AsyncComputeTaskPool::get();
-
     for event in events.iter() {
-        let (position, world_name) = query
+        let (position, world_name, mut pathfinder, settings, inventory) = query
             .get_mut(event.entity)
             .expect("Called goto on an entity that's not in the world");
+        // forget about the old path, any tasks that are still calculating it will be
+        // ignored since the id changed
+        pathfinder.path.clear();
+        pathfinder.is_path_partial = false;
+        pathfinder.mining = None;
+        pathfinder.goal = Some(event.goal.clone());
+        pathfinder.goto_id = Some(event.id);
+        // the paths that are still being calculated are for an old goal or an old
+        // version of the world, so dropping their tasks cancels them
+        for (task_entity, compute_path) in &compute_path_tasks {
+            if compute_path.entity == event.entity {
+                commands.entity(task_entity).despawn();
+            }
+        }
+
         let start = Node {
             pos: BlockPos::from(position),
             vertical_vel: VerticalVel::None,
         };
+        pathfinder.last_node = Some(start);
+        pathfinder.ticks_without_progress = 0;
+        pathfinder.stuck_replans = 0;
 
         let world_lock = instance_container
             .get(world_name)
             .expect("Entity tried to pathfind but the entity isn't in a valid world");
-        let end = event.goal.goal_node();
-
-        let goal = event.goal.clone();
-        let entity = event.entity;
-
-        let task = thread_pool.spawn(async move {
-            debug!("start: {start:?}, end: {end:?}");
-
-            let possible_moves: Vec<&dyn moves::Move> = vec![
-                &moves::ForwardMove(CardinalDirection::North),
-                &moves::ForwardMove(CardinalDirection::East),
-                &moves::ForwardMove(CardinalDirection::South),
-                &moves::ForwardMove(CardinalDirection::West),
-                //
-                &moves::AscendMove(CardinalDirection::North),
-                &moves::AscendMove(CardinalDirection::East),
-                &moves::AscendMove(CardinalDirection::South),
-                &moves::AscendMove(CardinalDirection::West),
-                //
-                &moves::DescendMove(CardinalDirection::North),
-                &moves::DescendMove(CardinalDirection::East),
-                &moves::DescendMove(CardinalDirection::South),
-                &moves::DescendMove(CardinalDirection::West),
-                //
-                &moves::DiagonalMove(CardinalDirection::North),
-                &moves::DiagonalMove(CardinalDirection::East),
-                &moves::DiagonalMove(CardinalDirection::South),
-                &moves::DiagonalMove(CardinalDirection::West),
-            ];
-
-            let successors = |node: &Node| {
-                let mut edges = Vec::new();
-
-                let world = world_lock.read();
-                for possible_move in &possible_moves {
-                    edges.push(Edge {
-                        target: possible_move.next_node(node),
-                        cost: possible_move.cost(&world, node),
-                    });
-                }
-                edges
-            };
+        let task = spawn_compute_path(
+            start,
+            event.goal.clone(),
+            event.id,
+            event.entity,
+            world_lock,
+            settings.clone(),
+            inventory.map(hotbar_items).unwrap_or_default(),
+        );
+        commands.spawn(ComputePath {
+            entity: event.entity,
+            goto_id: event.id,
+            task,
+        });
+    }
+}
 
-            // let mut pf = MTDStarLite::new(
-            //     start,
-            //     end,
-            //     |n| goal.heuristic(n),
-            //     successors,
-            //     successors,
-            //     |n| goal.success(n),
-            // );
-
-            let start_time = std::time::Instant::now();
-            let p = a_star(
-                start,
-                |n| goal.heuristic(n),
-                successors,
-                |n| goal.success(n),
-            );
-            let end_time = std::time::Instant::now();
-            debug!("path: {p:?}");
-            debug!("time: {:?}", end_time - start_time);
-
-            // convert the Option<Vec<Node>> to a VecDeque<Node>
-            if let Some(p) = p {
-                let path = p.into_iter().collect::<VecDeque<_>>();
-                // commands.entity(event.entity).insert(Pathfinder { path: p });
-                Some(PathFoundEvent { entity, path })
-            } else {
-                error!("no path found");
-                None
+#[allow(clippy::type_complexity)]
+fn recalculate_path_listener(
+    mut commands: Commands,
+    mut events: EventReader<RecalculatePathEvent>,
+    compute_path_tasks: Query<(Entity, &ComputePath)>,
+    query: Query<(
+        &Position,
+        &WorldName,
+        &Pathfinder,
+        &PathfinderSettings,
+        Option<&InventoryComponent>,
+    )>,
+    instance_container: Res<InstanceContainer>,
+) {
+    // only calculate one path per entity even if we were asked to more than once
+    let entities = events.iter().map(|e| e.entity).collect::<HashSet<_>>();
+    for entity in entities {
+        let Ok((position, world_name, pathfinder, settings, inventory)) = query.get(entity) else {
+            continue;
+        };
+        let (Some(goal), Some(goto_id)) = (pathfinder.goal.clone(), pathfinder.goto_id) else {
+            continue;
+        };
+        let Some(world_lock) = instance_container.get(world_name) else {
+            continue;
+        };
+        // the paths that are still being calculated might go through blocks that
+        // changed, so they're replaced by the new one
+        for (task_entity, compute_path) in &compute_path_tasks {
+            if compute_path.entity == entity {
+                commands.entity(task_entity).despawn();
             }
+        }
+
+        let start = Node {
+            pos: BlockPos::from(position),
+            vertical_vel: VerticalVel::None,
+        };
+        let task = spawn_compute_path(
+            start,
+            goal,
+            goto_id,
+            entity,
+            world_lock,
+            settings.clone(),
+            inventory.map(hotbar_items).unwrap_or_default(),
+        );
+        commands.spawn(ComputePath {
+            entity,
+            goto_id,
+            task,
         });
+    }
+}
+
+/// Start calculating a path from `start` to the goal on another thread.
+fn spawn_compute_path(
+    start: Node,
+    goal: Arc<dyn Goal + Send + Sync>,
+    goto_id: GotoId,
+    entity: Entity,
+    world_lock: Arc<RwLock<Instance>>,
+    settings: PathfinderSettings,
+    hotbar: Vec<ItemSlot>,
+) -> Task<Option<PathFoundEvent>> {
+    let thread_pool = AsyncComputeTaskPool::get();
+
+    thread_pool.spawn(async move {
+        debug!("start: {start:?}");
+
+        let possible_moves = moves::default_moves();
+        let border = world_lock.read().world_border.bounds();
+
+        let successors = |node: &Node| {
+            let mut edges = Vec::new();
 
-        commands.spawn(ComputePath(task));
+            let world = world_lock.read();
+            let ctx = moves::MoveContext {
+                world: &world,
+                settings: &settings,
+                hotbar: &hotbar,
+                border,
+            };
+            for possible_move in &possible_moves {
+                edges.push(Edge {
+                    target: possible_move.next_node(node),
+                    cost: ctx.move_cost(possible_move.as_ref(), node),
+                });
+            }
+            edges
+        };
+
+        // let mut pf = MTDStarLite::new(
+        //     start,
+        //     end,
+        //     |n| goal.heuristic(n),
+        //     successors,
+        //     successors,
+        //     |n| goal.success(n),
+        // );
+
+        let start_time = std::time::Instant::now();
+        let p = a_star(
+            start,
+            |n| goal.heuristic(n),
+            successors,
+            |n| goal.success(n),
+            SearchLimits {
+                max_nodes: settings.max_search_nodes,
+                timeout: settings.max_search_time,
+            },
+        );
+        let end_time = std::time::Instant::now();
+        debug!("path: {p:?}");
+        debug!("time: {:?}", end_time - start_time);
+
+        // convert the Option<Vec<Node>> to a VecDeque<Node>
+        if let Some(p) = p {
+            let path = p.path.into_iter().collect::<VecDeque<_>>();
+            // commands.entity(event.entity).insert(Pathfinder { path: p });
+            Some(PathFoundEvent {
+                entity,
+                goto_id,
+                path,
+                is_partial: p.is_partial,
+            })
+        } else {
+            error!("no path found");
+            None
+        }
+    })
+}
+
+/// When we're getting close to the end of a partial path, start calculating
+/// the next part of it so we can keep walking. More of the world has probably
+/// loaded by now, so we can get further than last time.
+#[allow(clippy::type_complexity)]
+fn continue_partial_paths(
+    mut commands: Commands,
+    compute_path_tasks: Query<&ComputePath>,
+    query: Query<(
+        Entity,
+        &WorldName,
+        &Pathfinder,
+        &PathfinderSettings,
+        Option<&InventoryComponent>,
+    )>,
+    instance_container: Res<InstanceContainer>,
+) {
+    for (entity, world_name, pathfinder, settings, inventory) in &query {
+        if !pathfinder.is_path_partial || pathfinder.path.len() > PLAN_AHEAD_NODES {
+            continue;
+        }
+        if compute_path_tasks.iter().any(|task| task.entity == entity) {
+            continue;
+        }
+        let (Some(goal), Some(goto_id)) = (pathfinder.goal.clone(), pathfinder.goto_id) else {
+            continue;
+        };
+        let Some(start) = pathfinder.path.back().copied().or(pathfinder.last_node) else {
+            continue;
+        };
+        let Some(world_lock) = instance_container.get(world_name) else {
+            continue;
+        };
+        debug!("Calculating the next part of the path from {start:?}");
+        let task = spawn_compute_path(
+            start,
+            goal,
+            goto_id,
+            entity,
+            world_lock,
+            settings.clone(),
+            inventory.map(hotbar_items).unwrap_or_default(),
+        );
+        commands.spawn(ComputePath {
+            entity,
+            goto_id,
+            task,
+        });
     }
 }
 
@@ -198,12 +663,26 @@ fn goto_listener(
 fn handle_tasks(
     mut commands: Commands,
     mut transform_tasks: Query<(Entity, &mut ComputePath)>,
+    mut pathfinders: Query<&mut Pathfinder>,
     mut path_found_events: EventWriter<PathFoundEvent>,
+    mut path_failed_events: EventWriter<PathFailedEvent>,
 ) {
     for (entity, mut task) in &mut transform_tasks {
-        if let Some(optional_path_found_event) = future::block_on(future::poll_once(&mut task.0)) {
+        if let Some(optional_path_found_event) = future::block_on(future::poll_once(&mut task.task))
+        {
             if let Some(path_found_event) = optional_path_found_event {
                 path_found_events.send(path_found_event);
+            } else {
+                // there's no rest of the path to wait for anymore
+                if let Ok(mut pathfinder) = pathfinders.get_mut(task.entity) {
+                    if pathfinder.goto_id == Some(task.goto_id) {
+                        pathfinder.is_path_partial = false;
+                    }
+                }
+                path_failed_events.send(PathFailedEvent {
+                    entity: task.entity,
+                    goto_id: task.goto_id,
+                });
             }
 
             // Task is complete, so remove task component from entity
@@ -218,56 +697,523 @@ fn path_found_listener(mut events: EventReader<PathFoundEvent>, mut query: Query
         let mut pathfinder = query
             .get_mut(event.entity)
             .expect("Path found for an entity that doesn't have a pathfinder");
-        pathfinder.path = event.path.clone();
+        if pathfinder.goto_id != Some(event.goto_id) || pathfinder.goal.is_none() {
+            debug!("Ignoring path for an old goal");
+            continue;
+        }
+        pathfinder.is_path_partial = event.is_partial;
+        // if this is the next part of a partial path, it starts where the
+        // current one ends
+        if pathfinder.path.back().is_some() && pathfinder.path.back() == event.path.front() {
+            pathfinder.path.extend(event.path.iter().skip(1));
+        } else {
+            pathfinder.path = event.path.clone();
+        }
+    }
+}
+
+fn stop_pathfinding_listener(
+    mut events: EventReader<StopPathfindingEvent>,
+    mut query: Query<&mut Pathfinder>,
+    mut walk_events: EventWriter<StartWalkEvent>,
+    mut stop_mining_events: EventWriter<StopMiningBlockEvent>,
+) {
+    for event in events.iter() {
+        let Ok(mut pathfinder) = query.get_mut(event.entity) else {
+            continue;
+        };
+        pathfinder.path.clear();
+        pathfinder.is_path_partial = false;
+        if pathfinder.mining.take().is_some() {
+            stop_mining_events.send(StopMiningBlockEvent {
+                entity: event.entity,
+            });
+        }
+        if pathfinder.goal.take().is_some() {
+            if let Some(goto_id) = pathfinder.goto_id {
+                pathfinder.last_outcome = Some((goto_id, Err(GotoError::Interrupted)));
+            }
+        }
+        walk_events.send(StartWalkEvent {
+            entity: event.entity,
+            direction: WalkDirection::None,
+        });
     }
 }
 
+/// Keep [`Pathfinder::last_outcome`] updated so [`GotoHandle`]s know when
+/// they're done.
+fn record_goto_outcome(
+    mut goal_reached_events: EventReader<GoalReachedEvent>,
+    mut path_failed_events: EventReader<PathFailedEvent>,
+    mut query: Query<&mut Pathfinder>,
+) {
+    let outcomes = goal_reached_events
+        .iter()
+        .map(|e| (e.entity, e.goto_id, Ok::<(), GotoError>(())))
+        .chain(
+            path_failed_events
+                .iter()
+                .map(|e| (e.entity, e.goto_id, Err(GotoError::NoPath))),
+        );
+    for (entity, goto_id, outcome) in outcomes {
+        let Ok(mut pathfinder) = query.get_mut(entity) else {
+            continue;
+        };
+        // outcomes for old gotos don't matter since they were interrupted anyways
+        if pathfinder.goto_id != Some(goto_id) || pathfinder.goal.is_none() {
+            continue;
+        }
+        pathfinder.goal = None;
+        pathfinder.last_outcome = Some((goto_id, outcome));
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn tick_execute_path(
-    mut query: Query<(Entity, &mut Pathfinder, &Position, &Physics)>,
+    mut query: Query<(
+        Entity,
+        &mut Pathfinder,
+        &Position,
+        &Physics,
+        &WorldName,
+        &PathfinderSettings,
+        Option<&InventoryComponent>,
+        Option<&HitResultComponent>,
+    )>,
+    instance_container: Res<InstanceContainer>,
     mut look_at_events: EventWriter<LookAtEvent>,
     mut sprint_events: EventWriter<StartSprintEvent>,
     mut walk_events: EventWriter<StartWalkEvent>,
     mut jump_events: EventWriter<JumpEvent>,
+    mut goal_reached_events: EventWriter<GoalReachedEvent>,
+    mut path_failed_events: EventWriter<PathFailedEvent>,
+    mut start_mining_events: EventWriter<StartMiningBlockEvent>,
+    mut block_interact_events: EventWriter<BlockInteractEvent>,
+    mut set_selected_hotbar_slot_events: EventWriter<SetSelectedHotbarSlotEvent>,
+    mut recalculate_path_events: EventWriter<RecalculatePathEvent>,
+    compute_path_tasks: Query<&ComputePath>,
 ) {
-    for (entity, mut pathfinder, position, physics) in &mut query {
+    for (entity, mut pathfinder, position, physics, world_name, settings, inventory, hit_result) in
+        &mut query
+    {
+        pathfinder.place_cooldown = pathfinder.place_cooldown.saturating_sub(1);
+
         loop {
             let Some(target) = pathfinder.path.front() else {
                 break;
             };
-            let center = target.pos.center();
-            // println!("going to {center:?} (at {pos:?})", pos = bot.entity().pos());
-            look_at_events.send(LookAtEvent {
-                entity,
-                position: center,
-            });
-            debug!(
-                "tick: pathfinder {entity:?}; going to {:?}; currently at {position:?}",
-                target.pos
-            );
-            sprint_events.send(StartSprintEvent {
-                entity,
-                direction: SprintDirection::Forward,
-            });
-            // check if we should jump
-            if target.pos.y > position.y.floor() as i32 {
-                jump_events.send(JumpEvent(entity));
-            }
 
-            if target.is_reached(position, physics) {
+            let Some(world_lock) = instance_container.get(world_name) else {
+                break;
+            };
+            let world = world_lock.read();
+
+            if target.is_reached(position, physics, &world) {
                 // println!("reached target");
-                pathfinder.path.pop_front();
+                pathfinder.last_node = pathfinder.path.pop_front();
+                pathfinder.ticks_without_progress = 0;
+                pathfinder.stuck_replans = 0;
                 if pathfinder.path.is_empty() {
-                    // println!("reached goal");
                     walk_events.send(StartWalkEvent {
                         entity,
                         direction: WalkDirection::None,
                     });
+                    // if the path is partial or we're recalculating it then we wait for the
+                    // rest of it to be calculated
+                    let is_calculating =
+                        compute_path_tasks.iter().any(|task| task.entity == entity);
+                    if !pathfinder.is_path_partial && !is_calculating {
+                        // the goal is gone if we failed to find the rest of a partial path
+                        if let (Some(goto_id), Some(_)) = (pathfinder.goto_id, &pathfinder.goal) {
+                            goal_reached_events.send(GoalReachedEvent { entity, goto_id });
+                        }
+                    }
                 }
                 // tick again, maybe we already reached the next node!
-            } else {
+                continue;
+            }
+
+            let target = target.pos;
+            let current = BlockPos::from(position);
+            let last_pos = pathfinder.last_node.map(|node| node.pos).unwrap_or(current);
+            debug!("tick: pathfinder {entity:?}; going to {target:?}; currently at {position:?}");
+
+            pathfinder.ticks_without_progress += 1;
+            let is_stuck = pathfinder.ticks_without_progress > STUCK_TICKS;
+            // if we got knocked away from the path, wait until we land before figuring out
+            // how to get back
+            let is_off_path = physics.on_ground
+                && !is_near(current, target)
+                && !pathfinder
+                    .last_node
+                    .map(|last_node| is_near(current, last_node.pos))
+                    .unwrap_or_default();
+            if is_stuck {
+                pathfinder.stuck_replans += 1;
+                if pathfinder.stuck_replans > MAX_STUCK_REPLANS {
+                    warn!("Got stuck too many times, giving up on the path");
+                    give_up_on_path(&mut pathfinder, entity, &mut path_failed_events);
+                    walk_events.send(StartWalkEvent {
+                        entity,
+                        direction: WalkDirection::None,
+                    });
+                    break;
+                }
+            }
+            let is_recalculating = compute_path_tasks.iter().any(|task| task.entity == entity);
+            if is_stuck || (is_off_path && !is_recalculating) {
+                debug!("recalculating path (stuck: {is_stuck}, off path: {is_off_path})");
+                recalculate_path(&mut pathfinder, entity, &mut recalculate_path_events);
+            }
+
+            // break the blocks that are in the way
+            if let Some(block_pos) = blocks_in_the_way(current, target)
+                .into_iter()
+                .find(|pos| !moves::is_block_passable(pos, &world))
+            {
+                if !settings.allow_mining {
+                    if !compute_path_tasks.iter().any(|task| task.entity == entity) {
+                        debug!("{block_pos:?} is in the way and we can't mine, recalculating");
+                        recalculate_path(&mut pathfinder, entity, &mut recalculate_path_events);
+                    }
+                    walk_events.send(StartWalkEvent {
+                        entity,
+                        direction: WalkDirection::None,
+                    });
+                    break;
+                }
+                walk_events.send(StartWalkEvent {
+                    entity,
+                    direction: WalkDirection::None,
+                });
+                look_at_events.send(LookAtEvent {
+                    entity,
+                    position: block_pos.center(),
+                });
+                // mining can take a while, so it doesn't count as being stuck
+                pathfinder.ticks_without_progress = 0;
+                if pathfinder.mining != Some(block_pos) {
+                    let block_state = world.chunks.get_block_state(&block_pos).unwrap_or_default();
+                    if let Some(slot) = inventory
+                        .and_then(|inventory| best_hotbar_slot_for_mining(inventory, block_state))
+                    {
+                        set_selected_hotbar_slot_events
+                            .send(SetSelectedHotbarSlotEvent { entity, slot });
+                    }
+                    start_mining_events.send(StartMiningBlockEvent {
+                        entity,
+                        position: block_pos,
+                    });
+                    pathfinder.mining = Some(block_pos);
+                }
                 break;
             }
+            pathfinder.mining = None;
+
+            // place a block to stand on if there isn't one
+            let floor = target.down(1);
+            if moves::needs_floor(&target, &world) {
+                let Some((support, face)) = find_support(&world, floor) else {
+                    warn!("No block to place {floor:?} against, giving up on the path");
+                    give_up_on_path(&mut pathfinder, entity, &mut path_failed_events);
+                    walk_events.send(StartWalkEvent {
+                        entity,
+                        direction: WalkDirection::None,
+                    });
+                    break;
+                };
+                let scaffolding_slot = inventory.and_then(|inventory| {
+                    hotbar_items(inventory)
+                        .iter()
+                        .position(|item| settings.scaffolding_blocks.contains(&item.kind()))
+                });
+                let Some(scaffolding_slot) = scaffolding_slot else {
+                    warn!("Ran out of blocks to place, giving up on the path");
+                    give_up_on_path(&mut pathfinder, entity, &mut path_failed_events);
+                    walk_events.send(StartWalkEvent {
+                        entity,
+                        direction: WalkDirection::None,
+                    });
+                    break;
+                };
+                set_selected_hotbar_slot_events.send(SetSelectedHotbarSlotEvent {
+                    entity,
+                    slot: scaffolding_slot as u8,
+                });
+
+                let face_center = support.center() + face.normal() * 0.5;
+                let is_pillaring = target.x == last_pos.x && target.z == last_pos.z;
+                if is_pillaring {
+                    look_at_events.send(LookAtEvent {
+                        entity,
+                        position: face_center,
+                    });
+                    walk_events.send(StartWalkEvent {
+                        entity,
+                        direction: WalkDirection::None,
+                    });
+                    if physics.on_ground {
+                        jump_events.send(JumpEvent(entity));
+                    }
+                } else {
+                    // we can't sneak, so we walk up to the edge of the block we're standing on
+                    // and stop there to place the block, slowing down as we get close so we
+                    // don't slide off
+                    let direction = Vec3::new(
+                        (target.x - last_pos.x) as f64,
+                        0.,
+                        (target.z - last_pos.z) as f64,
+                    );
+                    let distance_past_center = (**position - last_pos.center()).dot(direction);
+                    let speed = physics.delta.dot(direction);
+                    if distance_past_center + speed * 3. < BRIDGE_EDGE_DISTANCE {
+                        look_at_events.send(LookAtEvent {
+                            entity,
+                            position: target.center(),
+                        });
+                        walk_events.send(StartWalkEvent {
+                            entity,
+                            direction: WalkDirection::Forward,
+                        });
+                        break;
+                    }
+                    look_at_events.send(LookAtEvent {
+                        entity,
+                        position: face_center,
+                    });
+                    walk_events.send(StartWalkEvent {
+                        entity,
+                        direction: WalkDirection::None,
+                    });
+                }
+
+                let is_looking_at_face = hit_result
+                    .map(|hit_result| {
+                        hit_result.block_pos == support && hit_result.direction == face
+                    })
+                    .unwrap_or_default();
+                // when pillaring we also have to wait until we're high enough to fit the
+                // block under us
+                let is_above_floor = !is_pillaring || position.y >= target.y as f64;
+                if is_looking_at_face && is_above_floor && pathfinder.place_cooldown == 0 {
+                    block_interact_events.send(BlockInteractEvent {
+                        entity,
+                        position: support,
+                    });
+                    pathfinder.place_cooldown = 4;
+                }
+                break;
+            }
+
+            look_at_events.send(LookAtEvent {
+                entity,
+                position: target.center(),
+            });
+            let is_vertical = target.x == current.x && target.z == current.z;
+            let is_parkour = (target.x - last_pos.x).abs() > 1 || (target.z - last_pos.z).abs() > 1;
+            if is_vertical {
+                // climbing or swimming straight up or down
+                walk_events.send(StartWalkEvent {
+                    entity,
+                    direction: WalkDirection::None,
+                });
+                if target.y > current.y {
+                    jump_events.send(JumpEvent(entity));
+                }
+            } else if physics.in_water {
+                walk_events.send(StartWalkEvent {
+                    entity,
+                    direction: WalkDirection::Forward,
+                });
+                // stay at the surface
+                if target.y >= current.y {
+                    jump_events.send(JumpEvent(entity));
+                }
+            } else {
+                sprint_events.send(StartSprintEvent {
+                    entity,
+                    direction: SprintDirection::Forward,
+                });
+                if is_parkour {
+                    // jump right before we'd walk off the edge
+                    let next_pos = BlockPos::from(**position + physics.delta);
+                    if physics.on_ground && (next_pos.x != last_pos.x || next_pos.z != last_pos.z) {
+                        jump_events.send(JumpEvent(entity));
+                    }
+                } else if target.y > position.y.floor() as i32 {
+                    jump_events.send(JumpEvent(entity));
+                }
+            }
+            break;
+        }
+    }
+}
+
+/// Whether `pos` is close enough to a node that we're probably still
+/// following the path.
+fn is_near(pos: BlockPos, node: BlockPos) -> bool {
+    (pos.x - node.x).abs() <= 1 && (pos.z - node.z).abs() <= 1 && (pos.y - node.y).abs() <= 2
+}
+
+/// Calculate a new path to the same goal from where we are now, which
+/// replaces the current path once it's found. [`GotoHandle`]s keep waiting
+/// since the goto id stays the same.
+fn recalculate_path(
+    pathfinder: &mut Pathfinder,
+    entity: Entity,
+    recalculate_path_events: &mut EventWriter<RecalculatePathEvent>,
+) {
+    // give the new path some time before we decide that we're stuck again
+    pathfinder.ticks_without_progress = 0;
+    recalculate_path_events.send(RecalculatePathEvent { entity });
+}
+
+/// Recalculate the path if a block changed in a way that makes the rest of
+/// it impossible to follow.
+#[allow(clippy::type_complexity)]
+fn check_path_after_block_updates(
+    mut events: EventReader<BlockUpdateEvent>,
+    mut query: Query<(
+        &mut Pathfinder,
+        &WorldName,
+        &PathfinderSettings,
+        Option<&InventoryComponent>,
+    )>,
+    instance_container: Res<InstanceContainer>,
+    mut recalculate_path_events: EventWriter<RecalculatePathEvent>,
+) {
+    let mut changed_blocks: HashMap<Entity, Vec<BlockPos>> = HashMap::new();
+    for event in events.iter() {
+        changed_blocks
+            .entry(event.entity)
+            .or_default()
+            .push(event.position);
+    }
+
+    for (entity, positions) in changed_blocks {
+        let Ok((mut pathfinder, world_name, settings, inventory)) = query.get_mut(entity) else {
+            continue;
+        };
+        if pathfinder.path.is_empty() || pathfinder.goal.is_none() {
+            continue;
+        }
+        // moves only care about the blocks right next to the nodes
+        let is_near_path = pathfinder
+            .last_node
+            .iter()
+            .chain(pathfinder.path.iter())
+            .any(|node| {
+                positions.iter().any(|pos| {
+                    (pos.x - node.pos.x).abs() <= 1
+                        && (pos.z - node.pos.z).abs() <= 1
+                        && (-1..=2).contains(&(pos.y - node.pos.y))
+                })
+            });
+        if !is_near_path {
+            continue;
         }
+
+        let Some(world_lock) = instance_container.get(world_name) else {
+            continue;
+        };
+        let world = world_lock.read();
+        let hotbar = inventory.map(hotbar_items).unwrap_or_default();
+        let ctx = moves::MoveContext {
+            world: &world,
+            settings,
+            hotbar: &hotbar,
+            border: world.world_border.bounds(),
+        };
+        let possible_moves = moves::default_moves();
+
+        let mut from = pathfinder.last_node;
+        let is_path_valid = pathfinder.path.iter().all(|to| {
+            let is_valid = from
+                .map(|from| moves::edge_cost(&ctx, &possible_moves, &from, to).is_finite())
+                .unwrap_or(true);
+            from = Some(*to);
+            is_valid
+        });
+        drop(world);
+
+        if !is_path_valid {
+            debug!("A block update made the path impossible, recalculating");
+            recalculate_path(&mut pathfinder, entity, &mut recalculate_path_events);
+        }
+    }
+}
+
+/// The blocks that have to be broken to move from `current` to `target`, from
+/// top to bottom. This includes the blocks above us if we're going up, and
+/// the blocks in the target position.
+fn blocks_in_the_way(current: BlockPos, target: BlockPos) -> Vec<BlockPos> {
+    let mut blocks = Vec::new();
+    if target.y > current.y {
+        // make room to jump
+        blocks.push(current.up(2));
+    }
+    let top = i32::max(target.y + 1, current.y + 1);
+    for y in (target.y..=top).rev() {
+        let pos = BlockPos::new(target.x, y, target.z);
+        // we're already in these blocks so they must be passable
+        if pos == current || pos == current.up(1) {
+            continue;
+        }
+        blocks.push(pos);
+    }
+    blocks
+}
+
+/// Find a solid block that we can place a block against to fill `pos`, and
+/// the face of that block that we'd have to click.
+fn find_support(world: &Instance, pos: BlockPos) -> Option<(BlockPos, Direction)> {
+    if moves::is_block_solid(&pos.down(1), world) {
+        return Some((pos.down(1), Direction::Up));
+    }
+    [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ]
+    .into_iter()
+    .find_map(|face| {
+        // the support is on the opposite side of the face that we click
+        let support = pos - BlockPos::from(face.normal());
+        moves::is_block_solid(&support, world).then_some((support, face))
+    })
+}
+
+fn hotbar_items(inventory: &InventoryComponent) -> Vec<ItemSlot> {
+    let menu = &inventory.inventory_menu;
+    menu.slots()[menu.hotbar_slots_range()].to_vec()
+}
+
+/// The hotbar slot with the item that mines the block the fastest, or `None`
+/// if nothing in the hotbar is better than an empty hand.
+fn best_hotbar_slot_for_mining(
+    inventory: &InventoryComponent,
+    block_state: azalea_block::BlockState,
+) -> Option<u8> {
+    let menu = &inventory.inventory_menu;
+    let hotbar = menu.hotbar_slots_range();
+    let slot = crate::auto_tool::best_tool_in_slots(menu, hotbar.clone(), block_state)?;
+    Some((slot - hotbar.start()) as u8)
+}
+
+/// Forget about the current path and send a [`PathFailedEvent`], for when we
+/// realize we can't follow it.
+fn give_up_on_path(
+    pathfinder: &mut Pathfinder,
+    entity: Entity,
+    path_failed_events: &mut EventWriter<PathFailedEvent>,
+) {
+    pathfinder.path.clear();
+    pathfinder.is_path_partial = false;
+    pathfinder.mining = None;
+    if let Some(goto_id) = pathfinder.goto_id {
+        path_failed_events.send(PathFailedEvent { entity, goto_id });
     }
 }
 
@@ -287,19 +1233,11 @@ pub struct Node {
     pub vertical_vel: VerticalVel,
 }
 
-pub trait Goal {
-    fn heuristic(&self, n: &Node) -> f32;
-    fn success(&self, n: &Node) -> bool;
-    // TODO: this should be removed and mtdstarlite should stop depending on
-    // being given a goal node
-    fn goal_node(&self) -> Node;
-}
-
 impl Node {
     /// Returns whether the entity is at the node and should start going to the
     /// next node.
     #[must_use]
-    pub fn is_reached(&self, position: &Position, physics: &Physics) -> bool {
+    pub fn is_reached(&self, position: &Position, physics: &Physics, world: &Instance) -> bool {
         // println!(
         //     "entity.delta.y: {} {:?}=={:?}, self.vertical_vel={:?}",
         //     entity.delta.y,
@@ -310,35 +1248,310 @@ impl Node {
         BlockPos::from(position) == self.pos
             && match self.vertical_vel {
                 VerticalVel::NoneMidair => physics.delta.y > -0.1 && physics.delta.y < 0.1,
-                VerticalVel::None => physics.on_ground,
+                // we don't have to be on the ground if we're swimming or holding onto a
+                // ladder
+                VerticalVel::None => {
+                    // standing on the edge of the block behind us while we place the floor
+                    // doesn't count
+                    (physics.on_ground && !moves::needs_floor(&self.pos, world))
+                        || physics.in_water
+                        || azalea_physics::on_climbable(world, position)
+                }
                 VerticalVel::FallingLittle => physics.delta.y < -0.1,
             }
     }
 }
 
-pub struct BlockPosGoal {
-    pub pos: BlockPos,
-}
-impl Goal for BlockPosGoal {
-    fn heuristic(&self, n: &Node) -> f32 {
-        let dx = (self.pos.x - n.pos.x) as f32;
-        let dy = (self.pos.y - n.pos.y) as f32;
-        let dz = (self.pos.z - n.pos.z) as f32;
-        dx * dx + dy * dy + dz * dz
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azalea_core::{ChunkPos, ResourceLocation};
+    use azalea_world::{entity::EntityBundle, Chunk, PartialInstance};
+    use bevy_ecs::event::Events;
+    use bevy_tasks::TaskPool;
+    use uuid::Uuid;
+
+    fn make_test_app() -> App {
+        let mut app = App::new();
+        app.add_event::<LookAtEvent>()
+            .add_event::<StartSprintEvent>()
+            .add_event::<StartWalkEvent>()
+            .add_event::<JumpEvent>()
+            .add_event::<GoalReachedEvent>()
+            .add_event::<PathFoundEvent>()
+            .add_event::<PathFailedEvent>()
+            .add_event::<StartMiningBlockEvent>()
+            .add_event::<BlockInteractEvent>()
+            .add_event::<SetSelectedHotbarSlotEvent>()
+            .add_event::<RecalculatePathEvent>()
+            .add_system(path_found_listener)
+            .add_system(tick_execute_path.after(path_found_listener))
+            .init_resource::<InstanceContainer>();
+        app
+    }
+
+    fn last_walk_direction(app: &App) -> Option<WalkDirection> {
+        let events = app.world.resource::<Events<StartWalkEvent>>();
+        let mut reader = events.get_reader();
+        let last_event = reader.iter(events).last();
+        last_event.map(|event| event.direction)
+    }
+
+    /// Add a world with stone at the given positions. The world is unloaded
+    /// when the returned partial world and lock are dropped.
+    fn insert_world(
+        app: &mut App,
+        stone: &[BlockPos],
+    ) -> (ResourceLocation, PartialInstance, Arc<RwLock<Instance>>) {
+        let world_name = ResourceLocation::new("minecraft:overworld");
+        let world_lock =
+            app.world
+                .resource_mut::<InstanceContainer>()
+                .insert(world_name.clone(), 384, -64);
+        let mut partial_world = PartialInstance::default();
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut world_lock.write().chunks,
+        );
+        for pos in stone {
+            partial_world.chunks.set_block_state(
+                pos,
+                azalea_registry::Block::Stone.into(),
+                &mut world_lock.write().chunks,
+            );
+        }
+        (world_name, partial_world, world_lock)
     }
-    fn success(&self, n: &Node) -> bool {
-        n.pos == self.pos
+
+    /// Spawn a player that's standing at the given position.
+    fn spawn_player(
+        app: &mut App,
+        world_name: ResourceLocation,
+        position: Vec3,
+        pathfinder: Pathfinder,
+    ) -> Entity {
+        let mut bundle = EntityBundle::new(
+            Uuid::nil(),
+            position,
+            azalea_registry::EntityKind::Player,
+            world_name,
+        );
+        bundle.physics.on_ground = true;
+        app.world
+            .spawn((bundle, pathfinder, PathfinderSettings::default()))
+            .id()
     }
-    fn goal_node(&self) -> Node {
+
+    fn node(x: i32, y: i32, z: i32) -> Node {
         Node {
-            pos: self.pos,
+            pos: BlockPos::new(x, y, z),
             vertical_vel: VerticalVel::None,
         }
     }
-}
 
-impl From<BlockPos> for BlockPosGoal {
-    fn from(pos: BlockPos) -> Self {
-        Self { pos }
+    #[test]
+    fn test_wait_for_rest_of_partial_path() {
+        let mut app = make_test_app();
+        let (world_name, _partial_world, _world_lock) =
+            insert_world(&mut app, &[BlockPos::new(0, 69, 0)]);
+
+        let goto_id = GotoId::new();
+        let mut bundle = EntityBundle::new(
+            Uuid::nil(),
+            Vec3::new(0.5, 70., 0.5),
+            azalea_registry::EntityKind::Player,
+            world_name,
+        );
+        bundle.physics.on_ground = true;
+        let entity = app
+            .world
+            .spawn((
+                bundle,
+                Pathfinder {
+                    path: VecDeque::from([node(0, 70, 0)]),
+                    is_path_partial: true,
+                    goal: Some(Arc::new(BlockPosGoal::from(BlockPos::new(0, 70, 5)))),
+                    goto_id: Some(goto_id),
+                    ..Default::default()
+                },
+                PathfinderSettings::default(),
+            ))
+            .id();
+        // the next part of the path is still being calculated
+        let task = AsyncComputeTaskPool::init(TaskPool::default).spawn(future::pending());
+        let task_entity = app
+            .world
+            .spawn(ComputePath {
+                entity,
+                goto_id,
+                task,
+            })
+            .id();
+
+        // we're at the end of the part of the path that we have, but we're not at
+        // the goal yet
+        app.update();
+        let pathfinder = app.world.get::<Pathfinder>(entity).unwrap();
+        assert!(pathfinder.path.is_empty());
+        assert!(pathfinder.is_path_partial);
+        assert!(app.world.resource::<Events<GoalReachedEvent>>().is_empty());
+
+        // the rest of the path is found and it ends where we are now
+        app.world.despawn(task_entity);
+        app.world.send_event(PathFoundEvent {
+            entity,
+            goto_id,
+            path: VecDeque::from([node(0, 70, 0)]),
+            is_partial: false,
+        });
+        app.update();
+        let pathfinder = app.world.get::<Pathfinder>(entity).unwrap();
+        assert!(!pathfinder.is_path_partial);
+        assert_eq!(app.world.resource::<Events<GoalReachedEvent>>().len(), 1);
+    }
+
+    #[test]
+    fn test_stop_at_edge_when_bridging() {
+        let mut app = make_test_app();
+        let (world_name, _partial_world, _world_lock) =
+            insert_world(&mut app, &[BlockPos::new(0, 69, 0)]);
+
+        let mut bundle = EntityBundle::new(
+            Uuid::nil(),
+            Vec3::new(0.5, 70., 0.8),
+            azalea_registry::EntityKind::Player,
+            world_name,
+        );
+        bundle.physics.on_ground = true;
+        let mut inventory = InventoryComponent::default();
+        *inventory.inventory_menu.slot_mut(36).unwrap() = ItemSlot::new(Item::Dirt, 64);
+        let entity = app
+            .world
+            .spawn((
+                bundle,
+                Pathfinder {
+                    path: VecDeque::from([node(0, 70, 1)]),
+                    last_node: Some(node(0, 70, 0)),
+                    ..Default::default()
+                },
+                PathfinderSettings::default(),
+                inventory,
+            ))
+            .id();
+
+        // there's nothing under the next node, so we walk towards the edge
+        app.update();
+        assert!(matches!(
+            last_walk_direction(&app),
+            Some(WalkDirection::Forward)
+        ));
+        app.world.resource_mut::<Events<StartWalkEvent>>().clear();
+        app.world.resource_mut::<Events<LookAtEvent>>().clear();
+
+        // we're past the edge now, but we're still standing on it so we stop and look
+        // at the side of the block that we're placing against
+        **app.world.get_mut::<Position>(entity).unwrap() = Vec3::new(0.5, 70., 1.2);
+        app.update();
+        assert!(matches!(
+            last_walk_direction(&app),
+            Some(WalkDirection::None)
+        ));
+        let look_at_events = app.world.resource::<Events<LookAtEvent>>();
+        let mut look_at_reader = look_at_events.get_reader();
+        let last_look_at = look_at_reader.iter(look_at_events).last().unwrap();
+        assert_eq!(last_look_at.position, Vec3::new(0.5, 69.5, 1.));
+        // and we didn't count the node as reached just because we're in it
+        assert_eq!(app.world.get::<Pathfinder>(entity).unwrap().path.len(), 1);
+    }
+
+    #[test]
+    fn test_recalculate_when_off_path() {
+        let mut app = make_test_app();
+        let (world_name, _partial_world, _world_lock) = insert_world(
+            &mut app,
+            &[BlockPos::new(0, 69, 0), BlockPos::new(0, 69, 10)],
+        );
+        // we got knocked far away from the next node
+        let entity = spawn_player(
+            &mut app,
+            world_name,
+            Vec3::new(0.5, 70., 0.5),
+            Pathfinder {
+                path: VecDeque::from([node(0, 70, 10)]),
+                last_node: Some(node(0, 70, 9)),
+                goal: Some(Arc::new(BlockPosGoal::from(BlockPos::new(0, 70, 10)))),
+                goto_id: Some(GotoId::new()),
+                ..Default::default()
+            },
+        );
+
+        app.update();
+        assert_eq!(
+            app.world.resource::<Events<RecalculatePathEvent>>().len(),
+            1
+        );
+        assert!(app.world.resource::<Events<PathFailedEvent>>().is_empty());
+        let pathfinder = app.world.get::<Pathfinder>(entity).unwrap();
+        assert_eq!(pathfinder.path.len(), 1);
+        assert!(pathfinder.goal.is_some());
+    }
+
+    #[test]
+    fn test_give_up_when_stuck() {
+        let mut app = make_test_app();
+        let (world_name, _partial_world, _world_lock) = insert_world(
+            &mut app,
+            &[BlockPos::new(0, 69, 0), BlockPos::new(0, 69, 1)],
+        );
+        // nothing in the test app moves us, so we never reach the next node
+        let entity = spawn_player(
+            &mut app,
+            world_name,
+            Vec3::new(0.5, 70., 0.5),
+            Pathfinder {
+                path: VecDeque::from([node(0, 70, 1)]),
+                goal: Some(Arc::new(BlockPosGoal::from(BlockPos::new(0, 70, 1)))),
+                goto_id: Some(GotoId::new()),
+                ..Default::default()
+            },
+        );
+
+        let mut recalculate_path_reader = app
+            .world
+            .resource::<Events<RecalculatePathEvent>>()
+            .get_reader();
+        let mut path_failed_reader = app.world.resource::<Events<PathFailedEvent>>().get_reader();
+        let mut recalculate_path_count = 0;
+        let mut path_failed_count = 0;
+        for tick in 1..=(STUCK_TICKS + 1) * (MAX_STUCK_REPLANS + 1) {
+            app.update();
+            recalculate_path_count += recalculate_path_reader
+                .iter(app.world.resource::<Events<RecalculatePathEvent>>())
+                .count();
+            path_failed_count += path_failed_reader
+                .iter(app.world.resource::<Events<PathFailedEvent>>())
+                .count();
+            // we recalculate the path every time we get stuck
+            assert_eq!(
+                recalculate_path_count as u32,
+                (tick / (STUCK_TICKS + 1)).min(MAX_STUCK_REPLANS)
+            );
+        }
+        // and give up after getting stuck too many times
+        assert_eq!(path_failed_count, 1);
+        assert!(app.world.get::<Pathfinder>(entity).unwrap().path.is_empty());
+    }
+
+    #[test]
+    fn test_best_hotbar_slot_for_mining() {
+        let mut inventory = InventoryComponent::default();
+        let stone = azalea_registry::Block::Stone.into();
+        *inventory.inventory_menu.slot_mut(36).unwrap() = ItemSlot::new(Item::Dirt, 64);
+        // dirt is no better than an empty hand
+        assert_eq!(best_hotbar_slot_for_mining(&inventory, stone), None);
+
+        *inventory.inventory_menu.slot_mut(39).unwrap() = ItemSlot::new(Item::IronPickaxe, 1);
+        assert_eq!(best_hotbar_slot_for_mining(&inventory, stone), Some(3));
     }
 }
@@ -1,22 +1,34 @@
-use super::{Node, VerticalVel};
+use super::{Node, PathfinderSettings, VerticalVel};
+use azalea_block::{Block, BlockState};
+use azalea_client::{interact, mining};
 use azalea_core::{BlockPos, CardinalDirection};
-use azalea_physics::collision::{self, BlockWithShape};
-use azalea_world::Instance;
+use azalea_inventory::ItemSlot;
+use azalea_physics::{
+    collision::{self, BlockWithShape},
+    is_climbable, is_water,
+};
+use azalea_world::{world_border::BorderBounds, Instance};
+
+/// whether this block state is passable
+fn is_state_passable(block: BlockState) -> bool {
+    // ladders have a hitbox but we can still walk into them
+    block.shape() == &collision::empty_shape() || is_climbable(block)
+}
 
 /// whether this block is passable
-fn is_block_passable(pos: &BlockPos, world: &Instance) -> bool {
+pub(super) fn is_block_passable(pos: &BlockPos, world: &Instance) -> bool {
     if let Some(block) = world.chunks.get_block_state(pos) {
-        block.shape() == &collision::empty_shape()
+        is_state_passable(block)
     } else {
         false
     }
 }
 
 /// whether this block has a solid hitbox (i.e. we can stand on it)
-fn is_block_solid(pos: &BlockPos, world: &Instance) -> bool {
+pub(super) fn is_block_solid(pos: &BlockPos, world: &Instance) -> bool {
     if let Some(block) = world.chunks.get_block_state(pos) {
         // block.shape() == &collision::block_shape()
-        block.shape() != &collision::empty_shape()
+        block.shape() != &collision::empty_shape() && !is_climbable(block)
     } else {
         false
     }
@@ -34,11 +46,187 @@ fn is_standable(pos: &BlockPos, world: &Instance) -> bool {
     is_block_solid(&pos.down(1), world) && is_passable(pos, world)
 }
 
-const JUMP_COST: f32 = 0.5;
-const WALK_ONE_BLOCK_COST: f32 = 1.0;
+fn is_liquid(pos: &BlockPos, world: &Instance) -> bool {
+    let Some(block) = world.chunks.get_block_state(pos) else {
+        return false;
+    };
+    matches!(Box::<dyn Block>::from(block).id(), "water" | "lava")
+}
+
+fn is_water_at(pos: &BlockPos, world: &Instance) -> bool {
+    world
+        .chunks
+        .get_block_state(pos)
+        .map(is_water)
+        .unwrap_or(false)
+}
+
+fn is_climbable_at(pos: &BlockPos, world: &Instance) -> bool {
+    world
+        .chunks
+        .get_block_state(pos)
+        .map(is_climbable)
+        .unwrap_or(false)
+}
+
+/// Whether we'd have to have a solid block under this position to stay in
+/// it, which isn't the case if we're climbing or swimming.
+pub(super) fn needs_floor(pos: &BlockPos, world: &Instance) -> bool {
+    !is_block_solid(&pos.down(1), world) && !is_water_at(pos, world) && !is_climbable_at(pos, world)
+}
+
+pub(super) const JUMP_COST: f32 = 0.5;
+pub(super) const WALK_ONE_BLOCK_COST: f32 = 1.0;
+pub(super) const DIAGONAL_COST: f32 = WALK_ONE_BLOCK_COST * 1.4;
+pub(super) const ASCEND_COST: f32 = WALK_ONE_BLOCK_COST + JUMP_COST;
+pub(super) const DESCEND_COST: f32 = WALK_ONE_BLOCK_COST;
+/// The cost of waiting for one tick, which is roughly how long it takes to
+/// sprint a quarter of a block.
+pub(super) const TICK_COST: f32 = WALK_ONE_BLOCK_COST / 4.;
+/// The cost of mining a block on top of the time it takes to break it, since
+/// we have to stop and look at it first.
+pub(super) const MINE_BLOCK_COST: f32 = WALK_ONE_BLOCK_COST;
+/// The cost of placing a block. This is high since it uses up one of our
+/// blocks.
+pub(super) const PLACE_BLOCK_COST: f32 = WALK_ONE_BLOCK_COST * 2.;
+// pillaring must never be cheaper than ascending, or the goal heuristics
+// would overestimate
+pub(super) const PILLAR_COST: f32 = JUMP_COST + PLACE_BLOCK_COST;
+/// We climb ladders at about 0.12 blocks per tick.
+pub(super) const CLIMB_UP_COST: f32 = TICK_COST * 9.;
+/// We slide down ladders at 0.15 blocks per tick.
+pub(super) const CLIMB_DOWN_COST: f32 = TICK_COST * 7.;
+/// Swimming is much slower than walking, about 0.1 blocks per tick.
+pub(super) const SWIM_ONE_BLOCK_COST: f32 = TICK_COST * 10.;
+pub(super) const SWIM_UP_COST: f32 = TICK_COST * 6.;
+/// The extra cost of jumping over a gap for every block of the gap, since
+/// longer jumps are more likely to fail.
+pub(super) const PARKOUR_GAP_COST: f32 = JUMP_COST;
+/// The cost of losing half a heart from fall damage.
+pub(super) const FALL_DAMAGE_COST: f32 = WALK_ONE_BLOCK_COST * 4.;
+/// The furthest we'll ever fall in one move, even into water.
+pub(super) const MAX_FALL_DISTANCE: i32 = 16;
+/// How many blocks we can fall without taking any damage.
+const SAFE_FALL_DISTANCE: i32 = 3;
+
+/// Everything that moves need to know to calculate their cost.
+pub struct MoveContext<'a> {
+    pub world: &'a Instance,
+    pub settings: &'a PathfinderSettings,
+    /// The items in our hotbar, used for figuring out how fast we can mine
+    /// blocks and whether we have any blocks to place.
+    pub hotbar: &'a [ItemSlot],
+    /// The edges of the world border, which we can't walk past. This is only
+    /// calculated once per search since it can change over time.
+    pub border: BorderBounds,
+}
+
+impl MoveContext<'_> {
+    /// The cost of doing this move from the node, or infinity if it'd take us
+    /// past the world border.
+    pub fn move_cost(&self, possible_move: &dyn Move, node: &Node) -> f32 {
+        if !self
+            .border
+            .is_within_bounds(&possible_move.next_node(node).pos)
+        {
+            return f32::INFINITY;
+        }
+        possible_move.cost(self, node)
+    }
+
+    /// Whether we're allowed to place blocks and have something to place.
+    fn can_place_blocks(&self) -> bool {
+        self.settings.allow_placing
+            && self
+                .hotbar
+                .iter()
+                .any(|item| self.settings.scaffolding_blocks.contains(&item.kind()))
+    }
+
+    /// The cost of making this block passable. This is 0 if it's already
+    /// passable and infinity if we can't mine it.
+    fn mine_cost(&self, pos: &BlockPos) -> f32 {
+        let Some(block) = self.world.chunks.get_block_state(pos) else {
+            return f32::INFINITY;
+        };
+        if is_state_passable(block) {
+            return 0.;
+        }
+        // don't let water or lava flow onto us
+        if !self.settings.allow_mining || is_liquid(&pos.up(1), self.world) {
+            return f32::INFINITY;
+        }
+        self.hotbar
+            .iter()
+            .filter_map(|item| mining::ticks_to_destroy(block, item.kind()))
+            .min()
+            .map(|ticks| MINE_BLOCK_COST + ticks as f32 * TICK_COST)
+            .unwrap_or(f32::INFINITY)
+    }
+
+    /// The cost of making sure there's a solid block at this position that
+    /// we can stand on. This is 0 if there's already one there and infinity if
+    /// we can't place a block.
+    fn place_cost(&self, pos: &BlockPos) -> f32 {
+        if is_block_solid(pos, self.world) {
+            0.
+        } else if self.can_place_blocks()
+            && self
+                .world
+                .chunks
+                .get_block_state(pos)
+                .map_or(false, interact::is_replaceable)
+        {
+            PLACE_BLOCK_COST
+        } else {
+            f32::INFINITY
+        }
+    }
+}
+
+const DIRECTIONS: [CardinalDirection; 4] = [
+    CardinalDirection::North,
+    CardinalDirection::East,
+    CardinalDirection::South,
+    CardinalDirection::West,
+];
+
+/// Every move that the pathfinder can make.
+pub fn default_moves() -> Vec<Box<dyn Move>> {
+    let mut moves: Vec<Box<dyn Move>> = Vec::new();
+    for direction in DIRECTIONS {
+        moves.push(Box::new(ForwardMove(direction)));
+        moves.push(Box::new(AscendMove(direction)));
+        moves.push(Box::new(DescendMove(direction)));
+        moves.push(Box::new(DiagonalMove(direction)));
+        moves.push(Box::new(SwimMove(direction)));
+        for gap in 1..=3 {
+            moves.push(Box::new(ParkourMove(direction, gap)));
+        }
+        for height in 2..=MAX_FALL_DISTANCE {
+            moves.push(Box::new(FallMove(direction, height)));
+        }
+    }
+    moves.push(Box::new(PillarMove));
+    moves.push(Box::new(DownMove));
+    moves.push(Box::new(ClimbUpMove));
+    moves.push(Box::new(ClimbDownMove));
+    moves.push(Box::new(SwimUpMove));
+    moves
+}
+
+/// The cheapest way to get from one node to another with a single move, or
+/// infinity if it's impossible.
+pub fn edge_cost(ctx: &MoveContext, moves: &[Box<dyn Move>], from: &Node, to: &Node) -> f32 {
+    moves
+        .iter()
+        .filter(|possible_move| possible_move.next_node(from) == *to)
+        .map(|possible_move| ctx.move_cost(possible_move.as_ref(), from))
+        .fold(f32::INFINITY, f32::min)
+}
 
 pub trait Move: Send + Sync {
-    fn cost(&self, world: &Instance, node: &Node) -> f32;
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32;
     /// Returns by how much the entity's position should be changed when this
     /// move is executed.
     fn offset(&self) -> BlockPos;
@@ -50,33 +238,37 @@ pub trait Move: Send + Sync {
     }
 }
 
+/// Walk forward, breaking the blocks in the way and placing a block to walk
+/// on if necessary.
 pub struct ForwardMove(pub CardinalDirection);
 impl Move for ForwardMove {
-    fn cost(&self, world: &Instance, node: &Node) -> f32 {
-        if is_standable(&(node.pos + self.offset()), world)
-            && node.vertical_vel == VerticalVel::None
-        {
-            WALK_ONE_BLOCK_COST
-        } else {
-            f32::INFINITY
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        if node.vertical_vel != VerticalVel::None {
+            return f32::INFINITY;
         }
+        let target = node.pos + self.offset();
+        WALK_ONE_BLOCK_COST
+            + ctx.mine_cost(&target.up(1))
+            + ctx.mine_cost(&target)
+            + ctx.place_cost(&target.down(1))
     }
     fn offset(&self) -> BlockPos {
         BlockPos::new(self.0.x(), 0, self.0.z())
     }
 }
 
+/// Jump up one block, breaking the blocks in the way if necessary.
 pub struct AscendMove(pub CardinalDirection);
 impl Move for AscendMove {
-    fn cost(&self, world: &Instance, node: &Node) -> f32 {
-        if node.vertical_vel == VerticalVel::None
-            && is_block_passable(&node.pos.up(2), world)
-            && is_standable(&(node.pos + self.offset()), world)
-        {
-            WALK_ONE_BLOCK_COST + JUMP_COST
-        } else {
-            f32::INFINITY
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        if node.vertical_vel != VerticalVel::None || !is_block_solid(&target.down(1), ctx.world) {
+            return f32::INFINITY;
         }
+        ASCEND_COST
+            + ctx.mine_cost(&node.pos.up(2))
+            + ctx.mine_cost(&target.up(1))
+            + ctx.mine_cost(&target)
     }
     fn offset(&self) -> BlockPos {
         BlockPos::new(self.0.x(), 1, self.0.z())
@@ -88,18 +280,20 @@ impl Move for AscendMove {
         }
     }
 }
+/// Walk down one block, breaking the blocks in the way if necessary.
 pub struct DescendMove(pub CardinalDirection);
 impl Move for DescendMove {
-    fn cost(&self, world: &Instance, node: &Node) -> f32 {
-        // check whether 3 blocks vertically forward are passable
-        if node.vertical_vel == VerticalVel::None
-            && is_standable(&(node.pos + self.offset()), world)
-            && is_block_passable(&(node.pos + self.offset().up(2)), world)
-        {
-            WALK_ONE_BLOCK_COST
-        } else {
-            f32::INFINITY
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        // we can also step down into water or onto a ladder
+        if node.vertical_vel != VerticalVel::None || needs_floor(&target, ctx.world) {
+            return f32::INFINITY;
         }
+        // the 3 blocks in front of us have to be passable
+        DESCEND_COST
+            + ctx.mine_cost(&target.up(2))
+            + ctx.mine_cost(&target.up(1))
+            + ctx.mine_cost(&target)
     }
     fn offset(&self) -> BlockPos {
         BlockPos::new(self.0.x(), -1, self.0.z())
@@ -113,7 +307,8 @@ impl Move for DescendMove {
 }
 pub struct DiagonalMove(pub CardinalDirection);
 impl Move for DiagonalMove {
-    fn cost(&self, world: &Instance, node: &Node) -> f32 {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let world = ctx.world;
         if node.vertical_vel != VerticalVel::None {
             return f32::INFINITY;
         }
@@ -133,7 +328,7 @@ impl Move for DiagonalMove {
         if !is_standable(&(node.pos + self.offset()), world) {
             return f32::INFINITY;
         }
-        WALK_ONE_BLOCK_COST * 1.4
+        DIAGONAL_COST
     }
     fn offset(&self) -> BlockPos {
         let right = self.0.right();
@@ -147,6 +342,195 @@ impl Move for DiagonalMove {
     }
 }
 
+/// Jump and place a block below us.
+pub struct PillarMove;
+impl Move for PillarMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        if node.vertical_vel != VerticalVel::None
+            || !ctx.can_place_blocks()
+            || !ctx
+                .world
+                .chunks
+                .get_block_state(&node.pos)
+                .map_or(false, interact::is_replaceable)
+        {
+            return f32::INFINITY;
+        }
+        PILLAR_COST + ctx.mine_cost(&node.pos.up(2))
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(0, 1, 0)
+    }
+}
+
+/// Mine the block we're standing on and fall into its place.
+pub struct DownMove;
+impl Move for DownMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        if node.vertical_vel != VerticalVel::None || !is_block_solid(&target.down(1), ctx.world) {
+            return f32::INFINITY;
+        }
+        DESCEND_COST + ctx.mine_cost(&target)
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(0, -1, 0)
+    }
+}
+
+/// Sprint and jump over a gap that's 1 to 3 blocks wide, landing at the same
+/// height that we jumped from.
+pub struct ParkourMove(pub CardinalDirection, pub i32);
+impl Move for ParkourMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let world = ctx.world;
+        let gap = self.1;
+        if node.vertical_vel != VerticalVel::None
+            || !is_block_solid(&node.pos.down(1), world)
+            || !is_block_passable(&node.pos.up(2), world)
+        {
+            return f32::INFINITY;
+        }
+        for i in 1..=gap {
+            let pos = node.pos + BlockPos::new(self.0.x() * i, 0, self.0.z() * i);
+            // if there's something to stand on then it's not a gap, and we need
+            // room for our head since we're jumping
+            if is_block_solid(&pos.down(1), world)
+                || !is_passable(&pos, world)
+                || !is_block_passable(&pos.up(2), world)
+            {
+                return f32::INFINITY;
+            }
+        }
+        if !is_standable(&(node.pos + self.offset()), world) {
+            return f32::INFINITY;
+        }
+        WALK_ONE_BLOCK_COST * (gap + 1) as f32 + JUMP_COST + PARKOUR_GAP_COST * gap as f32
+    }
+    fn offset(&self) -> BlockPos {
+        let distance = self.1 + 1;
+        BlockPos::new(self.0.x() * distance, 0, self.0.z() * distance)
+    }
+}
+
+/// Walk off an edge and fall more than one block, taking fall damage if
+/// [`PathfinderSettings::max_fall_damage`] allows it.
+pub struct FallMove(pub CardinalDirection, pub i32);
+impl Move for FallMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let world = ctx.world;
+        let height = self.1;
+        if node.vertical_vel != VerticalVel::None {
+            return f32::INFINITY;
+        }
+        let edge = node.pos + BlockPos::new(self.0.x(), 0, self.0.z());
+        if !is_block_passable(&edge.up(1), world) {
+            return f32::INFINITY;
+        }
+        // we can't stop on the way down, so all of these have to be air
+        for y in 0..height {
+            let pos = edge.down(y);
+            if !is_block_passable(&pos, world)
+                || is_liquid(&pos, world)
+                || is_climbable_at(&pos, world)
+            {
+                return f32::INFINITY;
+            }
+        }
+        let target = node.pos + self.offset();
+        let damage = if is_water_at(&target, world) {
+            0
+        } else if is_block_passable(&target, world)
+            && !is_liquid(&target, world)
+            && is_block_solid(&target.down(1), world)
+        {
+            (height - SAFE_FALL_DISTANCE).max(0) as u32
+        } else {
+            return f32::INFINITY;
+        };
+        if damage > ctx.settings.max_fall_damage {
+            return f32::INFINITY;
+        }
+        WALK_ONE_BLOCK_COST + DESCEND_COST * height as f32 + FALL_DAMAGE_COST * damage as f32
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(self.0.x(), -self.1, self.0.z())
+    }
+}
+
+/// Climb up a ladder or vines.
+pub struct ClimbUpMove;
+impl Move for ClimbUpMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        if node.vertical_vel != VerticalVel::None
+            || !is_climbable_at(&node.pos, ctx.world)
+            || !is_climbable_at(&target, ctx.world)
+            || !is_block_passable(&target.up(1), ctx.world)
+        {
+            return f32::INFINITY;
+        }
+        CLIMB_UP_COST
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(0, 1, 0)
+    }
+}
+
+/// Climb down a ladder or vines. The block we're in doesn't have to be
+/// climbable, so this also works for getting onto the top of a ladder.
+pub struct ClimbDownMove;
+impl Move for ClimbDownMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        if node.vertical_vel != VerticalVel::None || !is_climbable_at(&target, ctx.world) {
+            return f32::INFINITY;
+        }
+        CLIMB_DOWN_COST
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(0, -1, 0)
+    }
+}
+
+/// Swim forward along the surface of the water.
+pub struct SwimMove(pub CardinalDirection);
+impl Move for SwimMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        // we can't swim down (yet) so we stay at the surface
+        if node.vertical_vel != VerticalVel::None
+            || !is_water_at(&target, ctx.world)
+            || !is_block_passable(&target.up(1), ctx.world)
+            || is_liquid(&target.up(1), ctx.world)
+        {
+            return f32::INFINITY;
+        }
+        SWIM_ONE_BLOCK_COST
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(self.0.x(), 0, self.0.z())
+    }
+}
+
+/// Swim up towards the surface of the water.
+pub struct SwimUpMove;
+impl Move for SwimUpMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        if node.vertical_vel != VerticalVel::None
+            || !is_water_at(&node.pos, ctx.world)
+            || !is_water_at(&target, ctx.world)
+        {
+            return f32::INFINITY;
+        }
+        SWIM_UP_COST
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(0, 1, 0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,4 +624,284 @@ mod tests {
         assert!(!is_standable(&BlockPos::new(0, 0, 0), &world));
         assert!(!is_standable(&BlockPos::new(0, 2, 0), &world));
     }
+
+    #[test]
+    fn test_mining_and_placing_costs() {
+        let mut partial_world = PartialInstance::default();
+        let mut chunk_storage = ChunkStorage::default();
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut chunk_storage,
+        );
+        // we're standing at 0,1,0 and there's a stone wall in front of us
+        for pos in [
+            BlockPos::new(0, 0, 0),
+            BlockPos::new(1, 0, 0),
+            BlockPos::new(1, 1, 0),
+            BlockPos::new(1, 2, 0),
+        ] {
+            partial_world.chunks.set_block_state(
+                &pos,
+                azalea_registry::Block::Stone.into(),
+                &mut chunk_storage,
+            );
+        }
+        let world = chunk_storage.into();
+        let node = Node {
+            pos: BlockPos::new(0, 1, 0),
+            vertical_vel: VerticalVel::None,
+        };
+        let hotbar = [ItemSlot::new(azalea_registry::Item::Cobblestone, 64)];
+
+        let walk_only = PathfinderSettings::default();
+        let ctx = MoveContext {
+            world: &world,
+            settings: &walk_only,
+            hotbar: &hotbar,
+            border: world.world_border.bounds(),
+        };
+        assert_eq!(
+            ForwardMove(CardinalDirection::East).cost(&ctx, &node),
+            f32::INFINITY
+        );
+        assert_eq!(PillarMove.cost(&ctx, &node), f32::INFINITY);
+
+        let settings = PathfinderSettings {
+            allow_mining: true,
+            allow_placing: true,
+            ..Default::default()
+        };
+        let ctx = MoveContext {
+            world: &world,
+            settings: &settings,
+            hotbar: &hotbar,
+            border: world.world_border.bounds(),
+        };
+        // mining the wall is expensive since we don't have a pickaxe
+        let mine_stone_cost = MINE_BLOCK_COST + 150. * TICK_COST;
+        assert_eq!(
+            ForwardMove(CardinalDirection::East).cost(&ctx, &node),
+            WALK_ONE_BLOCK_COST + mine_stone_cost * 2.
+        );
+        // there's nothing to stand on to the south so we have to place a block
+        assert_eq!(
+            ForwardMove(CardinalDirection::South).cost(&ctx, &node),
+            WALK_ONE_BLOCK_COST + PLACE_BLOCK_COST
+        );
+        assert_eq!(PillarMove.cost(&ctx, &node), PILLAR_COST);
+        assert_eq!(DownMove.cost(&ctx, &node), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_edge_cost_after_block_update() {
+        let mut partial_world = PartialInstance::default();
+        let mut chunk_storage = ChunkStorage::default();
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut chunk_storage,
+        );
+        for x in 0..3 {
+            partial_world.chunks.set_block_state(
+                &BlockPos::new(x, 0, 0),
+                azalea_registry::Block::Stone.into(),
+                &mut chunk_storage,
+            );
+        }
+        let world: Instance = chunk_storage.into();
+        let settings = PathfinderSettings::default();
+        let moves = default_moves();
+        let from = Node {
+            pos: BlockPos::new(0, 1, 0),
+            vertical_vel: VerticalVel::None,
+        };
+        let to = Node {
+            pos: BlockPos::new(1, 1, 0),
+            vertical_vel: VerticalVel::None,
+        };
+
+        let ctx = MoveContext {
+            world: &world,
+            settings: &settings,
+            hotbar: &[],
+            border: world.world_border.bounds(),
+        };
+        assert_eq!(edge_cost(&ctx, &moves, &from, &to), WALK_ONE_BLOCK_COST);
+
+        // someone put a block in the way
+        world.chunks.set_block_state(
+            &BlockPos::new(1, 2, 0),
+            azalea_registry::Block::Stone.into(),
+        );
+        assert_eq!(edge_cost(&ctx, &moves, &from, &to), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_edge_cost_past_world_border() {
+        let mut partial_world = PartialInstance::default();
+        let mut chunk_storage = ChunkStorage::default();
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut chunk_storage,
+        );
+        for x in 0..3 {
+            partial_world.chunks.set_block_state(
+                &BlockPos::new(x, 0, 0),
+                azalea_registry::Block::Stone.into(),
+                &mut chunk_storage,
+            );
+        }
+        let mut world: Instance = chunk_storage.into();
+        // the border goes from x=-2 to x=2
+        world.world_border.set_size(4.);
+        let settings = PathfinderSettings::default();
+        let moves = default_moves();
+        let ctx = MoveContext {
+            world: &world,
+            settings: &settings,
+            hotbar: &[],
+            border: world.world_border.bounds(),
+        };
+        let at = |x| Node {
+            pos: BlockPos::new(x, 1, 0),
+            vertical_vel: VerticalVel::None,
+        };
+        assert_eq!(edge_cost(&ctx, &moves, &at(0), &at(1)), WALK_ONE_BLOCK_COST);
+        assert_eq!(edge_cost(&ctx, &moves, &at(1), &at(2)), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_parkour_and_fall_costs() {
+        let mut partial_world = PartialInstance::default();
+        let mut chunk_storage = ChunkStorage::default();
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut chunk_storage,
+        );
+        // two platforms at y=9 with a 2 block gap between them, and the ground
+        // at y=0
+        for pos in [
+            BlockPos::new(0, 9, 0),
+            BlockPos::new(3, 9, 0),
+            BlockPos::new(0, 0, 1),
+        ] {
+            partial_world.chunks.set_block_state(
+                &pos,
+                azalea_registry::Block::Stone.into(),
+                &mut chunk_storage,
+            );
+        }
+        let world = chunk_storage.into();
+        let node = Node {
+            pos: BlockPos::new(0, 10, 0),
+            vertical_vel: VerticalVel::None,
+        };
+        let settings = PathfinderSettings::default();
+        let ctx = MoveContext {
+            world: &world,
+            settings: &settings,
+            hotbar: &[],
+            border: world.world_border.bounds(),
+        };
+
+        assert_eq!(
+            ParkourMove(CardinalDirection::East, 2).cost(&ctx, &node),
+            WALK_ONE_BLOCK_COST * 3. + JUMP_COST + PARKOUR_GAP_COST * 2.
+        );
+        // we'd land in the gap
+        assert_eq!(
+            ParkourMove(CardinalDirection::East, 1).cost(&ctx, &node),
+            f32::INFINITY
+        );
+        // a 9 block fall does 6 damage, which isn't allowed by default
+        assert_eq!(
+            FallMove(CardinalDirection::South, 9).cost(&ctx, &node),
+            f32::INFINITY
+        );
+
+        let settings = PathfinderSettings {
+            max_fall_damage: 6,
+            ..Default::default()
+        };
+        let ctx = MoveContext {
+            world: &world,
+            settings: &settings,
+            hotbar: &[],
+            border: world.world_border.bounds(),
+        };
+        assert_eq!(
+            FallMove(CardinalDirection::South, 9).cost(&ctx, &node),
+            WALK_ONE_BLOCK_COST + DESCEND_COST * 9. + FALL_DAMAGE_COST * 6.
+        );
+        // there's nothing to land on
+        assert_eq!(
+            FallMove(CardinalDirection::South, 8).cost(&ctx, &node),
+            f32::INFINITY
+        );
+    }
+
+    #[test]
+    fn test_climb_and_swim_costs() {
+        let mut partial_world = PartialInstance::default();
+        let mut chunk_storage = ChunkStorage::default();
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut chunk_storage,
+        );
+        partial_world.chunks.set_block_state(
+            &BlockPos::new(0, 0, 0),
+            azalea_registry::Block::Stone.into(),
+            &mut chunk_storage,
+        );
+        for y in 1..4 {
+            partial_world.chunks.set_block_state(
+                &BlockPos::new(0, y, 0),
+                azalea_registry::Block::Ladder.into(),
+                &mut chunk_storage,
+            );
+        }
+        // a pool of water that's two blocks deep
+        for x in 2..4 {
+            for y in 0..2 {
+                partial_world.chunks.set_block_state(
+                    &BlockPos::new(x, y, 0),
+                    azalea_registry::Block::Water.into(),
+                    &mut chunk_storage,
+                );
+            }
+        }
+        let world = chunk_storage.into();
+        let settings = PathfinderSettings::default();
+        let ctx = MoveContext {
+            world: &world,
+            settings: &settings,
+            hotbar: &[],
+            border: world.world_border.bounds(),
+        };
+        let at = |x, y, z| Node {
+            pos: BlockPos::new(x, y, z),
+            vertical_vel: VerticalVel::None,
+        };
+
+        assert_eq!(ClimbUpMove.cost(&ctx, &at(0, 1, 0)), CLIMB_UP_COST);
+        assert_eq!(ClimbUpMove.cost(&ctx, &at(0, 3, 0)), f32::INFINITY);
+        assert_eq!(ClimbDownMove.cost(&ctx, &at(0, 4, 0)), CLIMB_DOWN_COST);
+        // ladders aren't something we can stand on
+        assert!(!is_standable(&BlockPos::new(0, 4, 0), &world));
+
+        assert_eq!(SwimUpMove.cost(&ctx, &at(2, 0, 0)), SWIM_UP_COST);
+        assert_eq!(
+            SwimMove(CardinalDirection::East).cost(&ctx, &at(2, 1, 0)),
+            SWIM_ONE_BLOCK_COST
+        );
+        // we only swim at the surface
+        assert_eq!(
+            SwimMove(CardinalDirection::East).cost(&ctx, &at(2, 0, 0)),
+            f32::INFINITY
+        );
+    }
 }
@@ -2,8 +2,9 @@
 //! re-exported here.
 
 pub use crate::{
-    bot::BotClientExt, container::ContainerClientExt, pathfinder::PathfinderClientExt,
-    ClientBuilder,
+    auto_tool::AutoToolClientExt, bot::BotClientExt, container::ContainerClientExt,
+    crafting::CraftingClientExt, furnace::FurnaceClientExt, merchant::MerchantClientExt,
+    pathfinder::PathfinderClientExt, ClientBuilder,
 };
 pub use azalea_client::{Account, Client, Event};
 // this is necessary to make the macros that reference bevy_ecs work
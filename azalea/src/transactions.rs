@@ -0,0 +1,528 @@
+//! Move items between a container and our inventory without having to know
+//! the slot indexes of every menu.
+//!
+//! Every operation is planned by simulating the clicks on a copy of our
+//! inventory, and then it waits for the server to send the slots it expects.
+
+use std::ops::RangeInclusive;
+
+use azalea_client::{inventory::InventoryComponent, PlayerAbilities};
+use azalea_inventory::{
+    item::MaxStackSizeExt,
+    operations::{ClickOperation, PickupClick, SwapClick},
+    ItemSlot, ItemSlotData, Menu,
+};
+use thiserror::Error;
+
+use crate::container::{ContainerHandle, MAX_TICKS_WAITING};
+
+impl ContainerHandle {
+    /// Move up to `count` of the item from our inventory into the container,
+    /// and return how many were moved. This fills stacks of the same item
+    /// before using empty slots, and returns
+    /// [`TransactionError::MissingItem`] if we don't have the item.
+    ///
+    /// ```
+    /// # use azalea::prelude::*;
+    /// # async fn example(mut bot: azalea::Client, pos: azalea::BlockPos) {
+    /// let Some(chest) = bot.open_container(pos).await else {
+    ///     return;
+    /// };
+    /// chest.put_items(azalea::Item::Cobblestone, 128).await.ok();
+    /// # }
+    /// ```
+    pub async fn put_items(
+        &self,
+        item: azalea_registry::Item,
+        count: u32,
+    ) -> Result<u32, TransactionError> {
+        let mut plan = self.plan()?;
+        let moved = plan.plan_put_items(item, count)?;
+        self.execute(plan).await?;
+        Ok(moved)
+    }
+
+    /// Move every stack in the container that matches the predicate into our
+    /// inventory, and return how many items were taken. This stops early if
+    /// our inventory fills up, and returns [`TransactionError::MissingItem`]
+    /// if nothing in the container matches.
+    ///
+    /// ```
+    /// # use azalea::prelude::*;
+    /// # async fn example(mut bot: azalea::Client, pos: azalea::BlockPos) {
+    /// let Some(chest) = bot.open_container(pos).await else {
+    ///     return;
+    /// };
+    /// chest
+    ///     .take_all(|item| item.kind == azalea::Item::Diamond)
+    ///     .await
+    ///     .ok();
+    /// # }
+    /// ```
+    pub async fn take_all(
+        &self,
+        predicate: impl Fn(&ItemSlotData) -> bool,
+    ) -> Result<u32, TransactionError> {
+        let mut plan = self.plan()?;
+        let taken = plan.plan_take_all(predicate)?;
+        self.execute(plan).await?;
+        Ok(taken)
+    }
+
+    /// Merge stacks of the same item and then sort them by item id, leaving
+    /// the empty slots at the end.
+    ///
+    /// This sorts the container's slots if it's a chest-like container, or
+    /// the main part of our inventory (not including the hotbar) if this is
+    /// our inventory.
+    pub async fn sort(&self) -> Result<(), TransactionError> {
+        let mut plan = self.plan()?;
+        plan.plan_sort()?;
+        self.execute(plan).await
+    }
+
+    /// Swap the item in the given slot with the one in the hotbar slot, which
+    /// is from 0 to 8 (or 40 for the offhand, which only works in our own
+    /// inventory).
+    ///
+    /// `slot` is a protocol index in the container's menu, so you'll
+    /// probably want something like [`Menu::player_slots_range`] to find it.
+    pub async fn swap_to_hotbar(
+        &self,
+        slot: usize,
+        hotbar_slot: u8,
+    ) -> Result<(), TransactionError> {
+        let mut plan = self.plan()?;
+        if plan.menu().slot(slot).is_none()
+            || plan
+                .inventory
+                .hotbar_index_to_menu_slot(hotbar_slot)
+                .is_none()
+        {
+            return Err(TransactionError::InvalidSlot);
+        }
+        plan.click(SwapClick {
+            source_slot: slot as u16,
+            target_slot: hotbar_slot,
+        });
+        self.execute(plan).await
+    }
+
+    /// Start planning clicks on a copy of our inventory.
+    fn plan(&self) -> Result<ClickPlan, TransactionError> {
+        let mut ecs = self.client.ecs.lock();
+        let (inventory, abilities) = self
+            .client
+            .query::<(&InventoryComponent, &PlayerAbilities)>(&mut ecs);
+        if inventory.id != self.id {
+            return Err(TransactionError::ContainerClosed);
+        }
+        Ok(ClickPlan::new(inventory, abilities))
+    }
+
+    /// Send the clicks in the plan, and wait until the server has sent us
+    /// every slot that we expect to change.
+    async fn execute(&self, plan: ClickPlan) -> Result<(), TransactionError> {
+        let expected_slots = plan.changed_slots();
+        if expected_slots.is_empty() {
+            return Ok(());
+        }
+
+        for operation in plan.clicks {
+            self.click(operation);
+        }
+        let confirmed = self
+            .wait_for_menu(MAX_TICKS_WAITING, |menu| {
+                expected_slots
+                    .iter()
+                    .all(|(i, expected)| menu.slot(*i) == Some(expected))
+            })
+            .await
+            .ok_or(TransactionError::ContainerClosed)?;
+        if !confirmed {
+            return Err(TransactionError::Rejected);
+        }
+        Ok(())
+    }
+}
+
+/// The slots that items can be stored in for chest-like containers, not
+/// including the player's inventory. Returns `None` for containers where the
+/// slots have a specific purpose, like furnaces.
+fn storage_slots(menu: &Menu) -> Option<RangeInclusive<usize>> {
+    match menu {
+        Menu::Generic9x1 { .. }
+        | Menu::Generic9x2 { .. }
+        | Menu::Generic9x3 { .. }
+        | Menu::Generic9x4 { .. }
+        | Menu::Generic9x5 { .. }
+        | Menu::Generic9x6 { .. }
+        | Menu::Generic3x3 { .. }
+        | Menu::Hopper { .. }
+        | Menu::ShulkerBox { .. } => Some(0..=*menu.player_slots_range().start() - 1),
+        _ => None,
+    }
+}
+
+/// A list of clicks and the inventory that we expect to have after doing
+/// them. Every click is simulated as it's added, so later clicks can be
+/// planned based on what the earlier ones did.
+struct ClickPlan {
+    /// The menu from before any of the clicks.
+    original_menu: Menu,
+    inventory: InventoryComponent,
+    abilities: PlayerAbilities,
+    clicks: Vec<ClickOperation>,
+}
+
+impl ClickPlan {
+    fn new(inventory: &InventoryComponent, abilities: &PlayerAbilities) -> Self {
+        Self {
+            original_menu: inventory.menu().clone(),
+            inventory: inventory.clone(),
+            abilities: abilities.clone(),
+            clicks: Vec::new(),
+        }
+    }
+
+    /// Plan the clicks for [`ContainerHandle::put_items`].
+    fn plan_put_items(
+        &mut self,
+        item: azalea_registry::Item,
+        count: u32,
+    ) -> Result<u32, TransactionError> {
+        let storage_slots =
+            storage_slots(self.menu()).ok_or(TransactionError::NotAStorageContainer)?;
+        let player_slots = self.menu().player_slots_range();
+
+        let mut left_to_move = count;
+        let mut found_item = false;
+        for source_slot in player_slots {
+            while left_to_move > 0 {
+                let ItemSlot::Present(source) = self.slot(source_slot) else {
+                    break;
+                };
+                if source.kind != item {
+                    break;
+                }
+                found_item = true;
+                let Some(target_slot) = self.slot_for(&source, storage_slots.clone()) else {
+                    break;
+                };
+                let moving = source.count.min(left_to_move.min(i8::MAX as u32) as i8);
+                let moved = self.move_items(source_slot, target_slot, moving);
+                if moved <= 0 {
+                    break;
+                }
+                left_to_move -= moved as u32;
+            }
+        }
+
+        let moved = count - left_to_move;
+        if moved == 0 {
+            return Err(if found_item {
+                TransactionError::NoSpace
+            } else {
+                TransactionError::MissingItem
+            });
+        }
+        Ok(moved)
+    }
+
+    /// Plan the clicks for [`ContainerHandle::take_all`].
+    fn plan_take_all(
+        &mut self,
+        predicate: impl Fn(&ItemSlotData) -> bool,
+    ) -> Result<u32, TransactionError> {
+        let storage_slots =
+            storage_slots(self.menu()).ok_or(TransactionError::NotAStorageContainer)?;
+        let player_slots = self.menu().player_slots_range();
+
+        let mut taken = 0;
+        let mut found_item = false;
+        for source_slot in storage_slots {
+            loop {
+                let ItemSlot::Present(source) = self.slot(source_slot) else {
+                    break;
+                };
+                if !predicate(&source) {
+                    break;
+                }
+                found_item = true;
+                let Some(target_slot) = self.slot_for(&source, player_slots.clone()) else {
+                    break;
+                };
+                let moved = self.move_items(source_slot, target_slot, source.count);
+                if moved <= 0 {
+                    break;
+                }
+                taken += moved as u32;
+            }
+        }
+
+        if taken == 0 {
+            return Err(if found_item {
+                TransactionError::NoSpace
+            } else {
+                TransactionError::MissingItem
+            });
+        }
+        Ok(taken)
+    }
+
+    /// Plan the clicks for [`ContainerHandle::sort`].
+    fn plan_sort(&mut self) -> Result<(), TransactionError> {
+        let slots = match self.menu() {
+            Menu::Player(_) => self.menu().player_slots_without_hotbar_range(),
+            menu => storage_slots(menu).ok_or(TransactionError::NotAStorageContainer)?,
+        };
+
+        // compact the stacks first so there's less to sort
+        for source_slot in slots.clone() {
+            for target_slot in *slots.start()..source_slot {
+                let ItemSlot::Present(source) = self.slot(source_slot) else {
+                    break;
+                };
+                let ItemSlot::Present(target) = self.slot(target_slot) else {
+                    continue;
+                };
+                if target.is_same_item_and_nbt(&source)
+                    && target.count < target.kind.max_stack_size()
+                {
+                    self.move_items(source_slot, target_slot, source.count);
+                }
+            }
+        }
+
+        // then do a selection sort, swapping by picking up one stack and
+        // clicking on the other
+        let sort_key = |slot: &ItemSlot| (slot.is_empty(), slot.kind() as u32);
+        for target_slot in slots.clone() {
+            let Some(source_slot) = (target_slot..=*slots.end())
+                .min_by_key(|&slot| sort_key(&self.slot(slot)))
+            else {
+                continue;
+            };
+            if sort_key(&self.slot(source_slot)) < sort_key(&self.slot(target_slot)) {
+                self.click(PickupClick::Left {
+                    slot: Some(source_slot as u16),
+                });
+                self.click(PickupClick::Left {
+                    slot: Some(target_slot as u16),
+                });
+                self.put_back_carried(source_slot);
+            }
+        }
+        Ok(())
+    }
+
+    fn menu(&self) -> &Menu {
+        self.inventory.menu()
+    }
+
+    fn slot(&self, index: usize) -> ItemSlot {
+        self.menu().slot(index).cloned().unwrap_or_default()
+    }
+
+    fn click(&mut self, operation: impl Into<ClickOperation>) {
+        let operation = operation.into();
+        self.inventory.simulate_click(&operation, &self.abilities);
+        self.clicks.push(operation);
+    }
+
+    /// Move up to `count` items from one slot to another, and return how many
+    /// were moved. Whatever isn't moved is put back in the source slot.
+    fn move_items(&mut self, source_slot: usize, target_slot: usize, count: i8) -> i8 {
+        let count_before = self.slot(target_slot).count();
+        // right clicking picks up half of the stack (rounded up), which might save us
+        // from having to put a lot of items back
+        let half = (self.slot(source_slot).count() + 1) / 2;
+        if count <= half {
+            self.click(PickupClick::Right {
+                slot: Some(source_slot as u16),
+            });
+        } else {
+            self.click(PickupClick::Left {
+                slot: Some(source_slot as u16),
+            });
+        }
+
+        // right clicking puts down one item at a time, so either put the extra items
+        // back one at a time and then put down the rest, or put down the items that
+        // we're moving one at a time, whichever takes fewer clicks
+        let extra = self.inventory.carried.count() - count;
+        if extra <= 0 {
+            self.click(PickupClick::Left {
+                slot: Some(target_slot as u16),
+            });
+        } else if extra < count {
+            for _ in 0..extra {
+                self.click(PickupClick::Right {
+                    slot: Some(source_slot as u16),
+                });
+            }
+            self.click(PickupClick::Left {
+                slot: Some(target_slot as u16),
+            });
+        } else {
+            for _ in 0..count {
+                self.click(PickupClick::Right {
+                    slot: Some(target_slot as u16),
+                });
+            }
+        }
+        self.put_back_carried(source_slot);
+        self.slot(target_slot).count() - count_before
+    }
+
+    /// Put the item we're holding with the cursor in the slot, which should
+    /// be empty or have the same item.
+    fn put_back_carried(&mut self, slot: usize) {
+        if self.inventory.carried.is_present() {
+            self.click(PickupClick::Left {
+                slot: Some(slot as u16),
+            });
+        }
+    }
+
+    /// The first slot in the range where at least one of the item can go,
+    /// preferring stacks of the same item over empty slots.
+    fn slot_for(&self, item: &ItemSlotData, slots: RangeInclusive<usize>) -> Option<usize> {
+        let max_stack_size = item.kind.max_stack_size();
+        slots
+            .clone()
+            .find(|&i| match self.slot(i) {
+                ItemSlot::Present(slot) => {
+                    slot.is_same_item_and_nbt(item) && slot.count < max_stack_size
+                }
+                ItemSlot::Empty => false,
+            })
+            .or_else(|| slots.into_iter().find(|&i| self.slot(i).is_empty()))
+    }
+
+    /// The slots that are different after doing the clicks, and what we
+    /// expect them to be.
+    fn changed_slots(&self) -> Vec<(usize, ItemSlot)> {
+        let original_slots = self.original_menu.slots();
+        let slots = self.menu().slots();
+        original_slots
+            .into_iter()
+            .zip(slots)
+            .enumerate()
+            .filter(|(_, (original, new))| original != new)
+            .map(|(i, (_, new))| (i, new))
+            .collect()
+    }
+}
+
+/// The reason that we couldn't move items in a container.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionError {
+    #[error("The container was closed")]
+    ContainerClosed,
+    #[error("The container isn't one that items can be stored in")]
+    NotAStorageContainer,
+    #[error("The item isn't in the inventory")]
+    MissingItem,
+    #[error("There's no space for the items")]
+    NoSpace,
+    #[error("That slot isn't in the menu")]
+    InvalidSlot,
+    #[error("The server didn't move the items the way we expected")]
+    Rejected,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azalea_registry::{Item, MenuKind};
+
+    /// A plan for an open chest with the given items in it and in our
+    /// inventory. Slots 0 to 26 are the chest and 27 to 62 are ours.
+    fn chest_plan(items: &[(usize, ItemSlot)]) -> ClickPlan {
+        let mut inventory = InventoryComponent {
+            id: 1,
+            container_menu: Some(Menu::from_kind(MenuKind::Generic9x3)),
+            ..Default::default()
+        };
+        for (i, item) in items {
+            *inventory.menu_mut().slot_mut(*i).unwrap() = item.clone();
+        }
+        ClickPlan::new(&inventory, &PlayerAbilities::default())
+    }
+
+    #[test]
+    fn test_put_items_partially() {
+        let mut plan = chest_plan(&[
+            (0, ItemSlot::new(Item::Stone, 60)),
+            (27, ItemSlot::new(Item::Stone, 10)),
+        ]);
+        assert_eq!(plan.plan_put_items(Item::Stone, 7), Ok(7));
+        // the existing stack is filled before the empty slot is used
+        assert_eq!(plan.slot(0), ItemSlot::new(Item::Stone, 64));
+        assert_eq!(plan.slot(1), ItemSlot::new(Item::Stone, 3));
+        assert_eq!(plan.slot(27), ItemSlot::new(Item::Stone, 3));
+        assert_eq!(plan.inventory.carried, ItemSlot::Empty);
+
+        assert_eq!(
+            plan.plan_put_items(Item::Diamond, 1),
+            Err(TransactionError::MissingItem)
+        );
+    }
+
+    #[test]
+    fn test_put_items_uses_few_clicks() {
+        let mut plan = chest_plan(&[(27, ItemSlot::new(Item::Stone, 64))]);
+        assert_eq!(plan.plan_put_items(Item::Stone, 30), Ok(30));
+        assert_eq!(plan.slot(0), ItemSlot::new(Item::Stone, 30));
+        assert_eq!(plan.slot(27), ItemSlot::new(Item::Stone, 34));
+        // pick up half, put two of them back, and put down the rest
+        assert_eq!(plan.clicks.len(), 4);
+    }
+
+    #[test]
+    fn test_take_all_stops_when_full() {
+        let mut items = vec![
+            (0, ItemSlot::new(Item::Diamond, 64)),
+            (1, ItemSlot::new(Item::Diamond, 64)),
+        ];
+        // every slot in our inventory is full except for one
+        items.extend((28..=62).map(|i| (i, ItemSlot::new(Item::Dirt, 64))));
+        let mut plan = chest_plan(&items);
+
+        assert_eq!(
+            plan.plan_take_all(|item| item.kind == Item::Stone),
+            Err(TransactionError::MissingItem)
+        );
+        assert_eq!(
+            plan.plan_take_all(|item| item.kind == Item::Diamond),
+            Ok(64)
+        );
+        assert_eq!(plan.slot(0), ItemSlot::Empty);
+        assert_eq!(plan.slot(1), ItemSlot::new(Item::Diamond, 64));
+        assert_eq!(plan.slot(27), ItemSlot::new(Item::Diamond, 64));
+        assert_eq!(
+            plan.plan_take_all(|item| item.kind == Item::Diamond),
+            Err(TransactionError::NoSpace)
+        );
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut plan = chest_plan(&[
+            (0, ItemSlot::new(Item::Dirt, 5)),
+            (2, ItemSlot::new(Item::Diamond, 1)),
+            (3, ItemSlot::new(Item::Dirt, 10)),
+            (5, ItemSlot::new(Item::Stone, 3)),
+            (27, ItemSlot::new(Item::Diamond, 2)),
+        ]);
+        assert_eq!(plan.plan_sort(), Ok(()));
+        assert_eq!(plan.slot(0), ItemSlot::new(Item::Stone, 3));
+        assert_eq!(plan.slot(1), ItemSlot::new(Item::Dirt, 15));
+        assert_eq!(plan.slot(2), ItemSlot::new(Item::Diamond, 1));
+        assert!((3..=26).all(|i| plan.slot(i).is_empty()));
+        // our inventory isn't touched
+        assert_eq!(plan.slot(27), ItemSlot::new(Item::Diamond, 2));
+        assert_eq!(plan.inventory.carried, ItemSlot::Empty);
+    }
+}
@@ -13,7 +13,7 @@ BLOCKS_RS_DIR = get_dir_location('../azalea-block/src/generated.rs')
 # - Block: Has properties and states.
 
 
-def generate_blocks(blocks_burger: dict, blocks_report: dict, ordered_blocks: list[str], mappings: Mappings):
+def generate_blocks(blocks_burger: dict, blocks_report: dict, blocks_pixlyzer: dict, ordered_blocks: list[str], mappings: Mappings):
     with open(BLOCKS_RS_DIR, 'r') as f:
         existing_code = f.read().splitlines()
 
@@ -134,9 +134,17 @@ def generate_blocks(blocks_burger: dict, blocks_report: dict, ordered_blocks: li
         else:
             properties_code += '\n        }'
 
-        # TODO: use burger to generate the blockbehavior
+        # TODO: use burger to generate the rest of the blockbehavior
+        block_data_pixlyzer = blocks_pixlyzer.get(f'minecraft:{block_id}', {})
+        behavior_code = 'BlockBehavior::default()'
+        hardness = block_data_pixlyzer.get('hardness', 0)
+        if hardness != 0:
+            behavior_code += f'.strength({format_float(hardness)})'
+        if block_data_pixlyzer.get('requires_tool', False):
+            behavior_code += '.requires_correct_tool_for_drops()'
+
         new_make_block_states_macro_code.append(
-            f'        {block_id} => BlockBehavior::default(), {properties_code},')
+            f'        {block_id} => {behavior_code}, {properties_code},')
 
     new_make_block_states_macro_code.append('    }')
     new_make_block_states_macro_code.append('}')
@@ -211,3 +219,10 @@ def get_property_struct_name(property: Optional[dict], block_data_burger: dict,
         property_name = 'Wire' + to_camel_case(property_name)
 
     return property_name
+
+
+def format_float(value: float) -> str:
+    '''Format a float the way rustfmt likes it, like `1.` or `1.5`.'''
+    if value == int(value):
+        return f'{int(value)}.'
+    return str(value)